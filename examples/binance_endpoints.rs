@@ -7,7 +7,7 @@ use binance::config::Config;
 use binance::errors::Error as BinanceLibError;
 use binance::general::*;
 use binance::market::*;
-use binance::rest_model::{OrderSide, OrderType, SymbolPrice, TimeInForce};
+use binance::rest_model::{KlineInterval, OrderSide, OrderType, SymbolPrice, TimeInForce};
 use env_logger::Builder;
 
 #[tokio::main]
@@ -68,7 +68,7 @@ async fn account() {
 
     let limit_buy = OrderRequest {
         symbol: symbol.to_string(),
-        quantity: Some(0.001),
+        quantity: Some("0.001".parse().unwrap()),
         price: Some(price),
         order_type: OrderType::Limit,
         side: OrderSide::Buy,
@@ -82,7 +82,7 @@ async fn account() {
 
     let market_buy = OrderRequest {
         symbol: symbol.to_string(),
-        quantity: Some(0.001),
+        quantity: Some("0.001".parse().unwrap()),
         order_type: OrderType::Market,
         side: OrderSide::Buy,
         ..OrderRequest::default()
@@ -94,7 +94,7 @@ async fn account() {
 
     let limit_sell = OrderRequest {
         symbol: symbol.to_string(),
-        quantity: Some(0.001),
+        quantity: Some("0.001".parse().unwrap()),
         price: Some(price),
         order_type: OrderType::Limit,
         side: OrderSide::Sell,
@@ -108,7 +108,7 @@ async fn account() {
 
     let market_sell = OrderRequest {
         symbol: symbol.to_string(),
-        quantity: Some(0.001),
+        quantity: Some("0.001".parse().unwrap()),
         order_type: OrderType::Market,
         side: OrderSide::Sell,
         ..OrderRequest::default()
@@ -201,7 +201,7 @@ async fn market_data() {
     }
 
     // last 10 5min klines (candlesticks) for a symbol:
-    match market.get_klines("BNBETH", "5m", 10, None, None).await {
+    match market.get_klines("BNBETH", KlineInterval::Minutes5, 10, None, None).await {
         Ok(answer) => info!("{:?}", answer),
         Err(e) => error!("Error: {e}"),
     }
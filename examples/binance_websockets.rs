@@ -2,6 +2,7 @@
 extern crate tokio;
 
 use binance::api::*;
+use binance::rest_model::KlineInterval;
 use binance::userstream::*;
 use binance::websockets::*;
 use binance::ws_model::{CombinedStreamEvent, WebsocketEvent, WebsocketEventUntag};
@@ -168,7 +169,7 @@ async fn all_trades_websocket(logger_tx: UnboundedSender<WebsocketEvent>) {
 #[allow(dead_code)]
 async fn kline_websocket(logger_tx: UnboundedSender<WebsocketEvent>) {
     let keep_running = AtomicBool::new(true);
-    let kline = kline_stream("ethbtc", "1m");
+    let kline = kline_stream("ethbtc", KlineInterval::Minutes1);
     let mut web_socket: WebSockets<'_, WebsocketEvent> = WebSockets::new(|event: WebsocketEvent| {
         logger_tx.send(event.clone()).unwrap();
         if let WebsocketEvent::Kline(kline_event) = event {
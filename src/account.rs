@@ -1,5 +1,6 @@
 use crate::client::*;
 use crate::errors::*;
+use crate::filters::OrderFilters;
 use crate::rest_model::*;
 use crate::util::*;
 
@@ -30,19 +31,22 @@ pub struct OrderRequest {
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub time_in_force: Option<TimeInForce>,
-    pub quantity: Option<f64>,
-    pub quote_order_qty: Option<f64>,
-    pub price: Option<f64>,
+    pub quantity: Option<Amount>,
+    pub quote_order_qty: Option<Amount>,
+    pub price: Option<Amount>,
     /// A unique id for the order, automatically generated if not sent.
     pub new_client_order_id: Option<String>,
     /// Used with stop loss, stop loss limit, take profit and take profit limit order types.
-    pub stop_price: Option<f64>,
+    pub stop_price: Option<Amount>,
     /// Used with limit, stop loss limit and take profit limit to create an iceberg order.
-    pub iceberg_qty: Option<f64>,
+    pub iceberg_qty: Option<Amount>,
     /// Set the response json, market and limit default to full others to ack.
     pub new_order_resp_type: Option<OrderResponse>,
     /// Cannot be greater than 60000
     pub recv_window: Option<u64>,
+    /// Filters of the symbol the price and quantity are validated against, and rounded to, before sending
+    #[serde(skip)]
+    pub filters: Option<OrderFilters>,
 }
 
 impl OrderRequest {
@@ -54,6 +58,23 @@ impl OrderRequest {
         }
         Ok(())
     }
+
+    fn apply_filters(&mut self) -> Result<()> {
+        if let Some(filters) = &self.filters {
+            let market = matches!(
+                self.order_type,
+                OrderType::Market | OrderType::StopLoss | OrderType::TakeProfit
+            );
+            filters.apply(
+                &self.side,
+                market,
+                &mut self.price,
+                &mut self.stop_price,
+                &mut self.quantity,
+            )?;
+        }
+        Ok(())
+    }
 }
 
 /// Order Cancellation Request
@@ -83,15 +104,15 @@ pub struct CancelReplaceRequest {
     pub order_type: OrderType,
     pub cancel_replace_mode: CancelReplaceMode,
     pub time_in_force: Option<TimeInForce>,
-    pub quantity: Option<f64>,
-    pub quote_order_qty: Option<f64>,
-    pub price: Option<f64>,
+    pub quantity: Option<Amount>,
+    pub quote_order_qty: Option<Amount>,
+    pub price: Option<Amount>,
     pub cancel_new_client_order_id: Option<String>,
     pub cancel_orig_client_order_id: Option<String>,
     pub cancel_order_id: Option<u64>,
     pub new_client_order_id: Option<String>,
-    pub stop_price: Option<f64>,
-    pub iceberg_qty: Option<f64>,
+    pub stop_price: Option<Amount>,
+    pub iceberg_qty: Option<Amount>,
     pub new_order_resp_type: Option<OrderResponse>,
     /// Cannot be greater than 60000
     pub recv_window: Option<u64>,
@@ -291,14 +312,15 @@ impl Account {
     /// Place an order
     /// Returns the Transaction if Ok
     /// This methods validates the order request before sending, making sure it complies with Binance rules
+    /// and with the `filters` of the symbol when they are set
     /// # Examples
     /// ```rust,no_run
     /// use binance::{api::*, account::*, config::*, rest_model::*};
     /// let account: Account = Binance::new_with_env(&Config::testnet());
     /// let limit_buy = OrderRequest {
     ///         symbol: "BTCUSDT".to_string(),
-    ///         quantity: Some(10.0),
-    ///         price: Some(0.014000),
+    ///         quantity: Some("10".parse().unwrap()),
+    ///         price: Some("0.014".parse().unwrap()),
     ///         order_type: OrderType::Limit,
     ///         side: OrderSide::Buy,
     ///         time_in_force: Some(TimeInForce::FOK),
@@ -307,8 +329,9 @@ impl Account {
     /// let transaction = tokio_test::block_on(account.place_order(limit_buy));
     /// assert!(transaction.is_ok(), "{:?}", transaction);
     /// ```
-    pub async fn place_order(&self, order: OrderRequest) -> Result<Transaction> {
+    pub async fn place_order(&self, mut order: OrderRequest) -> Result<Transaction> {
         order.valid()?;
+        order.apply_filters()?;
         let recv_window = order.recv_window.unwrap_or(self.recv_window);
         let request = build_signed_request_p(order, recv_window)?;
         self.client.post_signed(API_V3_ORDER, &request).await
@@ -324,8 +347,8 @@ impl Account {
     /// let account: Account = Binance::new_with_env(&Config::testnet());
     /// let limit_buy = OrderRequest {
     ///         symbol: "BTCUSDT".to_string(),
-    ///         quantity: Some(10.0),
-    ///         price: Some(0.014000),
+    ///         quantity: Some("10".parse().unwrap()),
+    ///         price: Some("0.014".parse().unwrap()),
     ///         order_type: OrderType::Limit,
     ///         side: OrderSide::Buy,
     ///         time_in_force: Some(TimeInForce::FOK),
@@ -334,8 +357,9 @@ impl Account {
     /// let resp = tokio_test::block_on(account.place_test_order(limit_buy));
     /// assert!(resp.is_ok(), "{:?}", resp);
     /// ```
-    pub async fn place_test_order(&self, order: OrderRequest) -> Result<TestResponse> {
+    pub async fn place_test_order(&self, mut order: OrderRequest) -> Result<TestResponse> {
         order.valid()?;
+        order.apply_filters()?;
         let recv_window = order.recv_window.unwrap_or(self.recv_window);
         let request = build_signed_request_p(order, recv_window)?;
         self.client.post_signed(API_V3_ORDER_TEST, &request).await
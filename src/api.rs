@@ -1,8 +1,13 @@
 use crate::account::*;
 use crate::client::*;
 use crate::config::Config;
+#[cfg(feature = "futures_api")]
+use crate::futures::futures_type::FuturesType;
+#[cfg(feature = "futures_api")]
+use crate::futures::router::FuturesRoute;
 use crate::general::*;
 use crate::market::*;
+use crate::signer::signer_or_invalid;
 use crate::spot::*;
 use crate::userstream::*;
 use crate::Spot;
@@ -24,9 +29,31 @@ pub trait Binance: Sized {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self;
 }
 
+fn spot_client(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Client {
+    let signer = signer_or_invalid(config.key_type, secret_key.as_deref().unwrap_or_default());
+    Client::new_with_signer(api_key, signer, config.rest_api_endpoint.clone(), config.timeout)
+        .with_rate_limiter(config.rate_limiter.clone())
+        .with_retry_policy(config.retry_policy.clone())
+        .with_time_sync(config.time_sync.clone(), "/api/v3/time")
+}
+
+#[cfg(feature = "futures_api")]
+fn futures_client<T: FuturesType>(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Client {
+    let host = if config.futures_rest_api_endpoint.is_empty() {
+        T::endpoint()
+    } else {
+        config.futures_rest_api_endpoint.clone()
+    };
+    let signer = signer_or_invalid(config.key_type, secret_key.as_deref().unwrap_or_default());
+    Client::new_with_signer(api_key, signer, host, config.timeout)
+        .with_rate_limiter(T::rate_limiter(config))
+        .with_retry_policy(config.retry_policy.clone())
+        .with_time_sync(config.time_sync.clone(), &T::router()(FuturesRoute::Time))
+}
+
 impl Binance for Spot {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
-        let client = Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout);
+        let client = spot_client(api_key, secret_key, config);
         Self {
             account: account::Account {
                 client: client.clone(),
@@ -52,7 +79,7 @@ impl Binance for Spot {
 impl Binance for General {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> General {
         General {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: spot_client(api_key, secret_key, config),
         }
     }
 }
@@ -60,7 +87,7 @@ impl Binance for General {
 impl Binance for Account {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Account {
         Account {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: spot_client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -70,7 +97,7 @@ impl Binance for Account {
 impl Binance for crate::savings::Savings {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: spot_client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -79,16 +106,26 @@ impl Binance for crate::savings::Savings {
 impl Binance for Market {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Market {
         Market {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: spot_client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
 }
 
+impl Binance for crate::backfill::Backfill {
+    fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
+        Self {
+            client: spot_client(api_key, secret_key, config),
+            budget: crate::backfill::RequestBudget::default(),
+            page_size: crate::backfill::MAX_PAGE_SIZE,
+        }
+    }
+}
+
 impl Binance for UserStream {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> UserStream {
         UserStream {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: spot_client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -100,14 +137,8 @@ where
     T: FuturesType,
 {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
-        let host = if config.futures_rest_api_endpoint == "" {
-            T::endpoint()
-        } else {
-            config.futures_rest_api_endpoint.clone()
-        };
-
         Self {
-            client: Client::new(api_key, secret_key, host, config.timeout),
+            client: futures_client::<T>(api_key, secret_key, config),
             recv_window: config.recv_window,
             router: T::router(),
             _marker: std::marker::PhantomData,
@@ -121,14 +152,8 @@ where
     T: FuturesType,
 {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
-        let host = if config.futures_rest_api_endpoint == "" {
-            T::endpoint()
-        } else {
-            config.futures_rest_api_endpoint.clone()
-        };
-
         Self {
-            client: Client::new(api_key, secret_key, host, config.timeout),
+            client: futures_client::<T>(api_key, secret_key, config),
             router: T::router(),
             _marker: std::marker::PhantomData,
         }
@@ -141,13 +166,8 @@ where
     T: FuturesType,
 {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
-        let host = if config.futures_rest_api_endpoint == "" {
-            T::endpoint()
-        } else {
-            config.futures_rest_api_endpoint.clone()
-        };
         Self {
-            client: Client::new(api_key, secret_key, host, config.timeout),
+            client: futures_client::<T>(api_key, secret_key, config),
             recv_window: config.recv_window,
             router: T::router(),
             _marker: std::marker::PhantomData,
@@ -156,19 +176,30 @@ where
 }
 
 #[cfg(feature = "futures_api")]
-impl<T> Binance for crate::futures::account::FuturesAccount<T>
+impl<T> Binance for crate::backfill::FuturesBackfill<T>
 where
     T: FuturesType,
 {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
-        let host = if config.futures_rest_api_endpoint == "" {
-            T::endpoint()
-        } else {
-            config.futures_rest_api_endpoint.clone()
-        };
+        Self {
+            client: futures_client::<T>(api_key, secret_key, config),
+            recv_window: config.recv_window,
+            router: T::router(),
+            budget: crate::backfill::RequestBudget::default(),
+            page_size: crate::backfill::MAX_PAGE_SIZE,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
 
+#[cfg(feature = "futures_api")]
+impl<T> Binance for crate::futures::account::FuturesAccount<T>
+where
+    T: FuturesType,
+{
+    fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(api_key, secret_key, host, config.timeout),
+            client: futures_client::<T>(api_key, secret_key, config),
             recv_window: config.recv_window,
             router: T::router(),
             _marker: std::marker::PhantomData,
@@ -180,7 +211,7 @@ where
 impl Binance for crate::margin::Margin {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: spot_client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -190,7 +221,7 @@ impl Binance for crate::margin::Margin {
 impl Binance for crate::wallet::Wallet {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: spot_client(api_key, secret_key, config),
             recv_window: config.recv_window,
             binance_us_api: config.binance_us_api,
         }
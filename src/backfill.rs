@@ -0,0 +1,676 @@
+//! Backfill of historical market data.
+//!
+//! Market data endpoints return at most one page of rows, e.g. 1000 klines. A backfill streams every row of
+//! a time or id range, querying the pages one after the other and dropping the rows repeated at the page
+//! boundaries. Streams of many symbols run concurrently, their requests share a [`RequestBudget`].
+//!
+//! ```rust,no_run
+//! use binance::api::Binance;
+//! use binance::backfill::{for_symbols, Backfill, RequestBudget};
+//! use binance::rest_model::KlineInterval;
+//! use futures::TryStreamExt;
+//!
+//! # async fn run() -> binance::errors::Result<()> {
+//! let backfill: Backfill = Binance::new(None, None);
+//! let backfill = backfill.set_budget(RequestBudget::new(4).set_weight_per_minute(3000));
+//! let klines: Vec<_> = for_symbols(["BTCUSDT", "ETHUSDT"], |symbol| {
+//!     backfill.klines(symbol, KlineInterval::Minutes1, 1_672_531_200_000, 1_675_209_600_000)
+//! })
+//! .try_collect()
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use tokio::sync::Semaphore;
+
+use crate::client::Client;
+use crate::errors::*;
+use crate::futures::futures_type::FuturesType;
+use crate::futures::rest_model::{AggTrade as FuturesAggTrade, ContractType, Trade as FuturesTrade};
+use crate::futures::router::FuturesRoute;
+use crate::rate_limit::{RateLimitMode, RateLimiter};
+use crate::rest_model::{
+    AggTrade, KlineInterval, KlineRow, KlineSummary, MarketTrade, RateLimit, RateLimitInterval, RateLimitType,
+};
+use crate::util::{build_request, build_signed_request};
+
+/// Largest page of the market data endpoints
+pub static MAX_PAGE_SIZE: u16 = 1000;
+
+/// Time span of an aggregate trades query by time
+static AGG_TRADES_WINDOW: u64 = 3_600_000;
+
+/// Rows of a backfill
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryRange {
+    /// Rows from `start` to `end`, times in ms inclusive
+    Time { start: u64, end: u64 },
+    /// Rows from id `from` to id `to` inclusive, up to the latest row without `to`
+    Ids { from: u64, to: Option<u64> },
+}
+
+impl HistoryRange {
+    fn after_start<R: HistoryRow>(&self, row: &R) -> bool {
+        match self {
+            HistoryRange::Time { start, .. } => row.time() >= *start,
+            HistoryRange::Ids { from, .. } => row.id().is_none_or(|id| id >= *from),
+        }
+    }
+
+    fn before_end<R: HistoryRow>(&self, row: &R) -> bool {
+        match self {
+            HistoryRange::Time { end, .. } => row.time() <= *end,
+            HistoryRange::Ids { to, .. } => match (to, row.id()) {
+                (Some(to), Some(id)) => id <= *to,
+                _ => true,
+            },
+        }
+    }
+}
+
+/// Row of a paginated history
+pub trait HistoryRow {
+    /// Time of the row in ms, the open time of klines
+    fn time(&self) -> u64;
+
+    /// Id of the row, if it has one
+    fn id(&self) -> Option<u64> { None }
+}
+
+impl HistoryRow for KlineSummary {
+    fn time(&self) -> u64 { self.open_time as u64 }
+}
+
+impl HistoryRow for KlineRow {
+    fn time(&self) -> u64 { self.open_time() as u64 }
+}
+
+impl HistoryRow for AggTrade {
+    fn time(&self) -> u64 { self.time }
+
+    fn id(&self) -> Option<u64> { Some(self.agg_id) }
+}
+
+impl HistoryRow for MarketTrade {
+    fn time(&self) -> u64 { self.time }
+
+    fn id(&self) -> Option<u64> { Some(self.id) }
+}
+
+impl HistoryRow for FuturesAggTrade {
+    fn time(&self) -> u64 { self.time }
+
+    fn id(&self) -> Option<u64> { Some(self.agg_id) }
+}
+
+impl HistoryRow for FuturesTrade {
+    fn time(&self) -> u64 { self.time }
+
+    fn id(&self) -> Option<u64> { Some(self.id) }
+}
+
+/// Budget of the requests of backfills, clones share the same budget
+#[derive(Clone, Debug)]
+pub struct RequestBudget {
+    concurrency: Arc<Semaphore>,
+    limiter: Option<RateLimiter>,
+}
+
+impl Default for RequestBudget {
+    fn default() -> Self { Self::new(4) }
+}
+
+impl RequestBudget {
+    /// At most `max_concurrent` requests in flight
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            concurrency: Arc::new(Semaphore::new(max_concurrent.max(1))),
+            limiter: None,
+        }
+    }
+
+    /// Delays the requests which would use more than `weight` per minute, as weighted by Binance
+    pub fn set_weight_per_minute(mut self, weight: u32) -> Self {
+        self.limiter = Some(RateLimiter::with_limits(
+            RateLimitMode::Delay,
+            &[RateLimit {
+                interval: RateLimitInterval::Minute,
+                rate_limit_type: RateLimitType::RequestWeight,
+                interval_num: 1,
+                limit: weight.min(i32::MAX as u32) as i32,
+            }],
+        ));
+        self
+    }
+
+    async fn get<T: DeserializeOwned>(&self, client: &Client, endpoint: &str, query: &str, signed: bool) -> Result<T> {
+        let _permit = self.concurrency.acquire().await.expect("the semaphore is never closed");
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire(&Method::GET, endpoint, query).await?;
+        }
+        if signed {
+            client.get_signed(endpoint, query).await
+        } else {
+            client.get(endpoint, Some(query)).await
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Cursor {
+    Time(u64),
+    Id(u64),
+}
+
+/// Paginated query of a history
+struct Pager {
+    client: Client,
+    budget: RequestBudget,
+    endpoint: String,
+    params: Vec<(&'static str, String)>,
+    /// Signs the queries with this receive window, for endpoints requiring an API key
+    recv_window: Option<u64>,
+    page_size: u16,
+    range: HistoryRange,
+    /// Longest time span of a query by time, the next span is queried when one is empty
+    window: Option<u64>,
+    cursor: Option<Cursor>,
+    /// Id, or time without ids, of the last row streamed
+    last_key: Option<u64>,
+}
+
+impl Pager {
+    fn new(client: &Client, budget: &RequestBudget, endpoint: String, range: HistoryRange) -> Self {
+        Self {
+            client: client.clone(),
+            budget: budget.clone(),
+            endpoint,
+            params: vec![],
+            recv_window: None,
+            page_size: MAX_PAGE_SIZE,
+            range,
+            window: None,
+            cursor: None,
+            last_key: None,
+        }
+    }
+
+    fn param(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.params.push((name, value.to_string()));
+        self
+    }
+
+    fn page_size(mut self, page_size: u16, max: u16) -> Self {
+        self.page_size = page_size.clamp(1, max);
+        self
+    }
+
+    fn signed(mut self, recv_window: u64) -> Self {
+        self.recv_window = Some(recv_window);
+        self
+    }
+
+    fn window(mut self, window: u64) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    fn end_time(&self) -> Option<u64> {
+        match self.range {
+            HistoryRange::Time { end, .. } => Some(end),
+            HistoryRange::Ids { .. } => None,
+        }
+    }
+
+    fn query(&self, cursor: Cursor) -> Result<String> {
+        let cursor = match cursor {
+            Cursor::Time(time) => {
+                let end = self.end_time().unwrap_or(u64::MAX);
+                let end = self
+                    .window
+                    .map_or(end, |window| end.min(time.saturating_add(window - 1)));
+                vec![("startTime", time.to_string()), ("endTime", end.to_string())]
+            }
+            Cursor::Id(id) => vec![("fromId", id.to_string())],
+        };
+        let params = self
+            .params
+            .iter()
+            .cloned()
+            .chain(cursor)
+            .chain([("limit", self.page_size.to_string())]);
+        match self.recv_window {
+            Some(recv_window) => build_signed_request(params, recv_window),
+            None => Ok(build_request(params)),
+        }
+    }
+
+    fn into_stream<R>(mut self) -> BoxStream<'static, Result<R>>
+    where
+        R: HistoryRow + DeserializeOwned + Send + 'static,
+    {
+        self.cursor = Some(match self.range {
+            HistoryRange::Time { start, .. } => Cursor::Time(start),
+            HistoryRange::Ids { from, .. } => Cursor::Id(from),
+        });
+        stream::try_unfold(self, |mut pager| async move {
+            let rows = pager.next_page::<R>().await?;
+            Ok::<_, Error>(rows.map(|rows| (rows, pager)))
+        })
+        .map_ok(|rows| stream::iter(rows.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Queries pages until one has new rows in the range, `None` once the range is exhausted
+    async fn next_page<R>(&mut self) -> Result<Option<Vec<R>>>
+    where
+        R: HistoryRow + DeserializeOwned,
+    {
+        let key = |row: &R| row.id().unwrap_or_else(|| row.time());
+        while let Some(current) = self.cursor {
+            let query = self.query(current)?;
+            let rows: Vec<R> = self
+                .budget
+                .get(&self.client, &self.endpoint, &query, self.recv_window.is_some())
+                .await?;
+            let full = rows.len() >= self.page_size as usize;
+            self.cursor = match (current, rows.last(), self.window) {
+                // nothing in this span, the next one may have rows
+                (Cursor::Time(time), None, Some(window)) => Some(time.saturating_add(window))
+                    .filter(|next| *next <= self.end_time().unwrap_or(u64::MAX))
+                    .map(Cursor::Time),
+                (_, None, _) => None,
+                // a span may be exhausted before the end of the range, continue after its last row
+                (Cursor::Time(_), Some(row), Some(_)) => Some(next_cursor(row)),
+                (_, Some(row), _) if full => Some(next_cursor(row)),
+                _ => None,
+            };
+            let received = rows.len();
+            let rows: Vec<R> = rows
+                .into_iter()
+                .filter(|row| self.range.after_start(row) && self.last_key.is_none_or(|last| key(row) > last))
+                .collect();
+            let new = rows.len();
+            let rows: Vec<R> = rows.into_iter().take_while(|row| self.range.before_end(row)).collect();
+            if rows.len() < new || (received > 0 && new == 0) {
+                // past the end of the range, or a page of duplicates which would be queried again
+                self.cursor = None;
+            }
+            if let Some(row) = rows.last() {
+                self.last_key = Some(key(row));
+                return Ok(Some(rows));
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn next_cursor<R: HistoryRow>(row: &R) -> Cursor {
+    match row.id() {
+        Some(id) => Cursor::Id(id + 1),
+        None => Cursor::Time(row.time() + 1),
+    }
+}
+
+fn into_klines(rows: BoxStream<'static, Result<KlineRow>>) -> BoxStream<'static, Result<KlineSummary>> {
+    rows.map_ok(KlineSummary::from).boxed()
+}
+
+/// Runs the backfill of every symbol concurrently, rows are tagged with their symbol
+///
+/// The streams are polled together, their requests are bounded by the budget of the backfill.
+pub fn for_symbols<S, T, F>(
+    symbols: impl IntoIterator<Item = S>,
+    mut backfill: F,
+) -> BoxStream<'static, Result<(String, T)>>
+where
+    S: Into<String>,
+    T: Send + 'static,
+    F: FnMut(&str) -> BoxStream<'static, Result<T>>,
+{
+    let streams = symbols.into_iter().map(|symbol| {
+        let symbol: String = symbol.into();
+        backfill(&symbol).map_ok(move |row| (symbol.clone(), row)).boxed()
+    });
+    stream::select_all(streams).boxed()
+}
+
+/// Backfill of spot market data
+#[derive(Clone)]
+pub struct Backfill {
+    pub(crate) client: Client,
+    pub(crate) budget: RequestBudget,
+    pub(crate) page_size: u16,
+}
+
+impl Backfill {
+    /// Shares the budget of other backfills, e.g. of futures
+    pub fn set_budget(mut self, budget: RequestBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Rows per request, capped by the endpoints
+    pub fn set_page_size(mut self, page_size: u16) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Klines opened between `start_time` and `end_time`, in ms
+    pub fn klines(
+        &self,
+        symbol: &str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'static, Result<KlineSummary>> {
+        let range = HistoryRange::Time {
+            start: start_time,
+            end: end_time,
+        };
+        let pager = Pager::new(&self.client, &self.budget, "/api/v3/klines".to_string(), range)
+            .param("symbol", symbol)
+            .param("interval", interval)
+            .page_size(self.page_size, 1000);
+        into_klines(pager.into_stream())
+    }
+
+    /// Aggregate trades of a time or id range
+    pub fn agg_trades(&self, symbol: &str, range: HistoryRange) -> BoxStream<'static, Result<AggTrade>> {
+        Pager::new(&self.client, &self.budget, "/api/v3/aggTrades".to_string(), range)
+            .param("symbol", symbol)
+            .page_size(self.page_size, 1000)
+            .window(AGG_TRADES_WINDOW)
+            .into_stream()
+    }
+
+    /// Trades from id `from_id` to id `to_id`, up to the latest trade without `to_id`
+    pub fn historical_trades(
+        &self,
+        symbol: &str,
+        from_id: u64,
+        to_id: Option<u64>,
+    ) -> BoxStream<'static, Result<MarketTrade>> {
+        let range = HistoryRange::Ids {
+            from: from_id,
+            to: to_id,
+        };
+        Pager::new(
+            &self.client,
+            &self.budget,
+            "/api/v3/historicalTrades".to_string(),
+            range,
+        )
+        .param("symbol", symbol)
+        .page_size(self.page_size, 1000)
+        .into_stream()
+    }
+}
+
+/// Backfill of USD-M or COIN-M futures market data
+#[derive(Clone)]
+pub struct FuturesBackfill<T: FuturesType> {
+    pub(crate) client: Client,
+    pub(crate) recv_window: u64,
+    pub(crate) router: fn(FuturesRoute) -> String,
+    pub(crate) budget: RequestBudget,
+    pub(crate) page_size: u16,
+    pub(crate) _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: FuturesType> FuturesBackfill<T> {
+    /// Shares the budget of other backfills, e.g. of spot
+    pub fn set_budget(mut self, budget: RequestBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Rows per request, capped by the endpoints
+    pub fn set_page_size(mut self, page_size: u16) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    fn klines_of(
+        &self,
+        route: FuturesRoute,
+        params: Vec<(&'static str, String)>,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'static, Result<KlineSummary>> {
+        let range = HistoryRange::Time {
+            start: start_time,
+            end: end_time,
+        };
+        let mut pager = Pager::new(&self.client, &self.budget, (self.router)(route), range);
+        pager.params = params;
+        into_klines(
+            pager
+                .param("interval", interval)
+                .page_size(self.page_size, 1500)
+                .into_stream(),
+        )
+    }
+
+    /// Klines opened between `start_time` and `end_time`, in ms
+    pub fn klines(
+        &self,
+        symbol: &str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'static, Result<KlineSummary>> {
+        let params = vec![("symbol", symbol.to_string())];
+        self.klines_of(FuturesRoute::Klines, params, interval, start_time, end_time)
+    }
+
+    /// Mark price klines opened between `start_time` and `end_time`, volumes are zero
+    pub fn mark_price_klines(
+        &self,
+        symbol: &str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'static, Result<KlineSummary>> {
+        let params = vec![("symbol", symbol.to_string())];
+        self.klines_of(FuturesRoute::MarkPriceKlines, params, interval, start_time, end_time)
+    }
+
+    /// Index price klines of a pair opened between `start_time` and `end_time`, volumes are zero
+    pub fn index_price_klines(
+        &self,
+        pair: &str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'static, Result<KlineSummary>> {
+        let params = vec![("pair", pair.to_string())];
+        self.klines_of(FuturesRoute::IndexPriceKlines, params, interval, start_time, end_time)
+    }
+
+    /// Continuous contract klines of a pair opened between `start_time` and `end_time`
+    pub fn continuous_klines(
+        &self,
+        pair: &str,
+        contract_type: ContractType,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'static, Result<KlineSummary>> {
+        let contract_type = serde_json::to_value(contract_type)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let params = vec![("pair", pair.to_string()), ("contractType", contract_type)];
+        self.klines_of(FuturesRoute::ContinuousKlines, params, interval, start_time, end_time)
+    }
+
+    /// Aggregate trades of a time or id range
+    pub fn agg_trades(&self, symbol: &str, range: HistoryRange) -> BoxStream<'static, Result<FuturesAggTrade>> {
+        Pager::new(
+            &self.client,
+            &self.budget,
+            (self.router)(FuturesRoute::AggTrades),
+            range,
+        )
+        .param("symbol", symbol)
+        .page_size(self.page_size, 1000)
+        .window(AGG_TRADES_WINDOW)
+        .into_stream()
+    }
+
+    /// Trades from id `from_id` to id `to_id`, up to the latest trade without `to_id`, requires an API key
+    pub fn historical_trades(
+        &self,
+        symbol: &str,
+        from_id: u64,
+        to_id: Option<u64>,
+    ) -> BoxStream<'static, Result<FuturesTrade>> {
+        let range = HistoryRange::Ids {
+            from: from_id,
+            to: to_id,
+        };
+        Pager::new(
+            &self.client,
+            &self.budget,
+            (self.router)(FuturesRoute::HistoricalTrades),
+            range,
+        )
+        .param("symbol", symbol)
+        .page_size(self.page_size, 500)
+        .signed(self.recv_window)
+        .into_stream()
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use hyper::Method;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::api::Binance;
+    use crate::mock::{MockResponse, MockServer};
+    use crate::test_util::param;
+
+    fn kline(open_time: u64) -> Value {
+        json!([
+            open_time,
+            "1.0",
+            "2.0",
+            "0.5",
+            "1.5",
+            "10.0",
+            open_time + 59_999,
+            "15.0",
+            3,
+            "5.0",
+            "7.5",
+            "0"
+        ])
+    }
+
+    #[tokio::test]
+    async fn klines_are_paginated_without_duplicates() {
+        let server = MockServer::start().await;
+        // pages overlap by one kline
+        server.mock_fn(Method::GET, "/api/v3/klines", |request| {
+            let start = param(request, "startTime").unwrap().saturating_sub(60_000);
+            let end = param(request, "endTime").unwrap();
+            let limit = param(request, "limit").unwrap();
+            let klines: Vec<Value> = (0..)
+                .map(|i| i * 60_000)
+                .skip_while(|time| *time < start)
+                .take_while(|time| *time <= end)
+                .take(limit as usize)
+                .map(kline)
+                .collect();
+            MockResponse::json(json!(klines))
+        });
+        let backfill: Backfill = Binance::new_with_config(None, None, &server.config());
+        let klines: Vec<KlineSummary> = backfill
+            .set_page_size(3)
+            .klines("BTCUSDT", KlineInterval::Minutes1, 60_000, 600_000)
+            .try_collect()
+            .await
+            .unwrap();
+        let times: Vec<i64> = klines.iter().map(|kline| kline.open_time).collect();
+        assert_eq!(times, (1..=10).map(|i| i * 60_000).collect::<Vec<_>>());
+        assert_eq!(server.requests()[0].param("interval"), Some("1m"));
+    }
+
+    #[tokio::test]
+    async fn agg_trades_continue_by_id_after_the_first_span() {
+        let server = MockServer::start().await;
+        // one trade every 20 minutes from the second hour
+        let trades: Vec<Value> = (0..12u64)
+            .map(|id| json!({"a": id, "p": "1.0", "q": "2.0", "f": id, "l": id, "T": 3_600_000 + id * 1_200_000, "m": true, "M": true}))
+            .collect();
+        server.mock_fn(Method::GET, "/api/v3/aggTrades", move |request| {
+            let limit = param(request, "limit").unwrap() as usize;
+            let page: Vec<&Value> = match param(request, "fromId") {
+                Some(from) => trades.iter().skip(from as usize).take(limit).collect(),
+                None => {
+                    let (start, end) = (param(request, "startTime").unwrap(), param(request, "endTime").unwrap());
+                    assert!(end - start < 3_600_000);
+                    trades
+                        .iter()
+                        .filter(|t| (start..=end).contains(&t["T"].as_u64().unwrap()))
+                        .take(limit)
+                        .collect()
+                }
+            };
+            MockResponse::json(json!(page))
+        });
+        let backfill: Backfill = Binance::new_with_config(None, None, &server.config());
+        let range = HistoryRange::Time {
+            start: 0,
+            end: 3_600_000 * 3,
+        };
+        let ids: Vec<u64> = backfill
+            .set_page_size(2)
+            .agg_trades("BTCUSDT", range)
+            .map_ok(|trade| trade.agg_id)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids, (0..=6).collect::<Vec<_>>());
+        let requests = server.requests();
+        assert_eq!(requests[0].param("endTime"), Some("3599999"));
+        assert_eq!(requests[2].param("fromId"), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn symbols_share_the_budget() {
+        let server = MockServer::start().await;
+        server.mock_fn(Method::GET, "/api/v3/historicalTrades", |request| {
+            let from = param(request, "fromId").unwrap();
+            let trades: Vec<Value> = (from..from + param(request, "limit").unwrap())
+                .map(|id| json!({"id": id, "price": "1.0", "qty": "2.0", "quoteQty": "2.0", "time": id, "isBuyerMaker": true, "isBestMatch": true}))
+                .collect();
+            MockResponse::json(json!(trades))
+        });
+        let backfill: Backfill = Binance::new_with_config(None, None, &server.config());
+        let backfill = backfill.set_budget(RequestBudget::new(1)).set_page_size(5);
+        let trades: Vec<(String, MarketTrade)> = for_symbols(["BTCUSDT", "ETHUSDT"], |symbol| {
+            backfill.historical_trades(symbol, 10, Some(21))
+        })
+        .try_collect()
+        .await
+        .unwrap();
+        for symbol in ["BTCUSDT", "ETHUSDT"] {
+            let ids: Vec<u64> = trades.iter().filter(|(s, _)| s == symbol).map(|(_, t)| t.id).collect();
+            assert_eq!(ids, (10..=21).collect::<Vec<_>>());
+        }
+        assert_eq!(server.requests().len(), 6);
+    }
+}
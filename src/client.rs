@@ -1,33 +1,46 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use boolinator::Boolinator;
-use hex::encode as hex_encode;
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, USER_AGENT};
+use chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER, USER_AGENT};
 use reqwest::Response;
-use reqwest::StatusCode;
-use ring::hmac;
+use reqwest::{Method, StatusCode};
 use serde::de;
 use serde::de::DeserializeOwned;
+use url::form_urlencoded;
 
 use crate::errors::error_messages;
 use crate::errors::*;
-use crate::util::{build_request_p, build_signed_request_p};
+use crate::rate_limit::{RateLimiter, RetryPolicy};
+use crate::rest_model::{RateLimit, ServerTime};
+use crate::signer::{HmacSigner, Signer};
+use crate::time_sync::TimeSync;
+use crate::util::{build_request_p, build_signed_request_p, get_timestamp};
 
 #[derive(Clone)]
 pub struct Client {
     api_key: String,
-    secret_key: String,
+    signer: Arc<dyn Signer>,
     inner: reqwest::Client,
     host: String,
+    rate_limiter: Option<RateLimiter>,
+    retry_policy: Option<RetryPolicy>,
+    time_sync: Option<TimeSync>,
+    time_endpoint: String,
 }
 
-
-
 impl Client {
     /// Returns a client based on the specified host and credentials
     /// Credentials do not need to be specified when using public endpoints
     /// Host is mandatory
     pub fn new(api_key: Option<String>, secret_key: Option<String>, host: String, timeout: Option<u64>) -> Self {
+        let signer = Arc::new(HmacSigner::new(secret_key.as_deref().unwrap_or_default()));
+        Self::new_with_signer(api_key, signer, host, timeout)
+    }
+
+    /// Returns a client signing requests with `signer`
+    pub fn new_with_signer(api_key: Option<String>, signer: Arc<dyn Signer>, host: String, timeout: Option<u64>) -> Self {
         let mut builder: reqwest::ClientBuilder = reqwest::ClientBuilder::new();
         if let Some(timeout_secs) = timeout {
             builder = builder.timeout(Duration::from_secs(timeout_secs))
@@ -35,17 +48,46 @@ impl Client {
         Client {
             // Does it ever make sense for api_key and secret_key to be ""?
             api_key: api_key.unwrap_or_else(|| "".into()),
-            secret_key: secret_key.unwrap_or_else(|| "".into()),
+            signer,
             inner: builder.build().unwrap(),
             host,
+            rate_limiter: None,
+            retry_policy: None,
+            time_sync: None,
+            time_endpoint: String::new(),
         }
     }
 
-    pub async fn get_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let response = self.inner.get(&url).headers(self.build_headers(true)?).send().await?;
+    /// Counts requests against the limits of `rate_limiter`
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Retries GET requests rejected by rate limits according to `retry_policy`
+    pub fn with_retry_policy(mut self, retry_policy: Option<RetryPolicy>) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
 
-        self.handler(response).await
+    /// Corrects the timestamp of signed requests with the clock offset measured by `time_sync`
+    /// against `time_endpoint`, e.g. `/api/v3/time`
+    pub fn with_time_sync(mut self, time_sync: Option<TimeSync>, time_endpoint: &str) -> Self {
+        self.time_sync = time_sync;
+        self.time_endpoint = time_endpoint.to_string();
+        self
+    }
+
+    /// Loads the limits of the exchange information into the rate limiter, if any
+    pub(crate) fn update_rate_limits(&self, rate_limits: &[RateLimit]) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.set_limits(rate_limits);
+        }
+    }
+
+    pub async fn get_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
+        self.send(Method::GET, endpoint, request, true, Some(self.build_headers(true)?))
+            .await
     }
 
     pub async fn get_signed_d<T: de::DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
@@ -63,10 +105,8 @@ impl Client {
     }
 
     pub async fn post_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let response = self.inner.post(&url).headers(self.build_headers(true)?).send().await?;
-
-        self.handler(response).await
+        self.send(Method::POST, endpoint, request, true, Some(self.build_headers(true)?))
+            .await
     }
 
     pub async fn post_signed_d<T: de::DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
@@ -83,6 +123,21 @@ impl Client {
         self.post_signed(endpoint, &request).await
     }
 
+    pub async fn put_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
+        self.send(Method::PUT, endpoint, request, true, Some(self.build_headers(true)?))
+            .await
+    }
+
+    pub async fn put_signed_p<T: de::DeserializeOwned, P: serde::Serialize>(
+        &self,
+        endpoint: &str,
+        payload: P,
+        recv_window: u64,
+    ) -> Result<T> {
+        let request = build_signed_request_p(payload, recv_window)?;
+        self.put_signed(endpoint, &request).await
+    }
+
     pub async fn delete_signed_p<T: de::DeserializeOwned, P: serde::Serialize>(
         &self,
         endpoint: &str,
@@ -94,25 +149,13 @@ impl Client {
     }
 
     pub async fn delete_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let response = self
-            .inner
-            .delete(&url)
-            .headers(self.build_headers(true)?)
-            .send()
-            .await?;
-
-        self.handler(response).await
+        self.send(Method::DELETE, endpoint, request, true, Some(self.build_headers(true)?))
+            .await
     }
 
     pub async fn get<T: DeserializeOwned>(&self, endpoint: &str, request: Option<&str>) -> Result<T> {
-        let url = request
-            .map(|r| format!("{}{}?{}", self.host, endpoint, r))
-            .unwrap_or_else(|| format!("{}{}", self.host, endpoint));
-
-        let response = self.inner.get(&url).send().await?;
-
-        self.handler(response).await
+        self.send(Method::GET, endpoint, request.unwrap_or_default(), false, None)
+            .await
     }
 
     pub async fn get_p<T: DeserializeOwned>(&self, endpoint: &str, request: Option<&str>) -> Result<T> {
@@ -133,13 +176,9 @@ impl Client {
     }
 
     pub async fn post<T: DeserializeOwned>(&self, endpoint: &str, symbol: Option<&str>) -> Result<T> {
-        let url = symbol
-            .map(|s| format!("{}{}?symbol={}", self.host, endpoint, s))
-            .unwrap_or_else(|| format!("{}{}", self.host, endpoint));
-
-        let response = self.inner.post(url).headers(self.build_headers(false)?).send().await?;
-
-        self.handler(response).await
+        let data = symbol.map(|s| format!("symbol={s}")).unwrap_or_default();
+        self.send(Method::POST, endpoint, &data, false, Some(self.build_headers(false)?))
+            .await
     }
 
     pub async fn put<T: DeserializeOwned>(&self, endpoint: &str, listen_key: &str, symbol: Option<&str>) -> Result<T> {
@@ -147,10 +186,7 @@ impl Client {
             .map(|s| format!("listenKey={listen_key}&symbol={s}"))
             .unwrap_or_else(|| format!("listenKey={listen_key}"));
         let headers = self.build_headers(false)?;
-        let url = format!("{}{}?{}", self.host, endpoint, data);
-        let response = self.inner.put(&url).headers(headers).send().await?;
-
-        self.handler(response).await
+        self.send(Method::PUT, endpoint, &data, false, Some(headers)).await
     }
 
     pub async fn delete<T: DeserializeOwned>(
@@ -162,24 +198,95 @@ impl Client {
         let data = symbol
             .map(|s| format!("listenKey={listen_key}&symbol={s}"))
             .unwrap_or_else(|| format!("listenKey={listen_key}"));
-        let url = format!("{}{}?{}", self.host, endpoint, data);
-        let response = self
-            .inner
-            .delete(url)
-            .headers(self.build_headers(false)?)
-            .send()
-            .await?;
+        self.send(Method::DELETE, endpoint, &data, false, Some(self.build_headers(false)?))
+            .await
+    }
+
+    /// Sends a request through the rate limiter, if any, retrying GET requests according to the retry policy.
+    /// Signed requests are signed again with a new timestamp on every attempt.
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: &str,
+        signed: bool,
+        headers: Option<HeaderMap>,
+    ) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire(&method, endpoint, query).await?;
+            }
+            let url = if signed {
+                self.sign_request(endpoint, query).await?
+            } else if query.is_empty() {
+                format!("{}{}", self.host, endpoint)
+            } else {
+                format!("{}{}?{}", self.host, endpoint, query)
+            };
+            let mut request = self.inner.request(method.clone(), &url);
+            if let Some(headers) = &headers {
+                request = request.headers(headers.clone());
+            }
+            let response = request.send().await?;
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.update_from_headers(response.headers());
+            }
 
-        self.handler(response).await
+            let result = self.handler(response).await;
+            if let (Some(time_sync), Err(Error::BinanceError { response })) = (&self.time_sync, &result) {
+                if response.code == error_codes::TIMESTAMP_OUTSIDE_RECV_WINDOW {
+                    time_sync.invalidate();
+                }
+            }
+            // every request waits for the end of a rate limit or a ban, not only the retries of this one
+            if let (Some(rate_limiter), Err(error @ (Error::TooManyRequests(_) | Error::IpBanned(_)))) =
+                (&self.rate_limiter, &result)
+            {
+                if let Some(retry_after) = error.retry_after() {
+                    rate_limiter.block_for(retry_after);
+                }
+            }
+            match result {
+                Err(error) if method == Method::GET => {
+                    let Some(delay) = self.retry_policy.as_ref().and_then(|p| p.delay(attempt, &error)) else {
+                        return Err(error);
+                    };
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
     }
 
-    // Request must be signed
-    fn sign_request(&self, endpoint: &str, request: &str) -> String {
-        let signed_key = hmac::Key::new(hmac::HMAC_SHA256, self.secret_key.as_bytes());
-        let signature = hex_encode(hmac::sign(&signed_key, request.as_bytes()).as_ref());
+    // Request must be signed, its timestamp is replaced by the current time
+    async fn sign_request(&self, endpoint: &str, request: &str) -> Result<String> {
+        let timestamp = match &self.time_sync {
+            Some(time_sync) => {
+                if time_sync.is_stale() {
+                    // boxed as the time is fetched through `send`, which signs requests with this method
+                    Box::pin(self.sync_time(time_sync)).await?;
+                }
+                time_sync.timestamp()
+            }
+            None => get_timestamp()?,
+        };
+        let request = with_timestamp(request, timestamp);
+        let signature = self.signer.sign(request.as_bytes())?;
+        let signature: String = form_urlencoded::byte_serialize(signature.as_bytes()).collect();
         let url = format!("{}{}?{}&signature={}", self.host, endpoint, request, signature);
 
-        url
+        Ok(url)
+    }
+
+    /// Measures the offset of the local clock against the server time
+    async fn sync_time(&self, time_sync: &TimeSync) -> Result<()> {
+        let sent = Utc::now().timestamp_millis();
+        let time: ServerTime = self.get(&self.time_endpoint, None).await?;
+        let received = Utc::now().timestamp_millis();
+        time_sync.record(sent, received, time.server_time as i64);
+        Ok(())
     }
 
     fn build_headers(&self, content_type: bool) -> Result<HeaderMap> {
@@ -215,15 +322,214 @@ impl Client {
                 let error: BinanceContentError = response.json().await?;
                 Err(handle_content_error(error))
             }
+            StatusCode::TOO_MANY_REQUESTS => Err(Error::TooManyRequests(Box::new(error_details(response).await))),
+            StatusCode::IM_A_TEAPOT => Err(Error::IpBanned(Box::new(error_details(response).await))),
+            StatusCode::FORBIDDEN => Err(Error::Forbidden(Box::new(error_details(response).await))),
+            StatusCode::NOT_FOUND => Err(Error::NotFound(Box::new(error_details(response).await))),
             s => Err(Error::Msg(format!("Received response: {s:?}"))),
         }
     }
 }
 
-fn handle_content_error(error: BinanceContentError) -> crate::errors::Error {
+/// Replaces the timestamp parameter of a request built by `build_signed_request`
+fn with_timestamp(request: &str, timestamp: u64) -> String {
+    request
+        .split('&')
+        .map(|param| {
+            if param.starts_with("timestamp=") {
+                format!("timestamp={timestamp}")
+            } else {
+                param.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+async fn error_details(response: Response) -> ResponseErrorDetails {
+    let headers = response.headers();
+    let usage = |prefix: &str| {
+        headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let interval = name.as_str().strip_prefix(prefix)?;
+                let value = value.to_str().ok()?.parse().ok()?;
+                Some((interval.to_string(), value))
+            })
+            .collect()
+    };
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok()?.trim().parse().ok())
+        .map(Duration::from_secs);
+    let used_weight = usage("x-mbx-used-weight-");
+    let order_count = usage("x-mbx-order-count-");
+    let content = response
+        .text()
+        .await
+        .ok()
+        .and_then(|body| serde_json::from_str(&body).ok());
+    ResponseErrorDetails {
+        retry_after,
+        used_weight,
+        order_count,
+        content,
+    }
+}
+
+pub(crate) fn handle_content_error(error: BinanceContentError) -> crate::errors::Error {
     match (error.code, error.msg.as_ref()) {
         (-1013, error_messages::INVALID_PRICE) => Error::InvalidPrice,
         (-1125, msg) => Error::InvalidListenKey(msg.to_string()),
         _ => Error::BinanceError { response: error },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::RateLimitMode;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serves one `(status, headers, body)` response per connection, returns the server address
+    /// and the request lines received
+    async fn serve(
+        responses: Vec<(&'static str, &'static str, &'static str)>,
+    ) -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for (status, headers, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 1024];
+                let read = socket.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..read]);
+                let _ = tx.send(request.lines().next().unwrap_or_default().to_string());
+                let response = format!(
+                    "HTTP/1.1 {status}\r\n{headers}Connection: close\r\nContent-Length: {}\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.shutdown().await.unwrap();
+            }
+        });
+        (format!("http://{addr}"), rx)
+    }
+
+    const TOO_MANY_REQUESTS: (&str, &str, &str) = (
+        "429 Too Many Requests",
+        "Retry-After: 0\r\nX-MBX-USED-WEIGHT-1M: 6001\r\n",
+        r#"{"code":-1003,"msg":"Too many requests."}"#,
+    );
+    const OK: (&str, &str, &str) = ("200 OK", "", r#"{"serverTime":1499827319559}"#);
+
+    #[tokio::test]
+    async fn rate_limit_responses_carry_details() {
+        let (host, _) = serve(vec![TOO_MANY_REQUESTS, ("403 Forbidden", "", "<html>blocked</html>")]).await;
+        let client = Client::new(None, None, host, None);
+
+        let error = client.get::<ServerTime>("/api/v3/time", None).await.unwrap_err();
+        let Error::TooManyRequests(details) = &error else {
+            panic!("unexpected error {error:?}");
+        };
+        assert_eq!(details.retry_after, Some(Duration::from_secs(0)));
+        assert_eq!(details.used_weight, vec![("1m".to_string(), 6001)]);
+        assert_eq!(details.content.as_ref().map(|c| c.code), Some(-1003));
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(0)));
+
+        let error = client.get::<ServerTime>("/api/v3/time", None).await.unwrap_err();
+        assert!(matches!(&error, Error::Forbidden(details) if details.content.is_none()));
+    }
+
+    #[tokio::test]
+    async fn get_requests_are_retried_after_retry_after() {
+        let (host, _) = serve(vec![TOO_MANY_REQUESTS, TOO_MANY_REQUESTS, OK]).await;
+        let client = Client::new(None, None, host, None).with_retry_policy(Some(RetryPolicy::default()));
+        let time: ServerTime = client.get("/api/v3/time", None).await.unwrap();
+        assert_eq!(time.server_time, 1499827319559);
+
+        let (host, _) = serve(vec![TOO_MANY_REQUESTS, TOO_MANY_REQUESTS]).await;
+        let client =
+            Client::new(None, None, host, None).with_retry_policy(Some(RetryPolicy::default().set_max_retries(1)));
+        let error = client.get::<ServerTime>("/api/v3/time", None).await.unwrap_err();
+        assert!(matches!(error, Error::TooManyRequests(_)));
+    }
+
+    /// Value of a parameter of a request line
+    fn param<'a>(request: &'a str, name: &str) -> &'a str {
+        request
+            .split(['&', '?', ' '])
+            .find_map(|param| param.strip_prefix(name)?.strip_prefix('='))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn signed_requests_are_signed_again_when_retried() {
+        let retry_in_a_second = ("429 Too Many Requests", "Retry-After: 1\r\n", TOO_MANY_REQUESTS.2);
+        let (host, mut requests) = serve(vec![retry_in_a_second, OK]).await;
+        let client =
+            Client::new(None, Some("secret".into()), host, None).with_retry_policy(Some(RetryPolicy::default()));
+        let _: ServerTime = client
+            .get_signed("/api/v3/account", "recvWindow=5000&timestamp=1")
+            .await
+            .unwrap();
+
+        let first = requests.recv().await.unwrap();
+        let retry = requests.recv().await.unwrap();
+        let timestamp = |request| param(request, "timestamp").parse::<u64>().unwrap();
+        assert!(timestamp(&retry) >= timestamp(&first) + 1000, "{first} / {retry}");
+        assert_ne!(param(&first, "signature"), param(&retry, "signature"));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_responses_hold_back_the_next_requests() {
+        let retry_later = ("429 Too Many Requests", "Retry-After: 30\r\n", TOO_MANY_REQUESTS.2);
+        let (host, _) = serve(vec![retry_later]).await;
+        let limiter = RateLimiter::new(RateLimitMode::Reject);
+        let client = Client::new(None, None, host, None).with_rate_limiter(Some(limiter));
+
+        let error = client.get::<ServerTime>("/api/v3/time", None).await.unwrap_err();
+        assert!(matches!(error, Error::TooManyRequests(_)));
+        // rejected locally, the server only serves one response
+        let error = client.get::<ServerTime>("/api/v3/time", None).await.unwrap_err();
+        let Error::RateLimitReached { retry_after, .. } = error else {
+            panic!("unexpected error {error:?}");
+        };
+        assert!(retry_after > Duration::from_secs(25) && retry_after <= Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn signed_requests_use_the_server_time() {
+        let (host, mut requests) = serve(vec![("200 OK", "", r#"{"serverTime":1000000}"#), OK]).await;
+        let time_sync = TimeSync::default();
+        let client = Client::new(None, Some("secret".into()), host, None)
+            .with_time_sync(Some(time_sync.clone()), "/api/v3/time");
+        let _: ServerTime = client
+            .get_signed("/api/v3/account", "recvWindow=5000&timestamp=1&symbol=BTCUSDT")
+            .await
+            .unwrap();
+
+        assert_eq!(requests.recv().await.unwrap(), "GET /api/v3/time HTTP/1.1");
+        let request = requests.recv().await.unwrap();
+        let timestamp: u64 = request
+            .split(['&', '?'])
+            .find_map(|param| param.strip_prefix("timestamp="))
+            .unwrap()
+            .parse()
+            .unwrap();
+        // the local clock is far ahead of the mocked server time
+        assert!((1_000_000..1_100_000).contains(&timestamp), "{request}");
+        assert!(request.contains("&symbol=BTCUSDT&signature="), "{request}");
+        assert!(time_sync.offset().unwrap() < 0);
+    }
+
+    #[test]
+    fn timestamp_is_replaced() {
+        assert_eq!(
+            with_timestamp("recvWindow=5000&timestamp=1&symbol=BTCUSDT", 42),
+            "recvWindow=5000&timestamp=42&symbol=BTCUSDT"
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use crate::futures::router::{FuturesRoute};
+use crate::rate_limit::{RateLimiter, RetryPolicy};
+use crate::signer::KeyType;
+use crate::time_sync::TimeSync;
 
 pub static DATA_REST_ENDPOINT: &str = "https://data.binance.com";
 
@@ -6,6 +8,7 @@ pub static DATA_REST_ENDPOINT: &str = "https://data.binance.com";
 pub struct Config {
     pub rest_api_endpoint: String,
     pub ws_endpoint: String,
+    pub ws_api_endpoint: String,
 
     pub futures_rest_api_endpoint: String,
     pub futures_ws_endpoint: String,
@@ -15,6 +18,16 @@ pub struct Config {
     pub binance_us_api: bool,
 
     pub timeout: Option<u64>,
+
+    pub rate_limiter: Option<RateLimiter>,
+    pub futures_rate_limiter: Option<RateLimiter>,
+    pub coin_m_futures_rate_limiter: Option<RateLimiter>,
+
+    pub retry_policy: Option<RetryPolicy>,
+
+    pub time_sync: Option<TimeSync>,
+
+    pub key_type: KeyType,
 }
 
 impl Config {
@@ -28,6 +41,7 @@ impl Config {
         Config::default()
             .set_rest_api_endpoint("https://testnet.binance.vision")
             .set_ws_endpoint("wss://testnet.binance.vision")
+            .set_ws_api_endpoint("wss://testnet.binance.vision/ws-api/v3")
             .set_futures_rest_api_endpoint("https://testnet.binancefuture.com")
             .set_futures_ws_endpoint("wss://testnet.binancefuture.com")
     }
@@ -72,6 +86,26 @@ impl Config {
         self
     }
 
+    /// Sets the websocket API endpoint. Defaults to "wss://ws-api.binance.com:443/ws-api/v3".
+    ///
+    /// # Arguments
+    ///
+    /// * `ws_api_endpoint`:
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// let config = Config::default();
+    /// config.set_ws_api_endpoint("ws://myendpoint:8080/ws-api/v3");
+    /// ```
+    pub fn set_ws_api_endpoint<T: Into<String>>(mut self, ws_api_endpoint: T) -> Self {
+        self.ws_api_endpoint = ws_api_endpoint.into();
+        self
+    }
+
     /// Sets the futures rest api endpoint. Defaults to <https://fapi.binance.com>.
     ///
     /// # Arguments
@@ -153,6 +187,135 @@ impl Config {
         self
     }
 
+    /// Sets the rate limiter of spot, margin, savings and wallet requests.
+    /// Clones of a limiter share their counters, so the same limiter should be used for all clients.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_limiter`: The rate limiter
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::rate_limit::{RateLimiter, RateLimitMode};
+    /// let config = Config::default();
+    /// config.set_rate_limiter(RateLimiter::new(RateLimitMode::Delay));
+    /// ```
+    pub fn set_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sets the rate limiter of USD-M futures requests, futures limits are separate from spot limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_limiter`: The rate limiter
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::rate_limit::{RateLimiter, RateLimitMode};
+    /// let config = Config::default();
+    /// config.set_futures_rate_limiter(RateLimiter::new(RateLimitMode::Reject));
+    /// ```
+    pub fn set_futures_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.futures_rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sets the rate limiter of COIN-M futures requests, COIN-M limits are separate from USD-M limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_limiter`: The rate limiter
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::rate_limit::{RateLimiter, RateLimitMode};
+    /// let config = Config::default();
+    /// config.set_coin_m_futures_rate_limiter(RateLimiter::new(RateLimitMode::Reject));
+    /// ```
+    pub fn set_coin_m_futures_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.coin_m_futures_rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sets the retry policy of GET requests rejected with a 429 or a 418. Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_policy`: The retry policy
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::rate_limit::RetryPolicy;
+    /// let config = Config::default();
+    /// config.set_retry_policy(RetryPolicy::default().set_max_retries(5));
+    /// ```
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Corrects the timestamp of signed requests with the offset of the local clock against the server time.
+    /// Disabled by default, the local clock is used as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_sync`: The time sync, shared by spot and futures clients
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::time_sync::TimeSync;
+    /// use std::time::Duration;
+    /// let config = Config::default();
+    /// config.set_time_sync(TimeSync::new(Duration::from_secs(300)));
+    /// ```
+    pub fn set_time_sync(mut self, time_sync: TimeSync) -> Self {
+        self.time_sync = Some(time_sync);
+        self
+    }
+
+    /// Sets the kind of API key, the secret key being a PKCS#8 PEM private key for Ed25519 and RSA keys.
+    /// Defaults to HMAC.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_type`: The kind of API key
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::signer::KeyType;
+    /// let config = Config::default();
+    /// config.set_key_type(KeyType::Ed25519);
+    /// ```
+    pub fn set_key_type(mut self, key_type: KeyType) -> Self {
+        self.key_type = key_type;
+        self
+    }
 }
 
 impl Default for Config {
@@ -166,6 +329,7 @@ impl Default for Config {
         Config {
             rest_api_endpoint: "https://api.binance.com".into(),
             ws_endpoint: "wss://stream.binance.com:9443".into(),
+            ws_api_endpoint: "wss://ws-api.binance.com:443/ws-api/v3".into(),
 
             //futures_rest_api_endpoint: "https://fapi.binance.com".into(),
             //futures_ws_endpoint: "wss://fstream.binance.com".into(),
@@ -177,6 +341,13 @@ impl Default for Config {
             binance_us_api: false,
 
             timeout: None,
+
+            rate_limiter: None,
+            futures_rate_limiter: None,
+            coin_m_futures_rate_limiter: None,
+            retry_policy: None,
+            time_sync: None,
+            key_type: KeyType::Hmac,
         }
     }
 }
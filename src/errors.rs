@@ -1,8 +1,12 @@
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
-#[derive(Debug, Deserialize, Error)]
+use crate::rest_model::{Amount, RateLimitType};
+
+#[derive(Debug, Clone, Deserialize, Error)]
 #[error("code: {code}, msg: {msg}")]
 pub struct BinanceContentError {
     pub code: i32,
@@ -12,6 +16,65 @@ pub struct BinanceContentError {
     extra: HashMap<String, Value>,
 }
 
+impl BinanceContentError {
+    pub(crate) fn new(code: i32, msg: String) -> Self {
+        Self {
+            code,
+            msg,
+            extra: HashMap::new(),
+        }
+    }
+
+    /// Additional data of the error, e.g. the end of a ban
+    pub(crate) fn data(&self) -> Option<&Value> { self.extra.get("data") }
+}
+
+/// Details of a response rejected by Binance or by a firewall in front of it
+#[derive(Debug, Clone, Default)]
+pub struct ResponseErrorDetails {
+    /// Value of the `Retry-After` header
+    pub retry_after: Option<Duration>,
+    /// `X-MBX-USED-WEIGHT-*` headers, as `(interval, weight)` e.g. `("1m", 1200)`
+    pub used_weight: Vec<(String, u32)>,
+    /// `X-MBX-ORDER-COUNT-*` headers, as `(interval, count)` e.g. `("10s", 50)`
+    pub order_count: Vec<(String, u32)>,
+    /// Binance error of the body, absent when the body is not a Binance error (e.g. a WAF page)
+    pub content: Option<BinanceContentError>,
+}
+
+impl fmt::Display for ResponseErrorDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(content) = &self.content {
+            write!(f, ", {content}")?;
+        }
+        if let Some(retry_after) = &self.retry_after {
+            write!(f, ", retry after {retry_after:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An order breaking one of the filters of its symbol, see [`crate::filters::OrderFilters`]
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum FilterViolation {
+    #[error("price {price} is below the minimum price {min_price}")]
+    PriceTooLow { price: Amount, min_price: Amount },
+    #[error("price {price} is above the maximum price {max_price}")]
+    PriceTooHigh { price: Amount, max_price: Amount },
+    #[error("price {price} is not a multiple of the tick size {tick_size}")]
+    PriceNotOnTick { price: Amount, tick_size: Amount },
+    #[error("quantity {quantity} is below the minimum quantity {min_qty}")]
+    QuantityTooLow { quantity: Amount, min_qty: Amount },
+    #[error("quantity {quantity} is above the maximum quantity {max_qty}")]
+    QuantityTooHigh { quantity: Amount, max_qty: Amount },
+    #[error("quantity {quantity} is not a multiple of the step size {step_size}")]
+    QuantityNotOnStep { quantity: Amount, step_size: Amount },
+    #[error("notional {notional} is below the minimum notional {min_notional}")]
+    NotionalTooLow { notional: Amount, min_notional: Amount },
+    #[error("notional {notional} is above the maximum notional {max_notional}")]
+    NotionalTooHigh { notional: Amount, max_notional: Amount },
+}
+
 /// First errors are technical errors
 /// All unhandled binance content errors are BinanceError
 /// The rest are binance content errors that are properly handled
@@ -51,21 +114,63 @@ pub enum Error {
     InvalidOrderError { msg: String },
     #[error("invalid price")]
     InvalidPrice,
+    #[error("filter violation: {0}")]
+    FilterViolation(#[from] FilterViolation),
     #[error("invalid period {0}")]
     InvalidPeriod(String),
+    #[error("more than {limit} incomes at {time}, they cannot be paged by time")]
+    IncomePageOverflow { time: u64, limit: u16 },
     #[error("internal server error")]
     InternalServerError,
     #[error("service unavailable")]
     ServiceUnavailable,
     #[error("Unauthorized")]
     Unauthorized,
+    #[error("{rate_limit_type:?} rate limit reached, retry after {retry_after:?}")]
+    RateLimitReached {
+        rate_limit_type: RateLimitType,
+        retry_after: Duration,
+    },
+    #[error("invalid signing key: {0}")]
+    InvalidSigningKey(String),
+    #[error("too many requests{0}")]
+    TooManyRequests(Box<ResponseErrorDetails>),
+    #[error("IP banned{0}")]
+    IpBanned(Box<ResponseErrorDetails>),
+    #[error("forbidden{0}")]
+    Forbidden(Box<ResponseErrorDetails>),
+    #[error("not found{0}")]
+    NotFound(Box<ResponseErrorDetails>),
     #[error("{0}")]
     Msg(String),
 }
 
+impl Error {
+    /// Delay after which the request may be retried, for rate limit errors
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::TooManyRequests(details) | Error::IpBanned(details) => details.retry_after,
+            Error::RateLimitReached { retry_after, .. } => Some(*retry_after),
+            _ => None,
+        }
+    }
+}
+
 /// Custom error messages
 pub mod error_messages {
     pub const INVALID_PRICE: &str = "Invalid price.";
 }
 
+/// Binance error codes
+pub mod error_codes {
+    /// Timestamp for this request is outside of the recvWindow
+    pub const TIMESTAMP_OUTSIDE_RECV_WINDOW: i32 = -1021;
+    /// The margin type of the symbol is already the requested one
+    pub const NO_NEED_TO_CHANGE_MARGIN_TYPE: i32 = -4046;
+    /// The position mode is already the requested one
+    pub const NO_NEED_TO_CHANGE_POSITION_SIDE: i32 = -4059;
+    /// The multi-assets mode is already the requested one
+    pub const NO_NEED_TO_CHANGE_MULTI_ASSETS_MODE: i32 = -4171;
+}
+
 pub type Result<T> = core::result::Result<T, Error>;
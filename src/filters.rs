@@ -0,0 +1,392 @@
+//! Pre-trade validation of orders against the filters of their symbol, so that orders breaking them
+//! are rejected locally instead of coming back as a -1013 from the exchange.
+
+use rust_decimal::prelude::*;
+
+use crate::errors::FilterViolation;
+use crate::futures::rest_model::Filters as FuturesFilters;
+use crate::rest_model::{Amount, Filters, OrderSide};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PriceFilter {
+    min_price: Decimal,
+    max_price: Decimal,
+    tick_size: Decimal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LotSize {
+    min_qty: Decimal,
+    max_qty: Decimal,
+    step_size: Decimal,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Notional {
+    min_notional: Decimal,
+    max_notional: Decimal,
+}
+
+/// Price, quantity and notional filters of a symbol, built from the filters of the exchange information
+/// with `Symbol::order_filters`.
+///
+/// Orders are checked against `PRICE_FILTER`, `LOT_SIZE`, `MARKET_LOT_SIZE`, `MIN_NOTIONAL` and `NOTIONAL`.
+/// Filters relative to the average price (`PERCENT_PRICE`, `PERCENT_PRICE_BY_SIDE`) and the notional of market
+/// orders depend on market data and are left to the exchange. As with Binance, a bound of 0 is disabled.
+///
+/// # Examples
+///
+/// ```
+/// use binance::filters::OrderFilters;
+/// use binance::rest_model::{Filters, OrderSide};
+/// let filters = OrderFilters::from(
+///     &[Filters::PriceFilter {
+///         min_price: 0.01,
+///         max_price: 1000000.0,
+///         tick_size: 0.01,
+///     }][..],
+/// )
+/// .set_rounding(true);
+/// let price = filters.check_price(&OrderSide::Buy, "25000.123".parse().unwrap()).unwrap();
+/// assert_eq!(price.to_string(), "25000.12");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OrderFilters {
+    price: Option<PriceFilter>,
+    lot_size: Option<LotSize>,
+    market_lot_size: Option<LotSize>,
+    notional: Option<Notional>,
+    rounding: bool,
+}
+
+impl OrderFilters {
+    /// Rounds prices to the tick size and quantities to the step size instead of rejecting them.
+    /// Quantities are rounded down, prices are rounded down for buys and up for sells so that an order is never
+    /// worse than requested. Disabled by default.
+    pub fn set_rounding(mut self, rounding: bool) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Checks a price against the price filter, returns the price rounded to the tick size if rounding is enabled
+    pub fn check_price(&self, side: &OrderSide, price: Amount) -> Result<Amount, FilterViolation> {
+        let Some(filter) = self.price else {
+            return Ok(price);
+        };
+        let mut value = to_decimal(price);
+        if !filter.tick_size.is_zero() && !(value % filter.tick_size).is_zero() {
+            if !self.rounding {
+                return Err(FilterViolation::PriceNotOnTick {
+                    price,
+                    tick_size: to_amount(filter.tick_size),
+                });
+            }
+            value = round_to_step(value, filter.tick_size, *side == OrderSide::Sell);
+        }
+        let price = to_amount(value);
+        if !filter.min_price.is_zero() && value < filter.min_price {
+            return Err(FilterViolation::PriceTooLow {
+                price,
+                min_price: to_amount(filter.min_price),
+            });
+        }
+        if !filter.max_price.is_zero() && value > filter.max_price {
+            return Err(FilterViolation::PriceTooHigh {
+                price,
+                max_price: to_amount(filter.max_price),
+            });
+        }
+        Ok(price)
+    }
+
+    /// Checks a quantity against the lot size, and the market lot size for market orders, returns the quantity
+    /// rounded down to the step size if rounding is enabled
+    pub fn check_quantity(&self, quantity: Amount, market: bool) -> Result<Amount, FilterViolation> {
+        let market_lot_size = self.market_lot_size.filter(|_| market);
+        let mut value = to_decimal(quantity);
+        for filter in self.lot_size.iter().chain(market_lot_size.iter()) {
+            if !filter.step_size.is_zero() && !(value % filter.step_size).is_zero() {
+                if !self.rounding {
+                    return Err(FilterViolation::QuantityNotOnStep {
+                        quantity: to_amount(value),
+                        step_size: to_amount(filter.step_size),
+                    });
+                }
+                value = round_to_step(value, filter.step_size, false);
+            }
+            if !filter.min_qty.is_zero() && value < filter.min_qty {
+                return Err(FilterViolation::QuantityTooLow {
+                    quantity: to_amount(value),
+                    min_qty: to_amount(filter.min_qty),
+                });
+            }
+            if !filter.max_qty.is_zero() && value > filter.max_qty {
+                return Err(FilterViolation::QuantityTooHigh {
+                    quantity: to_amount(value),
+                    max_qty: to_amount(filter.max_qty),
+                });
+            }
+        }
+        Ok(to_amount(value))
+    }
+
+    /// Checks the notional, `price * quantity`, of an order
+    pub fn check_notional(&self, price: Amount, quantity: Amount) -> Result<(), FilterViolation> {
+        let Some(filter) = self.notional else {
+            return Ok(());
+        };
+        let notional = to_decimal(price) * to_decimal(quantity);
+        if !filter.min_notional.is_zero() && notional < filter.min_notional {
+            return Err(FilterViolation::NotionalTooLow {
+                notional: to_amount(notional),
+                min_notional: to_amount(filter.min_notional),
+            });
+        }
+        if !filter.max_notional.is_zero() && notional > filter.max_notional {
+            return Err(FilterViolation::NotionalTooHigh {
+                notional: to_amount(notional),
+                max_notional: to_amount(filter.max_notional),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks, and rounds in place, the prices and quantity of an order
+    pub(crate) fn apply(
+        &self,
+        side: &OrderSide,
+        market: bool,
+        price: &mut Option<Amount>,
+        stop_price: &mut Option<Amount>,
+        quantity: &mut Option<Amount>,
+    ) -> Result<(), FilterViolation> {
+        if let Some(price) = price {
+            *price = self.check_price(side, *price)?;
+        }
+        if let Some(stop_price) = stop_price {
+            *stop_price = self.check_price(side, *stop_price)?;
+        }
+        if let Some(quantity) = quantity {
+            *quantity = self.check_quantity(*quantity, market)?;
+        }
+        match (market, *price, *quantity) {
+            (false, Some(price), Some(quantity)) => self.check_notional(price, quantity),
+            _ => Ok(()),
+        }
+    }
+
+    fn set_price_filter(&mut self, min_price: f64, max_price: f64, tick_size: f64) {
+        self.price = Some(PriceFilter {
+            min_price: from_f64(min_price),
+            max_price: from_f64(max_price),
+            tick_size: from_f64(tick_size),
+        });
+    }
+
+    fn lot_size(min_qty: f64, max_qty: f64, step_size: f64) -> Option<LotSize> {
+        Some(LotSize {
+            min_qty: from_f64(min_qty),
+            max_qty: from_f64(max_qty),
+            step_size: from_f64(step_size),
+        })
+    }
+
+    fn add_notional(&mut self, min_notional: f64, max_notional: f64) {
+        let notional = self.notional.get_or_insert_with(Notional::default);
+        notional.min_notional = notional.min_notional.max(from_f64(min_notional));
+        if max_notional > 0.0 {
+            notional.max_notional = from_f64(max_notional);
+        }
+    }
+}
+
+impl From<&[Filters]> for OrderFilters {
+    fn from(filters: &[Filters]) -> Self {
+        let mut order_filters = OrderFilters::default();
+        for filter in filters {
+            match *filter {
+                Filters::PriceFilter {
+                    min_price,
+                    max_price,
+                    tick_size,
+                } => order_filters.set_price_filter(min_price, max_price, tick_size),
+                Filters::LotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => order_filters.lot_size = OrderFilters::lot_size(min_qty, max_qty, step_size),
+                Filters::MarketLotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => order_filters.market_lot_size = OrderFilters::lot_size(min_qty, max_qty, step_size),
+                Filters::MinNotional { min_notional, .. } => order_filters.add_notional(min_notional, 0.0),
+                Filters::Notional {
+                    min_notional,
+                    max_notional,
+                    ..
+                } => order_filters.add_notional(min_notional, max_notional),
+                _ => {}
+            }
+        }
+        order_filters
+    }
+}
+
+impl From<&[FuturesFilters]> for OrderFilters {
+    fn from(filters: &[FuturesFilters]) -> Self {
+        let mut order_filters = OrderFilters::default();
+        for filter in filters {
+            match filter {
+                FuturesFilters::PriceFilter {
+                    min_price,
+                    max_price,
+                    tick_size,
+                } => order_filters.set_price_filter(*min_price, *max_price, *tick_size),
+                FuturesFilters::LotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => order_filters.lot_size = OrderFilters::lot_size(*min_qty, *max_qty, *step_size),
+                FuturesFilters::MarketLotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => {
+                    order_filters.market_lot_size = Some(LotSize {
+                        min_qty: min_qty.parse().unwrap_or_default(),
+                        max_qty: max_qty.parse().unwrap_or_default(),
+                        step_size: step_size.parse().unwrap_or_default(),
+                    })
+                }
+                FuturesFilters::MinNotional { notional } => order_filters.add_notional(*notional, 0.0),
+                _ => {}
+            }
+        }
+        order_filters
+    }
+}
+
+fn from_f64(value: f64) -> Decimal { Decimal::from_f64(value).unwrap_or_default() }
+
+#[cfg(not(feature = "decimal"))]
+fn to_decimal(value: Amount) -> Decimal { from_f64(value) }
+
+#[cfg(feature = "decimal")]
+fn to_decimal(value: Amount) -> Decimal { value }
+
+#[cfg(not(feature = "decimal"))]
+fn to_amount(value: Decimal) -> Amount { value.to_f64().unwrap_or_default() }
+
+#[cfg(feature = "decimal")]
+fn to_amount(value: Decimal) -> Amount { value.normalize() }
+
+/// Closest multiple of `step` below `value`, or above it if `up`
+fn round_to_step(value: Decimal, step: Decimal, up: bool) -> Decimal {
+    let steps = value / step;
+    let steps = if up { steps.ceil() } else { steps.floor() };
+    steps * step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::amount;
+
+    fn spot_filters() -> Vec<Filters> {
+        serde_json::from_str(
+            r#"[
+                {"filterType": "PRICE_FILTER", "minPrice": "0.01000000", "maxPrice": "1000000.00000000", "tickSize": "0.01000000"},
+                {"filterType": "LOT_SIZE", "minQty": "0.00001000", "maxQty": "9000.00000000", "stepSize": "0.00001000"},
+                {"filterType": "MARKET_LOT_SIZE", "minQty": "0.00000000", "maxQty": "100.00000000", "stepSize": "0.00000000"},
+                {"filterType": "NOTIONAL", "minNotional": "5.00000000", "applyMinToMarket": true, "maxNotional": "9000000.00000000", "applyMaxToMarket": false, "avgPriceMins": 5},
+                {"filterType": "PERCENT_PRICE_BY_SIDE", "bidMultiplierUp": "5", "bidMultiplierDown": "0.2", "askMultiplierUp": "5", "askMultiplierDown": "0.2", "avgPriceMins": 5},
+                {"filterType": "TRAILING_DELTA", "minTrailingAboveDelta": 10, "maxTrailingAboveDelta": 2000, "minTrailingBelowDelta": 10, "maxTrailingBelowDelta": 2000},
+                {"filterType": "MAX_NUM_ICEBERG_ORDERS", "maxNumIcebergOrders": 5}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn orders_breaking_filters_are_rejected() {
+        let filters = OrderFilters::from(spot_filters().as_slice());
+        assert_eq!(
+            filters.check_price(&OrderSide::Buy, amount("25000.12")),
+            Ok(amount("25000.12"))
+        );
+        assert_eq!(
+            filters.check_price(&OrderSide::Buy, amount("25000.123")),
+            Err(FilterViolation::PriceNotOnTick {
+                price: amount("25000.123"),
+                tick_size: amount("0.01")
+            })
+        );
+        assert_eq!(
+            filters.check_quantity(amount("0.000001"), false),
+            Err(FilterViolation::QuantityNotOnStep {
+                quantity: amount("0.000001"),
+                step_size: amount("0.00001")
+            })
+        );
+        assert_eq!(
+            filters.check_quantity(amount("200.0"), true),
+            Err(FilterViolation::QuantityTooHigh {
+                quantity: amount("200.0"),
+                max_qty: amount("100.0")
+            })
+        );
+        assert_eq!(filters.check_quantity(amount("200.0"), false), Ok(amount("200.0")));
+        assert_eq!(
+            filters.check_notional(amount("25000.0"), amount("0.0001")),
+            Err(FilterViolation::NotionalTooLow {
+                notional: amount("2.5"),
+                min_notional: amount("5.0")
+            })
+        );
+    }
+
+    #[test]
+    fn prices_and_quantities_are_rounded() {
+        let filters = OrderFilters::from(spot_filters().as_slice()).set_rounding(true);
+        assert_eq!(filters.check_price(&OrderSide::Buy, amount("25000.129")), Ok(amount("25000.12")));
+        assert_eq!(filters.check_price(&OrderSide::Sell, amount("25000.121")), Ok(amount("25000.13")));
+        assert_eq!(filters.check_quantity(amount("0.123456789"), false), Ok(amount("0.12345")));
+        assert_eq!(
+            filters.check_quantity(amount("0.000001"), false),
+            Err(FilterViolation::QuantityTooLow {
+                quantity: amount("0.0"),
+                min_qty: amount("0.00001")
+            })
+        );
+    }
+
+    #[test]
+    fn futures_filters_are_converted() {
+        let filters: Vec<FuturesFilters> = serde_json::from_str(
+            r#"[
+                {"filterType": "PRICE_FILTER", "minPrice": "556.80", "maxPrice": "4529764", "tickSize": "0.10"},
+                {"filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "1000", "stepSize": "0.001"},
+                {"filterType": "MARKET_LOT_SIZE", "minQty": "0.001", "maxQty": "120", "stepSize": "0.001"},
+                {"filterType": "MIN_NOTIONAL", "notional": "100"}
+            ]"#,
+        )
+        .unwrap();
+        let filters = OrderFilters::from(filters.as_slice());
+        let (mut price, mut stop_price, mut quantity) = (Some(amount("30000.0")), None, Some(amount("0.001")));
+        assert_eq!(
+            filters.apply(&OrderSide::Buy, false, &mut price, &mut stop_price, &mut quantity),
+            Err(FilterViolation::NotionalTooLow {
+                notional: amount("30.0"),
+                min_notional: amount("100.0")
+            })
+        );
+        assert_eq!(
+            filters.check_price(&OrderSide::Buy, amount("500.0")),
+            Err(FilterViolation::PriceTooLow {
+                price: amount("500.0"),
+                min_price: amount("556.8")
+            })
+        );
+    }
+}
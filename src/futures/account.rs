@@ -6,15 +6,24 @@ use serde::Serializer;
 use crate::account::OrderCancellation;
 use crate::client::Client;
 use crate::errors::*;
-use crate::futures::futures_type::FuturesType;
-use crate::rest_model::{OrderSide, TimeInForce};
+use crate::filters::OrderFilters;
+use crate::futures::futures_type::{FuturesLinearType, FuturesType};
+use crate::rest_model::{Amount, OrderResponse, OrderSide, Success, TimeInForce};
 use crate::rest_model::{PairAndWindowQuery, PairQuery};
 use crate::util::*;
 
+use super::income::IncomeHistoryBuilder;
+use super::order::{
+    CancelBatchOrdersBuilder, ModifyBatchOrdersBuilder, ModifyOrderBuilder, PlaceBatchOrdersBuilder, PlaceOrderBuilder,
+    QueryOrderAmendmentsBuilder,
+};
+use super::position::PositionMarginHistoryBuilder;
+use super::query::{QueryAllOrdersBuilder, QueryForceOrdersBuilder, QueryOrderBuilder, QueryUserTradesBuilder};
 use super::router::FuturesRoute;
 use super::rest_model::{
-    AccountBalance, AccountInformation, CanceledOrder, ChangeLeverageResponse, Order, OrderType, Position,
-    PositionSide, Transaction, WorkingType,
+    AccountBalance, AccountInformation, CanceledOrder, ChangeLeverageResponse, IncomeDownloadId, IncomeDownloadLink,
+    MarginType, MultiAssetsMode, Order, OrderType, Position, PositionMarginAction, PositionMarginResult, PositionMode,
+    PositionSide, PriceMatch, SelfTradePreventionMode, Transaction, WorkingType,
 };
 
 #[derive(Clone)]
@@ -56,17 +65,45 @@ pub struct OrderRequest {
     pub order_type: OrderType,
     pub time_in_force: Option<TimeInForce>,
     #[serde(rename = "quantity")]
-    pub quantity: Option<f64>,
+    pub quantity: Option<Amount>,
     pub reduce_only: Option<bool>,
-    pub price: Option<f64>,
-    pub stop_price: Option<f64>,
+    pub price: Option<Amount>,
+    pub stop_price: Option<Amount>,
     pub close_position: Option<bool>,
-    pub activation_price: Option<f64>,
+    pub activation_price: Option<Amount>,
     pub callback_rate: Option<f64>,
     pub working_type: Option<WorkingType>,
     #[serde(serialize_with = "serialize_opt_as_uppercase")]
     pub price_protect: Option<bool>,
     pub new_client_order_id: Option<String>,
+    /// Only used with `OrderType::Limit`, `OrderType::Stop` and `OrderType::TakeProfit`, exclusive with `price`
+    pub price_match: Option<PriceMatch>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    /// Expiry in milliseconds of `TimeInForce::GTD` orders
+    pub good_till_date: Option<u64>,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// Filters of the symbol the price and quantity are validated against, and rounded to, before sending
+    #[serde(skip)]
+    pub filters: Option<OrderFilters>,
+}
+
+impl OrderRequest {
+    pub(crate) fn apply_filters(&mut self) -> Result<()> {
+        if let Some(filters) = &self.filters {
+            let market = matches!(
+                self.order_type,
+                OrderType::Market | OrderType::StopMarket | OrderType::TakeProfitMarket | OrderType::TrailingStopMarket
+            );
+            filters.apply(
+                &self.side,
+                market,
+                &mut self.price,
+                &mut self.stop_price,
+                &mut self.quantity,
+            )?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize)]
@@ -76,6 +113,38 @@ struct ChangePositionModeRequest {
     pub dual_side_position: bool,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeMultiAssetsModeRequest {
+    #[serde(serialize_with = "serialize_as_str")]
+    pub multi_assets_margin: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeMarginTypeRequest {
+    pub symbol: String,
+    pub margin_type: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangePositionMarginRequest {
+    pub symbol: String,
+    pub position_side: Option<PositionSide>,
+    pub amount: Amount,
+    #[serde(rename = "type")]
+    pub action: PositionMarginAction,
+}
+
+/// Treats the error of a change to the current value as a success
+fn unchanged_as_ok(result: Result<()>, code: i32) -> Result<()> {
+    match result {
+        Err(Error::BinanceError { response }) if response.code == code => Ok(()),
+        result => result,
+    }
+}
+
 impl<T> FuturesAccount<T>
     where T: FuturesType,
 {
@@ -83,40 +152,137 @@ impl<T> FuturesAccount<T>
         (self.router)(f)
     }
 
-    pub async fn place_order(&self, order: OrderRequest) -> Result<Transaction> {
+    pub async fn place_order(&self, mut order: OrderRequest) -> Result<Transaction> {
+        order.apply_filters()?;
         self.client
             .post_signed_p(self.get_api(FuturesRoute::Order).as_str(), order, self.recv_window)
             .await
     }
 
+    /// Builds an order with all the order parameters, see [`PlaceOrderBuilder`]
+    pub fn new_order(
+        &self,
+        symbol: impl Into<String>,
+        side: OrderSide,
+        order_type: OrderType,
+    ) -> PlaceOrderBuilder<'_> {
+        PlaceOrderBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::Order),
+            self.recv_window,
+            symbol.into(),
+            side,
+            order_type,
+        )
+    }
+
+    /// Places up to 5 orders at once, each order succeeding or failing on its own
+    pub fn place_batch_orders(&self) -> PlaceBatchOrdersBuilder<'_> {
+        PlaceBatchOrdersBuilder::new(&self.client, self.get_api(FuturesRoute::BatchOrders), self.recv_window)
+    }
+
+    /// Cancels up to 10 orders of `symbol` by order id or by client order id
+    pub fn cancel_batch_orders(&self, symbol: impl Into<String>) -> CancelBatchOrdersBuilder<'_> {
+        CancelBatchOrdersBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::BatchOrders),
+            self.recv_window,
+            symbol.into(),
+        )
+    }
+
+    /// Modifies the price and quantity of an open limit order, keeping its place in the order book when the price
+    /// is unchanged and the quantity is decreased, see [`ModifyOrderBuilder`]
+    pub fn modify_order(&self, symbol: impl Into<String>, side: OrderSide) -> ModifyOrderBuilder<'_> {
+        ModifyOrderBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::Order),
+            self.recv_window,
+            symbol.into(),
+            side,
+        )
+    }
+
+    /// Modifies up to 5 orders at once, each modification succeeding or failing on its own
+    pub fn modify_batch_orders(&self) -> ModifyBatchOrdersBuilder<'_> {
+        ModifyBatchOrdersBuilder::new(&self.client, self.get_api(FuturesRoute::BatchOrders), self.recv_window)
+    }
+
+    /// Amendment history of the orders of `symbol`
+    pub fn get_order_amendments(&self, symbol: impl Into<String>) -> QueryOrderAmendmentsBuilder<'_> {
+        QueryOrderAmendmentsBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::OrderAmendment),
+            self.recv_window,
+            symbol.into(),
+        )
+    }
+
     pub async fn get_open_orders(&self, symbol: impl Into<String>) -> Result<Vec<Order>> {
         let payload = build_signed_request_p([("symbol", symbol.into())], self.recv_window)?;
         self.client.get_signed(self.get_api(FuturesRoute::OpenOrders).as_str(), &payload).await
     }
 
+    /// An order of `symbol`, by order id or by client order id
+    pub fn get_order(&self, symbol: impl Into<String>) -> QueryOrderBuilder<'_> {
+        QueryOrderBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::Order),
+            self.recv_window,
+            symbol.into(),
+        )
+    }
+
+    /// An open order of `symbol`, by order id or by client order id
+    pub fn get_open_order(&self, symbol: impl Into<String>) -> QueryOrderBuilder<'_> {
+        QueryOrderBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::OpenOrder),
+            self.recv_window,
+            symbol.into(),
+        )
+    }
+
+    /// Orders of `symbol`, open or not, see [`QueryAllOrdersBuilder`]
+    pub fn get_all_orders(&self, symbol: impl Into<String>) -> QueryAllOrdersBuilder<'_> {
+        QueryAllOrdersBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::AllOrders),
+            self.recv_window,
+            symbol.into(),
+        )
+    }
+
+    /// Trades of the account on `symbol`, see [`QueryUserTradesBuilder`]
+    pub fn get_user_trades(&self, symbol: impl Into<String>) -> QueryUserTradesBuilder<'_> {
+        QueryUserTradesBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::UserTrades),
+            self.recv_window,
+            symbol.into(),
+        )
+    }
+
+    /// Liquidation and auto-deleveraging orders of the account
+    pub fn get_force_orders(&self) -> QueryForceOrdersBuilder<'_> {
+        QueryForceOrdersBuilder::new(&self.client, self.get_api(FuturesRoute::ForceOrders), self.recv_window)
+    }
+
     pub async fn limit_buy(
         &self,
         symbol: impl Into<String>,
-        qty: impl Into<f64>,
-        price: f64,
+        qty: impl Into<Amount>,
+        price: Amount,
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let order = OrderRequest {
             symbol: symbol.into(),
             side: OrderSide::Buy,
-            position_side: None,
             order_type: OrderType::Limit,
             time_in_force: Some(time_in_force),
             quantity: Some(qty.into()),
-            reduce_only: None,
             price: Some(price),
-            stop_price: None,
-            close_position: None,
-            activation_price: None,
-            callback_rate: None,
-            working_type: None,
-            price_protect: None,
-            new_client_order_id: None,
+            ..Default::default()
         };
         self.place_order(order).await
     }
@@ -124,26 +290,18 @@ impl<T> FuturesAccount<T>
     pub async fn limit_sell(
         &self,
         symbol: impl Into<String>,
-        qty: impl Into<f64>,
-        price: f64,
+        qty: impl Into<Amount>,
+        price: Amount,
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let order = OrderRequest {
             symbol: symbol.into(),
             side: OrderSide::Sell,
-            position_side: None,
             order_type: OrderType::Limit,
             time_in_force: Some(time_in_force),
             quantity: Some(qty.into()),
-            reduce_only: None,
             price: Some(price),
-            stop_price: None,
-            close_position: None,
-            activation_price: None,
-            callback_rate: None,
-            working_type: None,
-            price_protect: None,
-            new_client_order_id: None,
+            ..Default::default()
         };
         self.place_order(order).await
     }
@@ -152,24 +310,14 @@ impl<T> FuturesAccount<T>
     pub async fn market_buy<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
         where
             S: Into<String>,
-            F: Into<f64>,
+            F: Into<Amount>,
     {
         let order = OrderRequest {
             symbol: symbol.into(),
             side: OrderSide::Buy,
-            position_side: None,
             order_type: OrderType::Market,
-            time_in_force: None,
             quantity: Some(qty.into()),
-            reduce_only: None,
-            price: None,
-            stop_price: None,
-            close_position: None,
-            activation_price: None,
-            callback_rate: None,
-            working_type: None,
-            price_protect: None,
-            new_client_order_id: None,
+            ..Default::default()
         };
         self.place_order(order).await
     }
@@ -178,24 +326,14 @@ impl<T> FuturesAccount<T>
     pub async fn market_sell<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
         where
             S: Into<String>,
-            F: Into<f64>,
+            F: Into<Amount>,
     {
         let order: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             side: OrderSide::Sell,
-            position_side: None,
             order_type: OrderType::Market,
-            time_in_force: None,
             quantity: Some(qty.into()),
-            reduce_only: None,
-            price: None,
-            stop_price: None,
-            close_position: None,
-            activation_price: None,
-            callback_rate: None,
-            working_type: None,
-            price_protect: None,
-            new_client_order_id: None,
+            ..Default::default()
         };
         self.place_order(order).await
     }
@@ -234,6 +372,31 @@ impl<T> FuturesAccount<T>
         self.client.get_signed_d(self.get_api(FuturesRoute::Balance).as_str(), request.as_str()).await
     }
 
+    /// Funding fees, commissions, realized profits and other incomes, see [`IncomeHistoryBuilder`]
+    pub fn income_history(&self) -> IncomeHistoryBuilder<'_> {
+        IncomeHistoryBuilder::new(&self.client, self.get_api(FuturesRoute::Income), self.recv_window)
+    }
+
+    /// Starts preparing a download of the incomes from `start_time` to `end_time`, in ms, for histories too long to
+    /// be queried page by page
+    pub async fn income_download_id(&self, start_time: u64, end_time: u64) -> Result<IncomeDownloadId> {
+        let request = build_signed_request(
+            [("startTime", start_time.to_string()), ("endTime", end_time.to_string())],
+            self.recv_window,
+        )?;
+        self.client
+            .get_signed(self.get_api(FuturesRoute::IncomeDownloadId).as_str(), &request)
+            .await
+    }
+
+    /// Link of a download started with [`Self::income_download_id`], once completed
+    pub async fn income_download_link(&self, download_id: &str) -> Result<IncomeDownloadLink> {
+        let request = build_signed_request([("downloadId", download_id)], self.recv_window)?;
+        self.client
+            .get_signed(self.get_api(FuturesRoute::IncomeDownloadLink).as_str(), &request)
+            .await
+    }
+
     pub async fn change_initial_leverage<S>(&self, symbol: S, leverage: u8) -> Result<ChangeLeverageResponse>
         where
             S: Into<String>,
@@ -247,7 +410,8 @@ impl<T> FuturesAccount<T>
     }
 
     pub async fn change_position_mode(&self, dual_side_position: bool) -> Result<()> {
-        self.client
+        let _: Success = self
+            .client
             .post_signed_p(
                 self.get_api(FuturesRoute::PositionSide).as_str(),
                 ChangePositionModeRequest { dual_side_position },
@@ -257,11 +421,94 @@ impl<T> FuturesAccount<T>
         Ok(())
     }
 
+    /// Current position mode, hedge mode when dual side
+    pub async fn get_position_mode(&self) -> Result<PositionMode> {
+        let request = build_signed_request([("", "")], self.recv_window)?;
+        self.client
+            .get_signed(self.get_api(FuturesRoute::PositionSide).as_str(), &request)
+            .await
+    }
+
+    /// Changes the position mode unless it is already `dual_side_position`
+    pub async fn ensure_position_mode(&self, dual_side_position: bool) -> Result<()> {
+        unchanged_as_ok(
+            self.change_position_mode(dual_side_position).await,
+            error_codes::NO_NEED_TO_CHANGE_POSITION_SIDE,
+        )
+    }
+
+    pub async fn change_margin_type(&self, symbol: impl Into<String>, margin_type: MarginType) -> Result<()> {
+        let margin_type = match margin_type {
+            MarginType::Isolated => "ISOLATED",
+            MarginType::Cross => "CROSSED",
+        };
+        let _: Success = self
+            .client
+            .post_signed_p(
+                self.get_api(FuturesRoute::MarginType).as_str(),
+                ChangeMarginTypeRequest {
+                    symbol: symbol.into(),
+                    margin_type,
+                },
+                self.recv_window,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Sets the margin type and the leverage of `symbol`, whether they are already set or not
+    pub async fn ensure_symbol_settings(
+        &self,
+        symbol: impl Into<String>,
+        margin_type: MarginType,
+        leverage: u8,
+    ) -> Result<ChangeLeverageResponse> {
+        let symbol = symbol.into();
+        unchanged_as_ok(
+            self.change_margin_type(symbol.as_str(), margin_type).await,
+            error_codes::NO_NEED_TO_CHANGE_MARGIN_TYPE,
+        )?;
+        self.change_initial_leverage(symbol, leverage).await
+    }
+
+    /// Adds margin to, or reduces the margin of, an isolated position, of `position_side` in hedge mode
+    pub async fn change_position_margin(
+        &self,
+        symbol: impl Into<String>,
+        action: PositionMarginAction,
+        amount: Amount,
+        position_side: Option<PositionSide>,
+    ) -> Result<PositionMarginResult> {
+        self.client
+            .post_signed_p(
+                self.get_api(FuturesRoute::PositionMargin).as_str(),
+                ChangePositionMarginRequest {
+                    symbol: symbol.into(),
+                    position_side,
+                    amount,
+                    action,
+                },
+                self.recv_window,
+            )
+            .await
+    }
+
+    /// Changes of the margins of the isolated positions of `symbol`
+    pub fn position_margin_history(&self, symbol: impl Into<String>) -> PositionMarginHistoryBuilder<'_> {
+        PositionMarginHistoryBuilder::new(
+            &self.client,
+            self.get_api(FuturesRoute::PositionMarginHistory),
+            self.recv_window,
+            symbol.into(),
+        )
+    }
+
     pub async fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<()>
         where
             S: Into<String>,
     {
-        self.client
+        let _: Success = self
+            .client
             .delete_signed_p(
                 self.get_api(FuturesRoute::AllOpenOrders).as_str(),
                 PairQuery { symbol: symbol.into() },
@@ -271,3 +518,35 @@ impl<T> FuturesAccount<T>
         Ok(())
     }
 }
+
+/// Settings of USD-M futures only
+impl FuturesAccount<FuturesLinearType> {
+    /// Multi-assets mode, where margins of all assets are shared
+    pub async fn get_multi_assets_mode(&self) -> Result<MultiAssetsMode> {
+        let request = build_signed_request([("", "")], self.recv_window)?;
+        self.client
+            .get_signed(self.get_api(FuturesRoute::MultiAssetsMargin).as_str(), &request)
+            .await
+    }
+
+    /// Changes the multi-assets mode
+    pub async fn change_multi_assets_mode(&self, multi_assets_margin: bool) -> Result<()> {
+        let _: Success = self
+            .client
+            .post_signed_p(
+                self.get_api(FuturesRoute::MultiAssetsMargin).as_str(),
+                ChangeMultiAssetsModeRequest { multi_assets_margin },
+                self.recv_window,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Changes the multi-assets mode unless it is already `multi_assets_margin`
+    pub async fn ensure_multi_assets_mode(&self, multi_assets_margin: bool) -> Result<()> {
+        unchanged_as_ok(
+            self.change_multi_assets_mode(multi_assets_margin).await,
+            error_codes::NO_NEED_TO_CHANGE_MULTI_ASSETS_MODE,
+        )
+    }
+}
@@ -1,9 +1,13 @@
+use crate::config::Config;
 use crate::futures::router::{FuturesRoute, Router};
+use crate::rate_limit::RateLimiter;
 
 pub trait FuturesType {
     fn router() ->  fn(FuturesRoute) ->String;
     fn endpoint() -> String;
     fn ws_endpoint() -> String;
+    /// Rate limiter of the requests of this market
+    fn rate_limiter(config: &Config) -> Option<RateLimiter>;
 }
 
 pub struct FuturesLinearType;
@@ -20,6 +24,10 @@ impl FuturesType for FuturesLinearType {
     fn ws_endpoint() -> String {
         "wss://fstream.binance.com".into()
     }
+
+    fn rate_limiter(config: &Config) -> Option<RateLimiter> {
+        config.futures_rate_limiter.clone()
+    }
 }
 
 pub struct FuturesInverseType;
@@ -36,4 +44,26 @@ impl FuturesType for FuturesInverseType {
     fn ws_endpoint() -> String {
         "wss://dstream.binance.com".into()
     }
-}
\ No newline at end of file
+
+    fn rate_limiter(config: &Config) -> Option<RateLimiter> {
+        config.coin_m_futures_rate_limiter.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::RateLimitMode;
+
+    #[test]
+    fn markets_have_separate_rate_limiters() {
+        let usd_m = RateLimiter::new(RateLimitMode::Reject);
+        let coin_m = RateLimiter::new(RateLimitMode::Reject);
+        let config = Config::default()
+            .set_futures_rate_limiter(usd_m.clone())
+            .set_coin_m_futures_rate_limiter(coin_m.clone());
+        assert_eq!(FuturesLinearType::rate_limiter(&config), Some(usd_m));
+        assert_eq!(FuturesInverseType::rate_limiter(&config), Some(coin_m));
+        assert_ne!(FuturesLinearType::rate_limiter(&config), FuturesInverseType::rate_limiter(&config));
+    }
+}
@@ -32,8 +32,14 @@ impl<T> FuturesGeneral<T>
 
     // Obtain exchange information
     // - Current exchange trading rules and symbol information
+    // - Rate limits, loaded into the configured rate limiter if any
     pub async fn exchange_info(&self) -> Result<ExchangeInformation> {
-        self.client.get_p(self.get_api(FuturesRoute::ExchangeInfo).as_str(), None).await
+        let info: ExchangeInformation = self
+            .client
+            .get_p(self.get_api(FuturesRoute::ExchangeInfo).as_str(), None)
+            .await?;
+        self.client.update_rate_limits(&info.rate_limits);
+        Ok(info)
     }
 
     // Get Symbol information
@@ -0,0 +1,313 @@
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+
+use crate::client::Client;
+use crate::errors::*;
+
+use super::rest_model::{Income, IncomeType};
+
+/// Largest page of the income history
+pub const MAX_INCOME_PAGE: u16 = 1000;
+
+#[derive(Serialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+struct IncomeRequest {
+    symbol: Option<String>,
+    income_type: Option<IncomeType>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    page: Option<u32>,
+    limit: Option<u16>,
+}
+
+/// 获取账户损益资金流水
+/// https://binance-docs.github.io/apidocs/futures/cn/#user_data-7
+///
+/// Without a start and end time, only the incomes of the last 7 days are queried.
+pub struct IncomeHistoryBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: IncomeRequest,
+    recv_window: u64,
+}
+
+impl<'a> IncomeHistoryBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: IncomeRequest::default(),
+            recv_window,
+        }
+    }
+
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.playload.symbol = Some(symbol.into());
+        self
+    }
+
+    pub fn income_type(mut self, income_type: IncomeType) -> Self {
+        self.playload.income_type = Some(income_type);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    /// Page of the query, USD-M futures only
+    pub fn page(mut self, page: u32) -> Self {
+        self.playload.page = Some(page);
+        self
+    }
+
+    /// Defaults to 100, at most 1000
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<Income>> {
+        self.client
+            .get_signed_p(self.endpoint.as_str(), Some(&self.playload), self.recv_window)
+            .await
+    }
+
+    /// Every income from `start_time` to `end_time`, in ms, querying the pages one after the other
+    ///
+    /// Pages are `limit` incomes long, at most 1000 by default. Incomes of the same millisecond across two pages are
+    /// streamed once, by transaction id. A millisecond with more than 1000 incomes cannot be paged through, the stream
+    /// then fails with `Error::IncomePageOverflow` rather than skipping incomes.
+    pub fn into_stream(self, start_time: u64, end_time: u64) -> BoxStream<'static, Result<Income>> {
+        let limit = self.playload.limit.unwrap_or(MAX_INCOME_PAGE).clamp(1, MAX_INCOME_PAGE);
+        let pager = IncomePager {
+            client: self.client.clone(),
+            endpoint: self.endpoint,
+            request: IncomeRequest {
+                start_time: Some(start_time),
+                end_time: Some(end_time),
+                page: None,
+                limit: Some(limit),
+                ..self.playload
+            },
+            recv_window: self.recv_window,
+            seen: vec![],
+            overflow: None,
+            done: start_time > end_time,
+        };
+        stream::try_unfold(pager, |mut pager| async move {
+            let incomes = pager.next_page().await?;
+            Ok::<_, Error>(incomes.map(|incomes| (incomes, pager)))
+        })
+        .map_ok(|incomes| stream::iter(incomes.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+}
+
+/// Paginated query of the income history, by start time
+struct IncomePager {
+    client: Client,
+    endpoint: String,
+    request: IncomeRequest,
+    recv_window: u64,
+    /// Transaction ids of the incomes streamed at the current start time
+    seen: Vec<u64>,
+    /// Start time of a full page of a single millisecond, failing the stream once its incomes are streamed
+    overflow: Option<u64>,
+    done: bool,
+}
+
+impl IncomePager {
+    /// Queries pages until one has new incomes, `None` once the range is exhausted, an error once a millisecond
+    /// overflows the pages
+    async fn next_page(&mut self) -> Result<Option<Vec<Income>>> {
+        while !self.done {
+            let incomes: Vec<Income> = self
+                .client
+                .get_signed_p(self.endpoint.as_str(), Some(&self.request), self.recv_window)
+                .await?;
+            let start = self.request.start_time.unwrap_or_default();
+            let limit = self.request.limit.unwrap_or(MAX_INCOME_PAGE);
+            let full = incomes.len() >= limit as usize;
+            let last = incomes.last().map(|income| income.time);
+            let incomes: Vec<Income> = incomes
+                .into_iter()
+                .filter(|income| !self.seen.contains(&income.tran_id))
+                .collect();
+            match last {
+                // the next page starts at the last millisecond of this one, which may have more incomes
+                Some(last) if full && last > start => {
+                    self.request.start_time = Some(last);
+                    self.seen = incomes
+                        .iter()
+                        .filter(|income| income.time == last)
+                        .map(|income| income.tran_id)
+                        .collect();
+                }
+                // a full page of a single millisecond, its other incomes may only fit in a larger page
+                Some(_) if full && limit < MAX_INCOME_PAGE => {
+                    self.request.limit = Some(MAX_INCOME_PAGE);
+                    self.seen.extend(incomes.iter().map(|income| income.tran_id));
+                }
+                Some(_) if full => {
+                    self.overflow = Some(start);
+                    self.done = true;
+                }
+                _ => self.done = true,
+            }
+            if self.request.start_time > self.request.end_time {
+                self.done = true;
+            }
+            if !incomes.is_empty() {
+                return Ok(Some(incomes));
+            }
+        }
+        match self.overflow.take() {
+            Some(time) => Err(Error::IncomePageOverflow {
+                time,
+                limit: MAX_INCOME_PAGE,
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mock", feature = "futures_api"))]
+mod tests {
+    use reqwest::Method;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::futures::futures_type::FuturesLinearType;
+    use crate::futures::rest_model::DownloadStatus;
+    use crate::mock::MockResponse;
+    use crate::test_util::{account, param};
+
+    fn income(tran_id: u64, time: u64) -> Value {
+        json!({
+            "symbol": "BTCUSDT", "incomeType": "FUNDING_FEE", "income": "-0.37500000", "asset": "USDT",
+            "info": "FUNDING_FEE", "time": time, "tranId": tran_id, "tradeId": ""
+        })
+    }
+
+    #[tokio::test]
+    async fn income_history_is_filtered_by_type() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::GET,
+            "/fapi/v1/income",
+            MockResponse::json(json!([income(1, 1000)])),
+        );
+        let incomes = account
+            .income_history()
+            .symbol("BTCUSDT")
+            .income_type(IncomeType::FundingFee)
+            .limit(10)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(incomes[0].income_type, IncomeType::FundingFee);
+        assert_eq!(incomes[0].tran_id, 1);
+        let request = &server.requests()[0];
+        assert_eq!(request.param("incomeType"), Some("FUNDING_FEE"));
+        assert_eq!(request.param("symbol"), Some("BTCUSDT"));
+        assert_eq!(request.param("startTime"), None);
+    }
+
+    #[tokio::test]
+    async fn income_history_is_paginated_without_duplicates() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        // three incomes per millisecond, e.g. funding fees of three symbols
+        let incomes: Vec<Value> = (0..12u64).map(|id| income(id, 1000 * (id / 3 + 1))).collect();
+        server.mock_fn(Method::GET, "/fapi/v1/income", move |request| {
+            let (start, end) = (param(request, "startTime").unwrap(), param(request, "endTime").unwrap());
+            let page: Vec<&Value> = incomes
+                .iter()
+                .filter(|income| (start..=end).contains(&income["time"].as_u64().unwrap()))
+                .take(param(request, "limit").unwrap() as usize)
+                .collect();
+            MockResponse::json(json!(page))
+        });
+        let ids: Vec<u64> = account
+            .income_history()
+            .limit(4)
+            .into_stream(0, 3000)
+            .map_ok(|income| income.tran_id)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids, (0..9).collect::<Vec<_>>());
+        let starts: Vec<Option<u64>> = server.requests().iter().map(|r| param(r, "startTime")).collect();
+        assert_eq!(starts, vec![Some(0), Some(2000), Some(3000)]);
+    }
+
+    #[tokio::test]
+    async fn crowded_milliseconds_are_not_skipped() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        // 3 incomes at 1000, then 1001 incomes at 2000
+        let incomes: Vec<Value> = (0..1004u64)
+            .map(|id| income(id, if id < 3 { 1000 } else { 2000 }))
+            .collect();
+        server.mock_fn(Method::GET, "/fapi/v1/income", move |request| {
+            let (start, end) = (param(request, "startTime").unwrap(), param(request, "endTime").unwrap());
+            let page: Vec<&Value> = incomes
+                .iter()
+                .filter(|income| (start..=end).contains(&income["time"].as_u64().unwrap()))
+                .take(param(request, "limit").unwrap() as usize)
+                .collect();
+            MockResponse::json(json!(page))
+        });
+        let ids: Vec<u64> = account
+            .income_history()
+            .limit(2)
+            .into_stream(1000, 1000)
+            .map_ok(|income| income.tran_id)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(ids, vec![0, 1, 2]);
+        assert_eq!(server.requests()[1].param("limit"), Some("1000"));
+
+        let results: Vec<Result<Income>> = account.income_history().into_stream(2000, 3000).collect().await;
+        assert_eq!(results.len(), 1001);
+        assert!(results[..1000].iter().all(|result| result.is_ok()));
+        assert!(matches!(results[1000], Err(Error::IncomePageOverflow { time: 2000, limit: 1000 })));
+    }
+
+    #[tokio::test]
+    async fn income_downloads_are_requested_by_id() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::GET,
+            "/fapi/v1/income/asyn",
+            MockResponse::json(json!({"avgCostTimestampOfLast30d": 7241837, "downloadId": "546975389218332672"})),
+        );
+        server.mock(
+            Method::GET,
+            "/fapi/v1/income/asyn/id",
+            MockResponse::json(json!({
+                "downloadId": "546975389218332672", "status": "processing", "url": "", "notified": false,
+                "expirationTimestamp": -1, "isExpired": null
+            })),
+        );
+        let download = account.income_download_id(1000, 2000).await.unwrap();
+        assert_eq!(download.download_id, "546975389218332672");
+        let link = account.income_download_link(&download.download_id).await.unwrap();
+        assert_eq!(link.status, DownloadStatus::Processing);
+        let requests = server.requests();
+        assert_eq!(requests[0].param("startTime"), Some("1000"));
+        assert_eq!(requests[1].param("downloadId"), Some("546975389218332672"));
+    }
+}
@@ -6,7 +6,7 @@ use crate::errors::*;
 use crate::futures::futures_type::FuturesType;
 use crate::futures::rest_model::*;
 use crate::rest_model::{
-    BookTickers, KlineSummaries, KlineSummary, PairAndWindowQuery, PairQuery, SymbolPrice, Tickers,
+    BookTickers, KlineInterval, KlineRow, KlineSummaries, KlineSummary, PairAndWindowQuery, PairQuery, SymbolPrice, Tickers,
 };
 use crate::util::*;
 
@@ -41,6 +41,17 @@ impl<T> FuturesMarket<T>
             .await
     }
 
+    /// Order book with a custom depth limit
+    /// Supported limits are: 5, 10, 20, 50, 100, 500, 1000
+    pub async fn get_custom_depth<S>(&self, symbol: S, limit: u16) -> Result<OrderBook>
+        where
+            S: Into<String>,
+    {
+        let parameters = [("symbol", symbol.into()), ("limit", limit.to_string())];
+        let request = build_request(parameters);
+        self.client.get(self.get_api(FuturesRoute::Depth).as_str(), Some(&request)).await
+    }
+
     /// Get trades for a pair
     pub async fn get_trades<S>(&self, symbol: S) -> Result<Trades>
         where
@@ -298,80 +309,62 @@ impl<T> FuturesMarket<T>
             .await
     }
 
-    /// Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' klines for given symbol and interval
     /// https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data
-    pub async fn get_klines<S1, S2, S3, S4, S5>(
+    pub async fn get_klines<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<KlineSummaries>
         where
             S1: Into<String>,
-            S2: Into<String>,
-            S3: Into<u16>,
+            S2: Into<u16>,
+            S3: Into<Option<u64>>,
             S4: Into<Option<u64>>,
-            S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
-        let data: Vec<Vec<Value>> = self.client.get_d(self.get_api(FuturesRoute::Klines).as_str(), Some(query)).await?;
-
-        let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
-        );
+        let rows: Vec<KlineRow> = self.client.get_d(self.get_api(FuturesRoute::Klines).as_str(), Some(query)).await?;
+
+        let klines = KlineSummaries::AllKlineSummaries(rows.into_iter().map(KlineSummary::from).collect());
         Ok(klines)
     }
 
-    /// Returns up to 'limit' blvt klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' blvt klines for given symbol and interval
     /// Note that the symbol is not the traditional pair but rather {symbol}{UP|DOWN}
     /// https://binance-docs.github.io/apidocs/futures/en/#blvt-nav-kline-candlestick-streams
     /// As the vector fields are undocumented on binance futures you are un your own, follow
     /// KlineSummary for an example
-    pub async fn get_blvt_klines_v<S1, S2, S3, S4, S5>(
+    pub async fn get_blvt_klines_v<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<Vec<Vec<Value>>>
         where
             S1: Into<String>,
-            S2: Into<String>,
-            S3: Into<u16>,
+            S2: Into<u16>,
+            S3: Into<Option<u64>>,
             S4: Into<Option<u64>>,
-            S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
@@ -380,31 +373,30 @@ impl<T> FuturesMarket<T>
         Ok(klines)
     }
 
-    /// Returns up to 'limit' mark price klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' mark price klines for given symbol and interval
     /// https://binance-docs.github.io/apidocs/futures/en/#mark-price-kline-candlestick-data
     /// As the vector fields are undocumented on binance futures you are un your own, follow
     /// KlineSummary for an example
-    pub async fn get_mark_price_klines_v<S1, S2, S3, S4, S5>(
+    pub async fn get_mark_price_klines_v<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<Vec<Vec<Value>>>
         where
             S1: Into<String>,
-            S2: Into<String>,
-            S3: Into<u16>,
+            S2: Into<u16>,
+            S3: Into<Option<u64>>,
             S4: Into<Option<u64>>,
-            S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
@@ -413,31 +405,30 @@ impl<T> FuturesMarket<T>
         Ok(klines)
     }
 
-    /// Returns up to 'limit' index price klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' index price klines for given symbol and interval
     /// https://binance-docs.github.io/apidocs/futures/en/#index-price-kline-candlestick-data
     /// As the vector fields are undocumented on binance futures you are un your own, follow
     /// KlineSummary for an example
-    pub async fn get_index_price_klines_v<S1, S2, S3, S4, S5>(
+    pub async fn get_index_price_klines_v<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<Vec<Vec<Value>>>
         where
             S1: Into<String>,
-            S2: Into<String>,
-            S3: Into<u16>,
+            S2: Into<u16>,
+            S3: Into<Option<u64>>,
             S4: Into<Option<u64>>,
-            S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
@@ -447,31 +438,30 @@ impl<T> FuturesMarket<T>
         Ok(klines)
     }
 
-    /// Returns up to 'limit' continuous contract klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' continuous contract klines for given symbol and interval
     /// https://binance-docs.github.io/apidocs/futures/en/#continuous-contract-kline-candlestick-data
     /// As the vector fields are undocumented on binance futures you are un your own, follow
     /// KlineSummary for an example
-    pub async fn get_continuous_contract_klines_v<S1, S2, S3, S4, S5>(
+    pub async fn get_continuous_contract_klines_v<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<Vec<Vec<Value>>>
         where
             S1: Into<String>,
-            S2: Into<String>,
-            S3: Into<u16>,
+            S2: Into<u16>,
+            S3: Into<Option<u64>>,
             S4: Into<Option<u64>>,
-            S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
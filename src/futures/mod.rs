@@ -1,6 +1,10 @@
 pub mod account;
 pub mod general;
+pub mod income;
 pub mod market;
+pub mod order;
+pub mod position;
+pub mod query;
 pub mod rest_model;
 pub mod websockets;
 pub mod ws_model;
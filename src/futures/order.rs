@@ -0,0 +1,770 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::client::Client;
+use crate::errors::*;
+use crate::filters::OrderFilters;
+use crate::rest_model::{Amount, OrderResponse, OrderSide, TimeInForce};
+use crate::util::*;
+
+use super::account::OrderRequest;
+use super::rest_model::{
+    CanceledOrder, OrderAmendment, OrderType, PositionSide, PriceMatch, SelfTradePreventionMode, Transaction,
+    WorkingType,
+};
+
+/// Most orders accepted by a batch
+pub const MAX_BATCH_ORDERS: usize = 5;
+
+/// Most orders cancelled by a batch
+pub const MAX_BATCH_CANCELLATIONS: usize = 10;
+
+/// Modification of the price and quantity of an open limit order, by order id or by client order id
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifyOrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+    pub quantity: Option<Amount>,
+    pub price: Option<Amount>,
+    /// Exclusive with `price`
+    pub price_match: Option<PriceMatch>,
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct OrderAmendmentsRequest {
+    symbol: String,
+    order_id: Option<u64>,
+    orig_client_order_id: Option<String>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    limit: Option<u16>,
+}
+
+fn check_batch_size(len: usize, max: usize) -> Result<()> {
+    if len == 0 || len > max {
+        return Err(Error::InvalidOrderError {
+            msg: format!("a batch has between 1 and {max} orders, got {len}"),
+        });
+    }
+    Ok(())
+}
+
+/// Results of a batch, in the order of the batch, failed entries are a `BinanceContentError`
+fn batch_results<R: DeserializeOwned>(entries: Vec<Value>) -> Vec<Result<R>> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            if entry.get("code").is_some() && entry.get("orderId").is_none() {
+                Err(serde_json::from_value::<BinanceContentError>(entry)?.into())
+            } else {
+                Ok(serde_json::from_value(entry)?)
+            }
+        })
+        .collect()
+}
+
+/// URL-encoded JSON of a batch parameter
+fn batch_param<P: serde::Serialize>(param: &P) -> Result<String> {
+    let json = serde_json::to_string(param)?;
+    Ok(url::form_urlencoded::byte_serialize(json.as_bytes()).collect())
+}
+
+/// An order of a batch, as a JSON object of its query parameters
+fn batch_order<O: serde::Serialize>(order: &O) -> Result<serde_json::Map<String, Value>> {
+    let query = qs::to_string(order)?;
+    Ok(url::form_urlencoded::parse(query.as_bytes())
+        .map(|(k, v)| (k.into_owned(), Value::String(v.into_owned())))
+        .collect())
+}
+
+/// 下单
+/// https://binance-docs.github.io/apidocs/futures/cn/#trade-3
+pub struct PlaceOrderBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: OrderRequest,
+    recv_window: u64,
+}
+
+impl<'a> PlaceOrderBuilder<'a> {
+    pub(crate) fn new(
+        client: &'a Client,
+        endpoint: String,
+        recv_window: u64,
+        symbol: String,
+        side: OrderSide,
+        order_type: OrderType,
+    ) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: OrderRequest {
+                symbol,
+                side,
+                order_type,
+                ..Default::default()
+            },
+            recv_window,
+        }
+    }
+
+    pub fn position_side(mut self, position_side: PositionSide) -> Self {
+        self.playload.position_side = Some(position_side);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.playload.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn quantity(mut self, quantity: Amount) -> Self {
+        self.playload.quantity = Some(quantity);
+        self
+    }
+
+    pub fn reduce_only(mut self, reduce_only: bool) -> Self {
+        self.playload.reduce_only = Some(reduce_only);
+        self
+    }
+
+    pub fn price(mut self, price: Amount) -> Self {
+        self.playload.price = Some(price);
+        self
+    }
+
+    pub fn stop_price(mut self, stop_price: Amount) -> Self {
+        self.playload.stop_price = Some(stop_price);
+        self
+    }
+
+    /// Close the whole position once triggered, only used with stop market and take profit market orders
+    pub fn close_position(mut self, close_position: bool) -> Self {
+        self.playload.close_position = Some(close_position);
+        self
+    }
+
+    pub fn activation_price(mut self, activation_price: Amount) -> Self {
+        self.playload.activation_price = Some(activation_price);
+        self
+    }
+
+    pub fn callback_rate(mut self, callback_rate: f64) -> Self {
+        self.playload.callback_rate = Some(callback_rate);
+        self
+    }
+
+    pub fn working_type(mut self, working_type: WorkingType) -> Self {
+        self.playload.working_type = Some(working_type);
+        self
+    }
+
+    pub fn price_protect(mut self, price_protect: bool) -> Self {
+        self.playload.price_protect = Some(price_protect);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: String) -> Self {
+        self.playload.new_client_order_id = Some(new_client_order_id);
+        self
+    }
+
+    pub fn price_match(mut self, price_match: PriceMatch) -> Self {
+        self.playload.price_match = Some(price_match);
+        self
+    }
+
+    pub fn self_trade_prevention_mode(mut self, self_trade_prevention_mode: SelfTradePreventionMode) -> Self {
+        self.playload.self_trade_prevention_mode = Some(self_trade_prevention_mode);
+        self
+    }
+
+    pub fn good_till_date(mut self, good_till_date: u64) -> Self {
+        self.playload.good_till_date = Some(good_till_date);
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponse) -> Self {
+        self.playload.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    /// Validates the price and quantity against the filters of the symbol before sending,
+    /// rounding them to the tick and step sizes if the filters round.
+    pub fn filters(mut self, filters: OrderFilters) -> Self {
+        self.playload.filters = Some(filters);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    /// The order, to be sent in a batch
+    pub fn into_request(self) -> OrderRequest { self.playload }
+
+    pub async fn send(mut self) -> Result<Transaction> {
+        self.playload.apply_filters()?;
+        self.client
+            .post_signed_p(self.endpoint.as_str(), &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 批量下单，最多5个订单
+/// https://binance-docs.github.io/apidocs/futures/cn/#trade-4
+pub struct PlaceBatchOrdersBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    orders: Vec<OrderRequest>,
+    recv_window: u64,
+}
+
+impl<'a> PlaceBatchOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64) -> Self {
+        Self {
+            client,
+            endpoint,
+            orders: vec![],
+            recv_window,
+        }
+    }
+
+    pub fn order(mut self, order: OrderRequest) -> Self {
+        self.orders.push(order);
+        self
+    }
+
+    pub fn orders(mut self, orders: impl IntoIterator<Item = OrderRequest>) -> Self {
+        self.orders.extend(orders);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    /// The result of each order, in the order of the batch
+    pub async fn send(mut self) -> Result<Vec<Result<Transaction>>> {
+        check_batch_size(self.orders.len(), MAX_BATCH_ORDERS)?;
+        let mut orders = Vec::with_capacity(self.orders.len());
+        for order in &mut self.orders {
+            order.apply_filters()?;
+            orders.push(batch_order(order)?);
+        }
+        let data = build_signed_request([("batchOrders", batch_param(&orders)?)], self.recv_window)?;
+        let entries: Vec<Value> = self.client.post_signed(self.endpoint.as_str(), data.as_str()).await?;
+        Ok(batch_results(entries))
+    }
+}
+
+/// 批量撤销订单，最多10个订单
+/// https://binance-docs.github.io/apidocs/futures/cn/#trade-6
+pub struct CancelBatchOrdersBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    symbol: String,
+    order_ids: Vec<u64>,
+    orig_client_order_ids: Vec<String>,
+    recv_window: u64,
+}
+
+impl<'a> CancelBatchOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64, symbol: String) -> Self {
+        Self {
+            client,
+            endpoint,
+            symbol,
+            order_ids: vec![],
+            orig_client_order_ids: vec![],
+            recv_window,
+        }
+    }
+
+    pub fn order_ids(mut self, order_ids: Vec<u64>) -> Self {
+        self.order_ids = order_ids;
+        self
+    }
+
+    pub fn orig_client_order_ids(mut self, orig_client_order_ids: Vec<String>) -> Self {
+        self.orig_client_order_ids = orig_client_order_ids;
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    fn get_params(&self) -> Result<String> {
+        let parameters = [
+            Some(("symbol", self.symbol.clone())),
+            (!self.order_ids.is_empty())
+                .then(|| batch_param(&self.order_ids).map(|ids| ("orderIdList", ids)))
+                .transpose()?,
+            (!self.orig_client_order_ids.is_empty())
+                .then(|| batch_param(&self.orig_client_order_ids).map(|ids| ("origClientOrderIdList", ids)))
+                .transpose()?,
+        ];
+        build_signed_request(parameters.into_iter().flatten(), self.recv_window)
+    }
+
+    /// The result of each cancellation, in the order of the ids
+    pub async fn send(self) -> Result<Vec<Result<CanceledOrder>>> {
+        check_batch_size(
+            self.order_ids.len().max(self.orig_client_order_ids.len()),
+            MAX_BATCH_CANCELLATIONS,
+        )?;
+        let data = self.get_params()?;
+        let entries: Vec<Value> = self.client.delete_signed(self.endpoint.as_str(), data.as_str()).await?;
+        Ok(batch_results(entries))
+    }
+}
+
+/// 修改订单，仅限限价单，保持订单在队列中的位置
+/// https://binance-docs.github.io/apidocs/futures/cn/#trade-5
+pub struct ModifyOrderBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: ModifyOrderRequest,
+    recv_window: u64,
+}
+
+impl<'a> ModifyOrderBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64, symbol: String, side: OrderSide) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: ModifyOrderRequest {
+                symbol,
+                side,
+                ..Default::default()
+            },
+            recv_window,
+        }
+    }
+
+    pub fn order_id(mut self, order_id: u64) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn orig_client_order_id(mut self, orig_client_order_id: String) -> Self {
+        self.playload.orig_client_order_id = Some(orig_client_order_id);
+        self
+    }
+
+    pub fn quantity(mut self, quantity: Amount) -> Self {
+        self.playload.quantity = Some(quantity);
+        self
+    }
+
+    pub fn price(mut self, price: Amount) -> Self {
+        self.playload.price = Some(price);
+        self
+    }
+
+    pub fn price_match(mut self, price_match: PriceMatch) -> Self {
+        self.playload.price_match = Some(price_match);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    /// The modification, to be sent in a batch
+    pub fn into_request(self) -> ModifyOrderRequest { self.playload }
+
+    pub async fn send(self) -> Result<Transaction> {
+        self.client
+            .put_signed_p(self.endpoint.as_str(), &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 批量修改订单，最多5个订单
+/// https://binance-docs.github.io/apidocs/futures/cn/#trade-7
+pub struct ModifyBatchOrdersBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    orders: Vec<ModifyOrderRequest>,
+    recv_window: u64,
+}
+
+impl<'a> ModifyBatchOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64) -> Self {
+        Self {
+            client,
+            endpoint,
+            orders: vec![],
+            recv_window,
+        }
+    }
+
+    pub fn order(mut self, order: ModifyOrderRequest) -> Self {
+        self.orders.push(order);
+        self
+    }
+
+    pub fn orders(mut self, orders: impl IntoIterator<Item = ModifyOrderRequest>) -> Self {
+        self.orders.extend(orders);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    /// The result of each modification, in the order of the batch
+    pub async fn send(self) -> Result<Vec<Result<Transaction>>> {
+        check_batch_size(self.orders.len(), MAX_BATCH_ORDERS)?;
+        let orders = self.orders.iter().map(batch_order).collect::<Result<Vec<_>>>()?;
+        let data = build_signed_request([("batchOrders", batch_param(&orders)?)], self.recv_window)?;
+        let entries: Vec<Value> = self.client.put_signed(self.endpoint.as_str(), data.as_str()).await?;
+        Ok(batch_results(entries))
+    }
+}
+
+/// 查询订单修改历史
+/// https://binance-docs.github.io/apidocs/futures/cn/#user_data-3
+pub struct QueryOrderAmendmentsBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: OrderAmendmentsRequest,
+    recv_window: u64,
+}
+
+impl<'a> QueryOrderAmendmentsBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64, symbol: String) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: OrderAmendmentsRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window,
+        }
+    }
+
+    pub fn order_id(mut self, order_id: u64) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn orig_client_order_id(mut self, orig_client_order_id: String) -> Self {
+        self.playload.orig_client_order_id = Some(orig_client_order_id);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    /// Defaults to 50, at most 100
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<OrderAmendment>> {
+        self.client
+            .get_signed_p(self.endpoint.as_str(), Some(&self.playload), self.recv_window)
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "mock", feature = "futures_api"))]
+mod tests {
+    use reqwest::Method;
+    use serde_json::json;
+
+    use super::*;
+    use crate::futures::futures_type::{FuturesInverseType, FuturesLinearType};
+    use crate::mock::MockResponse;
+    use crate::rest_model::OrderStatus;
+    use crate::test_util::{account, amount};
+
+    fn transaction(order_id: u64) -> Value {
+        json!({
+            "clientOrderId": format!("order{order_id}"), "cumQty": "0", "cumQuote": "0", "executedQty": "0",
+            "orderId": order_id, "avgPrice": "0.00000", "origQty": "0.001", "price": "25000", "reduceOnly": false,
+            "side": "BUY", "positionSide": "BOTH", "status": "NEW", "stopPrice": "0", "closePosition": false,
+            "symbol": "BTCUSDT", "timeInForce": "GTD", "type": "LIMIT", "origType": "LIMIT",
+            "updateTime": 1566818724722u64, "workingType": "CONTRACT_PRICE", "priceMatch": "QUEUE",
+            "selfTradePreventionMode": "EXPIRE_MAKER", "goodTillDate": 1693207680000u64
+        })
+    }
+
+    /// COIN-M orders have a `cumBase` and a `pair` instead of a `cumQuote`
+    fn coin_m_transaction(order_id: u64) -> Value {
+        json!({
+            "clientOrderId": format!("order{order_id}"), "cumQty": "0", "cumBase": "0", "executedQty": "0",
+            "orderId": order_id, "avgPrice": "0.0", "origQty": "1", "price": "25000", "reduceOnly": false,
+            "side": "BUY", "positionSide": "BOTH", "status": "NEW", "stopPrice": "0", "closePosition": false,
+            "symbol": "BTCUSD_PERP", "pair": "BTCUSD", "timeInForce": "GTC", "type": "LIMIT", "origType": "LIMIT",
+            "updateTime": 1629182711600u64, "workingType": "CONTRACT_PRICE", "priceProtect": false
+        })
+    }
+
+    #[tokio::test]
+    async fn orders_are_sent_with_all_their_parameters() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(Method::POST, "/fapi/v1/order", MockResponse::json(transaction(1)));
+        let transaction = account
+            .new_order("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .quantity(amount("0.001"))
+            .price_match(PriceMatch::Queue)
+            .time_in_force(TimeInForce::GTD)
+            .good_till_date(1693207680000)
+            .self_trade_prevention_mode(SelfTradePreventionMode::ExpireMaker)
+            .new_order_resp_type(OrderResponse::Result)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(transaction.price_match, Some(PriceMatch::Queue));
+        assert_eq!(transaction.good_till_date, Some(1693207680000));
+        let request = &server.requests()[0];
+        assert_eq!(request.param("priceMatch"), Some("QUEUE"));
+        assert_eq!(request.param("timeInForce"), Some("GTD"));
+        assert_eq!(request.param("goodTillDate"), Some("1693207680000"));
+        assert_eq!(request.param("selfTradePreventionMode"), Some("EXPIRE_MAKER"));
+        assert_eq!(request.param("newOrderRespType"), Some("RESULT"));
+    }
+
+    #[tokio::test]
+    async fn batch_entries_fail_on_their_own() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::POST,
+            "/fapi/v1/batchOrders",
+            MockResponse::json(json!([transaction(1), {"code": -2022, "msg": "ReduceOnly Order is rejected."}])),
+        );
+        let orders = (0..2).map(|_| {
+            account
+                .new_order("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+                .quantity(amount("0.001"))
+                .price(amount("25000"))
+                .time_in_force(TimeInForce::GTC)
+                .into_request()
+        });
+        let results = account.place_batch_orders().orders(orders).send().await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().status, OrderStatus::New);
+        assert!(matches!(&results[1], Err(Error::BinanceError { response }) if response.code == -2022));
+
+        let batch: Vec<Value> = serde_json::from_str(server.requests()[0].param("batchOrders").unwrap()).unwrap();
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch[0]["symbol"], "BTCUSDT");
+        assert_eq!(batch[0]["type"], "LIMIT");
+        assert_eq!(batch[0]["price"], "25000");
+        assert!(batch[0].get("reduceOnly").is_none());
+    }
+
+    #[tokio::test]
+    async fn batches_are_limited_to_five_orders() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        let orders = (0..6).map(|_| {
+            account
+                .new_order("BTCUSDT", OrderSide::Buy, OrderType::Market)
+                .into_request()
+        });
+        let results = account.place_batch_orders().orders(orders).send().await;
+        assert!(matches!(results, Err(Error::InvalidOrderError { .. })));
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn batch_cancellations_are_sent_by_id_list() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::DELETE,
+            "/fapi/v1/batchOrders",
+            MockResponse::json(json!([{"code": -2011, "msg": "Unknown order sent."}])),
+        );
+        let results = account
+            .cancel_batch_orders("BTCUSDT")
+            .order_ids(vec![1, 2])
+            .send()
+            .await
+            .unwrap();
+        assert!(matches!(&results[0], Err(Error::BinanceError { response }) if response.code == -2011));
+        let request = &server.requests()[0];
+        assert_eq!(request.param("orderIdList"), Some("[1,2]"));
+        assert_eq!(request.param("origClientOrderIdList"), None);
+    }
+
+    #[tokio::test]
+    async fn batch_cancellations_are_limited_to_ten_orders() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        let results = account
+            .cancel_batch_orders("BTCUSDT")
+            .order_ids((1..12).collect())
+            .send()
+            .await;
+        assert!(matches!(results, Err(Error::InvalidOrderError { .. })));
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn coin_m_batch_entries_are_deserialized() {
+        let (server, account) = account::<FuturesInverseType>().await;
+        server.mock(
+            Method::POST,
+            "/dapi/v1/batchOrders",
+            MockResponse::json(json!([coin_m_transaction(1)])),
+        );
+        server.mock(
+            Method::DELETE,
+            "/dapi/v1/batchOrders",
+            MockResponse::json(json!([coin_m_transaction(1)])),
+        );
+        let orders = [account
+            .new_order("BTCUSD_PERP", OrderSide::Buy, OrderType::Limit)
+            .quantity(amount("1"))
+            .price(amount("25000"))
+            .time_in_force(TimeInForce::GTC)
+            .into_request()];
+        let results = account.place_batch_orders().orders(orders).send().await.unwrap();
+        assert_eq!(results[0].as_ref().unwrap().cum_base, Some(amount("0")));
+        let results = account
+            .cancel_batch_orders("BTCUSD_PERP")
+            .order_ids(vec![1])
+            .send()
+            .await
+            .unwrap();
+        let canceled = results[0].as_ref().unwrap();
+        assert_eq!(canceled.cum_base, Some(amount("0")));
+        assert_eq!(canceled.price, amount("25000"));
+    }
+
+    #[tokio::test]
+    async fn orders_are_modified_in_place() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(Method::PUT, "/fapi/v1/order", MockResponse::json(transaction(1)));
+        let transaction = account
+            .modify_order("BTCUSDT", OrderSide::Buy)
+            .order_id(1)
+            .quantity(amount("0.001"))
+            .price(amount("25000"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(transaction.order_id, 1);
+        let request = &server.requests()[0];
+        assert_eq!(request.method, Method::PUT);
+        assert_eq!(request.param("orderId"), Some("1"));
+        assert_eq!(request.param("side"), Some("BUY"));
+        assert_eq!(request.param("price"), Some("25000"));
+        assert!(request.param("signature").is_some());
+    }
+
+    #[tokio::test]
+    async fn batch_modifications_fail_on_their_own() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::PUT,
+            "/fapi/v1/batchOrders",
+            MockResponse::json(json!([transaction(1), {"code": -5027, "msg": "No need to modify the order."}])),
+        );
+        let orders = (1..3).map(|order_id| {
+            account
+                .modify_order("BTCUSDT", OrderSide::Buy)
+                .order_id(order_id)
+                .quantity(amount("0.001"))
+                .price_match(PriceMatch::Opponent)
+                .into_request()
+        });
+        let results = account.modify_batch_orders().orders(orders).send().await.unwrap();
+        assert!(results[0].is_ok());
+        assert!(matches!(&results[1], Err(Error::BinanceError { response }) if response.code == -5027));
+
+        let batch: Vec<Value> = serde_json::from_str(server.requests()[0].param("batchOrders").unwrap()).unwrap();
+        assert_eq!(batch[1]["orderId"], "2");
+        assert_eq!(batch[1]["priceMatch"], "OPPONENT");
+        assert!(batch[1].get("price").is_none());
+    }
+
+    #[tokio::test]
+    async fn coin_m_orders_are_modified_in_place() {
+        let (server, account) = account::<FuturesInverseType>().await;
+        server.mock(Method::PUT, "/dapi/v1/order", MockResponse::json(coin_m_transaction(1)));
+        server.mock(
+            Method::PUT,
+            "/dapi/v1/batchOrders",
+            MockResponse::json(json!([coin_m_transaction(2), coin_m_transaction(3)])),
+        );
+        let transaction = account
+            .modify_order("BTCUSD_PERP", OrderSide::Buy)
+            .order_id(1)
+            .quantity(amount("1"))
+            .price(amount("25000"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(transaction.cum_base, Some(amount("0")));
+        assert_eq!(transaction.pair.as_deref(), Some("BTCUSD"));
+        let orders = (2..4).map(|order_id| {
+            account
+                .modify_order("BTCUSD_PERP", OrderSide::Buy)
+                .order_id(order_id)
+                .quantity(amount("1"))
+                .price(amount("25000"))
+                .into_request()
+        });
+        let results = account.modify_batch_orders().orders(orders).send().await.unwrap();
+        assert_eq!(results[1].as_ref().unwrap().order_id, 3);
+        assert_eq!(server.requests()[1].method, Method::PUT);
+    }
+
+    #[tokio::test]
+    async fn order_amendments_are_queried() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::GET,
+            "/fapi/v1/orderAmendment",
+            MockResponse::json(json!([{
+                "amendmentId": 5363, "symbol": "BTCUSDT", "pair": "BTCUSDT", "orderId": 20072994037u64,
+                "clientOrderId": "LJ9R4QZDihCaS8UAOOLpgW", "time": 1629184560899u64,
+                "amendment": {
+                    "price": {"before": "30004", "after": "30003.2"},
+                    "origQty": {"before": "1", "after": "1"},
+                    "count": 3
+                }
+            }])),
+        );
+        let amendments = account
+            .get_order_amendments("BTCUSDT")
+            .order_id(20072994037)
+            .limit(10)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(amendments[0].amendment.price.after, amount("30003.2"));
+        assert_eq!(amendments[0].amendment.count, 3);
+        let request = &server.requests()[0];
+        assert_eq!(request.param("orderId"), Some("20072994037"));
+        assert_eq!(request.param("limit"), Some("10"));
+        assert_eq!(request.param("startTime"), None);
+    }
+}
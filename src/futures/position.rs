@@ -0,0 +1,197 @@
+use crate::client::Client;
+use crate::errors::*;
+
+use super::rest_model::{PositionMarginAction, PositionMarginChange};
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct PositionMarginHistoryRequest {
+    symbol: String,
+    #[serde(rename = "type")]
+    action: Option<PositionMarginAction>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    limit: Option<u16>,
+}
+
+/// 逐仓保证金变动历史
+/// https://binance-docs.github.io/apidocs/futures/cn/#trade-12
+pub struct PositionMarginHistoryBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: PositionMarginHistoryRequest,
+    recv_window: u64,
+}
+
+impl<'a> PositionMarginHistoryBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64, symbol: String) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: PositionMarginHistoryRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window,
+        }
+    }
+
+    /// Only the additions, or only the reductions
+    pub fn action(mut self, action: PositionMarginAction) -> Self {
+        self.playload.action = Some(action);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    /// Defaults to 500
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<PositionMarginChange>> {
+        self.client
+            .get_signed_p(self.endpoint.as_str(), Some(&self.playload), self.recv_window)
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "mock", feature = "futures_api"))]
+mod tests {
+    use reqwest::Method;
+    use serde_json::json;
+
+    use super::*;
+    use crate::futures::futures_type::FuturesLinearType;
+    use crate::futures::rest_model::{MarginType, PositionSide};
+    use crate::mock::MockResponse;
+    use crate::test_util::account;
+
+    #[tokio::test]
+    async fn symbol_settings_are_ensured_when_already_set() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::POST,
+            "/fapi/v1/marginType",
+            MockResponse::error(-4046, "No need to change margin type."),
+        );
+        server.mock(
+            Method::POST,
+            "/fapi/v1/leverage",
+            MockResponse::json(json!({"leverage": 10, "maxNotionalValue": "1000000", "symbol": "BTCUSDT"})),
+        );
+        let leverage = account
+            .ensure_symbol_settings("BTCUSDT", MarginType::Isolated, 10)
+            .await
+            .unwrap();
+        assert_eq!(leverage.leverage, 10);
+        let requests = server.requests();
+        assert_eq!(requests[0].param("marginType"), Some("ISOLATED"));
+        assert_eq!(requests[1].param("leverage"), Some("10"));
+
+        server.mock(
+            Method::POST,
+            "/fapi/v1/marginType",
+            MockResponse::error(-4047, "Margin type cannot be changed."),
+        );
+        let result = account.ensure_symbol_settings("BTCUSDT", MarginType::Cross, 10).await;
+        assert!(matches!(result, Err(Error::BinanceError { response }) if response.code == -4047));
+        assert_eq!(server.requests()[2].param("marginType"), Some("CROSSED"));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn isolated_margin_is_changed_and_queried() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::POST,
+            "/fapi/v1/positionMargin",
+            MockResponse::json(
+                json!({"amount": 100.0, "code": 200, "msg": "Successfully modify position margin.", "type": 1}),
+            ),
+        );
+        server.mock(
+            Method::GET,
+            "/fapi/v1/positionMargin/history",
+            MockResponse::json(json!([{
+                "symbol": "BTCUSDT", "type": 1, "deltaType": "USER_ADJUST", "amount": "100", "asset": "USDT",
+                "time": 1578047897183u64, "positionSide": "LONG"
+            }])),
+        );
+        let result = account
+            .change_position_margin(
+                "BTCUSDT",
+                PositionMarginAction::Add,
+                "100".parse().unwrap(),
+                Some(PositionSide::Long),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.action, PositionMarginAction::Add);
+        let changes = account
+            .position_margin_history("BTCUSDT")
+            .action(PositionMarginAction::Add)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(changes[0].delta_type, "USER_ADJUST");
+        let requests = server.requests();
+        assert_eq!(requests[0].param("type"), Some("1"));
+        assert_eq!(requests[0].param("positionSide"), Some("LONG"));
+        assert_eq!(requests[0].param("amount"), Some("100"));
+        assert_eq!(requests[1].param("type"), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn modes_are_read_and_ensured() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::GET,
+            "/fapi/v1/positionSide/dual",
+            MockResponse::json(json!({"dualSidePosition": true})),
+        );
+        server.mock(
+            Method::POST,
+            "/fapi/v1/positionSide/dual",
+            MockResponse::error(-4059, "No need to change position side."),
+        );
+        server.mock(
+            Method::GET,
+            "/fapi/v1/multiAssetsMargin",
+            MockResponse::json(json!({"multiAssetsMargin": false})),
+        );
+        server.mock(
+            Method::POST,
+            "/fapi/v1/multiAssetsMargin",
+            MockResponse::json(json!({"code": 200, "msg": "success"})),
+        );
+        assert!(account.get_position_mode().await.unwrap().dual_side_position);
+        account.ensure_position_mode(true).await.unwrap();
+        assert!(!account.get_multi_assets_mode().await.unwrap().multi_assets_margin);
+        account.change_multi_assets_mode(true).await.unwrap();
+        server.mock(
+            Method::POST,
+            "/fapi/v1/multiAssetsMargin",
+            MockResponse::error(-4171, "Multi-Assets mode is already set."),
+        );
+        account.ensure_multi_assets_mode(true).await.unwrap();
+        let requests = server.requests();
+        assert_eq!(requests[1].param("dualSidePosition"), Some("true"));
+        assert_eq!(requests[3].param("multiAssetsMargin"), Some("true"));
+        assert_eq!(requests[4].param("multiAssetsMargin"), Some("true"));
+    }
+}
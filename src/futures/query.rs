@@ -0,0 +1,400 @@
+use crate::client::Client;
+use crate::errors::*;
+
+use super::rest_model::{AutoCloseType, Order, UserTrade};
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct QueryOrderRequest {
+    symbol: String,
+    order_id: Option<u64>,
+    orig_client_order_id: Option<String>,
+}
+
+/// 查询订单，或查询当前挂单
+/// https://binance-docs.github.io/apidocs/futures/cn/#user_data-2
+pub struct QueryOrderBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: QueryOrderRequest,
+    recv_window: u64,
+}
+
+impl<'a> QueryOrderBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64, symbol: String) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: QueryOrderRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window,
+        }
+    }
+
+    pub fn order_id(mut self, order_id: u64) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn orig_client_order_id(mut self, orig_client_order_id: String) -> Self {
+        self.playload.orig_client_order_id = Some(orig_client_order_id);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Order> {
+        self.client
+            .get_signed_p(self.endpoint.as_str(), Some(&self.playload), self.recv_window)
+            .await
+    }
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct AllOrdersRequest {
+    symbol: String,
+    order_id: Option<u64>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    limit: Option<u16>,
+}
+
+/// 查询所有订单，包括历史订单
+/// https://binance-docs.github.io/apidocs/futures/cn/#user_data-4
+///
+/// Orders are paged by order id with `order_id`, or by time with `start_time` and `end_time`, at most 7 days apart.
+pub struct QueryAllOrdersBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: AllOrdersRequest,
+    recv_window: u64,
+}
+
+impl<'a> QueryAllOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64, symbol: String) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: AllOrdersRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window,
+        }
+    }
+
+    /// Orders from this order id on
+    pub fn order_id(mut self, order_id: u64) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    /// Defaults to 500, at most 1000
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<Order>> {
+        self.client
+            .get_signed_p(self.endpoint.as_str(), Some(&self.playload), self.recv_window)
+            .await
+    }
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct UserTradesRequest {
+    symbol: String,
+    order_id: Option<u64>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    from_id: Option<u64>,
+    limit: Option<u16>,
+}
+
+/// 账户成交历史
+/// https://binance-docs.github.io/apidocs/futures/cn/#user_data-11
+///
+/// Trades are paged by trade id with `from_id`, or by time with `start_time` and `end_time`, at most 7 days apart.
+pub struct QueryUserTradesBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: UserTradesRequest,
+    recv_window: u64,
+}
+
+impl<'a> QueryUserTradesBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64, symbol: String) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: UserTradesRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window,
+        }
+    }
+
+    /// Trades of this order only, USD-M futures only
+    pub fn order_id(mut self, order_id: u64) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    /// Trades from this trade id on, exclusive with the start and end times
+    pub fn from_id(mut self, from_id: u64) -> Self {
+        self.playload.from_id = Some(from_id);
+        self
+    }
+
+    /// Defaults to 500, at most 1000
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<UserTrade>> {
+        self.client
+            .get_signed_p(self.endpoint.as_str(), Some(&self.playload), self.recv_window)
+            .await
+    }
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct ForceOrdersRequest {
+    symbol: Option<String>,
+    auto_close_type: Option<AutoCloseType>,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    limit: Option<u16>,
+}
+
+/// 用户强平单历史
+/// https://binance-docs.github.io/apidocs/futures/cn/#user_data-10
+///
+/// Without a start time, only the orders of the last 7 days are queried.
+pub struct QueryForceOrdersBuilder<'a> {
+    client: &'a Client,
+    endpoint: String,
+    playload: ForceOrdersRequest,
+    recv_window: u64,
+}
+
+impl<'a> QueryForceOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: String, recv_window: u64) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: ForceOrdersRequest::default(),
+            recv_window,
+        }
+    }
+
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.playload.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Only the liquidations, or only the auto-deleveraging orders
+    pub fn auto_close_type(mut self, auto_close_type: AutoCloseType) -> Self {
+        self.playload.auto_close_type = Some(auto_close_type);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    /// Defaults to 50, at most 100
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<Order>> {
+        self.client
+            .get_signed_p(self.endpoint.as_str(), Some(&self.playload), self.recv_window)
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "mock", feature = "futures_api"))]
+mod tests {
+    use reqwest::Method;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::futures::futures_type::{FuturesInverseType, FuturesLinearType};
+    use crate::mock::MockResponse;
+    use crate::rest_model::OrderStatus;
+    use crate::test_util::account;
+
+    fn order(order_id: u64) -> Value {
+        json!({
+            "avgPrice": "0.00000", "clientOrderId": "abc", "cumQuote": "0", "executedQty": "0", "orderId": order_id,
+            "origQty": "0.40", "origType": "TRAILING_STOP_MARKET", "price": "0", "reduceOnly": false, "side": "BUY",
+            "positionSide": "SHORT", "status": "NEW", "stopPrice": "9300", "closePosition": false, "symbol": "BTCUSDT",
+            "time": 1579276756075u64, "timeInForce": "GTC", "type": "TRAILING_STOP_MARKET", "activatePrice": "9020",
+            "priceRate": "0.3", "updateTime": 1579276756075u64, "workingType": "CONTRACT_PRICE", "priceProtect": false,
+            "priceMatch": "NONE", "selfTradePreventionMode": "NONE", "goodTillDate": 0
+        })
+    }
+
+    #[tokio::test]
+    async fn orders_are_queried_by_id() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(Method::GET, "/fapi/v1/order", MockResponse::json(order(1)));
+        server.mock(Method::GET, "/fapi/v1/openOrder", MockResponse::json(order(2)));
+        server.mock(
+            Method::GET,
+            "/fapi/v1/allOrders",
+            MockResponse::json(json!([order(3), order(4)])),
+        );
+        let order = account.get_order("BTCUSDT").order_id(1).send().await.unwrap();
+        assert_eq!(order.status, OrderStatus::New);
+        assert_eq!(order.time, 1579276756075);
+        let open_order = account
+            .get_open_order("BTCUSDT")
+            .orig_client_order_id("abc".into())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(open_order.order_id, 2);
+        let orders = account
+            .get_all_orders("BTCUSDT")
+            .order_id(3)
+            .limit(2)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(orders.len(), 2);
+        let requests = server.requests();
+        assert_eq!(requests[0].param("orderId"), Some("1"));
+        assert_eq!(requests[1].param("origClientOrderId"), Some("abc"));
+        assert_eq!(requests[1].param("orderId"), None);
+        assert_eq!(requests[2].param("orderId"), Some("3"));
+        assert_eq!(requests[2].param("limit"), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn user_trades_and_force_orders_are_queried_by_time() {
+        let (server, account) = account::<FuturesLinearType>().await;
+        server.mock(
+            Method::GET,
+            "/fapi/v1/userTrades",
+            MockResponse::json(json!([{
+                "buyer": false, "commission": "-0.07819010", "commissionAsset": "USDT", "id": 698759, "maker": false,
+                "orderId": 25851813, "price": "7819.01", "qty": "0.002", "quoteQty": "15.63802",
+                "realizedPnl": "-0.91539999", "side": "SELL", "positionSide": "SHORT", "symbol": "BTCUSDT",
+                "time": 1569514978020u64
+            }])),
+        );
+        server.mock(
+            Method::GET,
+            "/fapi/v1/forceOrders",
+            MockResponse::json(json!([order(5)])),
+        );
+        let trades = account
+            .get_user_trades("BTCUSDT")
+            .start_time(1569514978000)
+            .end_time(1569514979000)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(trades[0].id, 698759);
+        assert!(trades[0].quote_qty.is_some());
+        let orders = account
+            .get_force_orders()
+            .auto_close_type(AutoCloseType::Liquidation)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(orders[0].order_id, 5);
+        let requests = server.requests();
+        assert_eq!(requests[0].param("startTime"), Some("1569514978000"));
+        assert_eq!(requests[0].param("fromId"), None);
+        assert_eq!(requests[1].param("autoCloseType"), Some("LIQUIDATION"));
+        assert_eq!(requests[1].param("symbol"), None);
+    }
+
+    #[tokio::test]
+    async fn inverse_queries_use_the_coin_m_models() {
+        let (server, account) = account::<FuturesInverseType>().await;
+        let mut order = order(1);
+        order.as_object_mut().unwrap().remove("cumQuote");
+        order["cumBase"] = json!("0");
+        order["pair"] = json!("BTCUSD");
+        server.mock(Method::GET, "/dapi/v1/allOrders", MockResponse::json(json!([order])));
+        server.mock(
+            Method::GET,
+            "/dapi/v1/userTrades",
+            MockResponse::json(json!([{
+                "symbol": "BTCUSD_200626", "id": 6, "orderId": 28, "pair": "BTCUSD", "side": "SELL", "price": "8800",
+                "qty": "1", "realizedPnl": "0", "marginAsset": "BTC", "baseQty": "0.01136364",
+                "commission": "0.00000454", "commissionAsset": "BTC", "time": 1590743483586u64,
+                "positionSide": "BOTH", "buyer": false, "maker": false
+            }])),
+        );
+        let orders = account.get_all_orders("BTCUSD_200626").send().await.unwrap();
+        assert_eq!(orders[0].pair.as_deref(), Some("BTCUSD"));
+        assert!(orders[0].cum_base.is_some());
+        let trades = account
+            .get_user_trades("BTCUSD_200626")
+            .from_id(6)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(trades[0].margin_asset.as_deref(), Some("BTC"));
+        assert_eq!(server.requests()[1].param("fromId"), Some("6"));
+    }
+}
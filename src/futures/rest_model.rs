@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use crate::filters::OrderFilters;
 
 use crate::rest_model::{string_or_bool, string_or_float, string_or_float_opt, string_or_u64};
 pub use crate::rest_model::{
-    Asks, Bids, BookTickers, KlineSummaries, KlineSummary, OrderSide, OrderStatus, RateLimit, ServerTime, SymbolPrice,
-    SymbolStatus, Tickers, TimeInForce,
+    Amount, Asks, Bids, BookTickers, KlineInterval, KlineSummaries, KlineSummary, OrderSide, OrderStatus, RateLimit,
+    ServerTime, SymbolPrice, SymbolStatus, Tickers, TimeInForce,
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -11,9 +14,13 @@ pub use crate::rest_model::{
 pub struct ExchangeInformation {
     pub timezone: String,
     pub server_time: u64,
+    /// Not sent by COIN-M futures
+    #[serde(default)]
     pub futures_type: String,
     pub rate_limits: Vec<RateLimit>,
     pub exchange_filters: Vec<Filters>,
+    /// Not sent by COIN-M futures
+    #[serde(default)]
     pub assets: Vec<AssetDetail>,
     pub symbols: Vec<Symbol>,
 }
@@ -35,6 +42,7 @@ pub struct Symbol {
     pub contract_type: ContractType,
     pub delivery_date: u64,
     pub onboard_date: u64,
+    #[serde(alias = "contractStatus")]
     pub status: SymbolStatus,
     #[serde(with = "string_or_float")]
     pub maint_margin_percent: f64,
@@ -48,14 +56,22 @@ pub struct Symbol {
     pub quote_precision: u64,
     pub underlying_type: String,
     pub underlying_sub_type: Vec<String>,
+    /// Not sent by COIN-M futures
+    #[serde(default)]
     pub settle_plan: u64,
     #[serde(with = "string_or_float")]
     pub trigger_protect: f64,
     pub filters: Vec<Filters>,
+    #[serde(alias = "OrderType")]
     pub order_types: Vec<OrderType>,
     pub time_in_force: Vec<TimeInForce>,
 }
 
+impl Symbol {
+    /// Filters used to validate and round orders of this symbol
+    pub fn order_filters(&self) -> OrderFilters { OrderFilters::from(self.filters.as_slice()) }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ContractType {
@@ -102,10 +118,42 @@ pub enum WorkingType {
     ContractPrice,
 }
 
+/// Price of a limit order set from the order book instead of the `price` parameter
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PriceMatch {
+    None,
+    Opponent,
+    #[serde(rename = "OPPONENT_5")]
+    Opponent5,
+    #[serde(rename = "OPPONENT_10")]
+    Opponent10,
+    #[serde(rename = "OPPONENT_20")]
+    Opponent20,
+    Queue,
+    #[serde(rename = "QUEUE_5")]
+    Queue5,
+    #[serde(rename = "QUEUE_10")]
+    Queue10,
+    #[serde(rename = "QUEUE_20")]
+    Queue20,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SelfTradePreventionMode {
+    None,
+    ExpireTaker,
+    ExpireBoth,
+    ExpireMaker,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum MarginType {
+    #[serde(alias = "ISOLATED")]
     Isolated,
+    #[serde(alias = "CROSSED", alias = "crossed")]
     Cross,
 }
 
@@ -313,23 +361,30 @@ pub struct OpenInterest {
 #[serde(rename_all = "camelCase")]
 pub struct Order {
     pub client_order_id: String,
+    /// Not sent by COIN-M futures
+    #[serde(default, with = "string_or_float")]
+    pub cum_quote: Amount,
+    /// Sent by COIN-M futures only
+    #[serde(default, with = "string_or_float_opt")]
+    pub cum_base: Option<Amount>,
+    /// Sent by COIN-M futures only
+    #[serde(default)]
+    pub pair: Option<String>,
     #[serde(with = "string_or_float")]
-    pub cum_quote: f64,
-    #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub avg_price: f64,
+    pub avg_price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     pub side: OrderSide,
     pub reduce_only: bool,
     pub position_side: PositionSide,
     pub status: OrderStatus,
     #[serde(with = "string_or_float", default = "default_stop_price")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     pub close_position: bool,
     pub symbol: String,
     pub time_in_force: TimeInForce,
@@ -337,12 +392,66 @@ pub struct Order {
     pub order_type: OrderType,
     pub orig_type: OrderType,
     #[serde(with = "string_or_float", default = "default_activation_price")]
-    pub activate_price: f64,
+    pub activate_price: Amount,
     #[serde(with = "string_or_float", default = "default_price_rate")]
-    pub price_rate: f64,
+    pub price_rate: Amount,
+    /// Creation time, not sent by the order placement endpoints
+    #[serde(default)]
+    pub time: u64,
     pub update_time: u64,
     pub working_type: WorkingType,
     pub price_protect: bool,
+    #[serde(default)]
+    pub price_match: Option<PriceMatch>,
+    #[serde(default)]
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    #[serde(default)]
+    pub good_till_date: Option<u64>,
+}
+
+/// Trade of the account
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTrade {
+    pub symbol: String,
+    pub id: u64,
+    pub order_id: u64,
+    /// Sent by COIN-M futures only
+    #[serde(default)]
+    pub pair: Option<String>,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    #[serde(with = "string_or_float")]
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub qty: Amount,
+    /// Not sent by COIN-M futures
+    #[serde(default, with = "string_or_float_opt")]
+    pub quote_qty: Option<Amount>,
+    /// Sent by COIN-M futures only
+    #[serde(default, with = "string_or_float_opt")]
+    pub base_qty: Option<Amount>,
+    #[serde(with = "string_or_float")]
+    pub realized_pnl: Amount,
+    /// Sent by COIN-M futures only
+    #[serde(default)]
+    pub margin_asset: Option<String>,
+    #[serde(with = "string_or_float")]
+    pub commission: Amount,
+    pub commission_asset: String,
+    pub time: u64,
+    pub buyer: bool,
+    pub maker: bool,
+}
+
+/// Cause of an order closing a position
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AutoCloseType {
+    /// Liquidation
+    Liquidation,
+    /// Auto-deleveraging
+    Adl,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -350,22 +459,29 @@ pub struct Order {
 pub struct Transaction {
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub cum_qty: f64,
-    #[serde(with = "string_or_float")]
-    pub cum_quote: f64,
+    pub cum_qty: Amount,
+    /// Not sent by COIN-M futures
+    #[serde(default, with = "string_or_float")]
+    pub cum_quote: Amount,
+    /// Sent by COIN-M futures only
+    #[serde(default, with = "string_or_float_opt")]
+    pub cum_base: Option<Amount>,
+    /// Sent by COIN-M futures only
+    #[serde(default)]
+    pub pair: Option<String>,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub avg_price: f64,
+    pub avg_price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     pub reduce_only: bool,
     pub side: OrderSide,
     pub position_side: PositionSide,
     pub status: OrderStatus,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     pub close_position: bool,
     pub symbol: String,
     pub time_in_force: TimeInForce,
@@ -374,13 +490,21 @@ pub struct Transaction {
     pub orig_type: OrderType,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub activate_price: Option<f64>,
+    pub activate_price: Option<Amount>,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub price_rate: Option<f64>,
+    pub price_rate: Option<Amount>,
     pub update_time: u64,
     pub working_type: WorkingType,
+    #[serde(default)]
     price_protect: bool,
+    #[serde(default)]
+    pub price_match: Option<PriceMatch>,
+    #[serde(default)]
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    /// Expiry of `TimeInForce::GTD` orders, 0 for other orders
+    #[serde(default)]
+    pub good_till_date: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -388,23 +512,30 @@ pub struct Transaction {
 pub struct CanceledOrder {
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub cum_qty: f64,
-    #[serde(with = "string_or_float")]
-    pub cum_quote: f64,
+    pub cum_qty: Amount,
+    /// Not sent by COIN-M futures
+    #[serde(default, with = "string_or_float")]
+    pub cum_quote: Amount,
+    /// Sent by COIN-M futures only
+    #[serde(default, with = "string_or_float_opt")]
+    pub cum_base: Option<Amount>,
+    /// Sent by COIN-M futures only
+    #[serde(default)]
+    pub pair: Option<String>,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     pub orig_type: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     pub reduce_only: bool,
     pub side: String,
     pub position_side: String,
     pub status: String,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     pub close_position: bool,
     pub symbol: String,
     pub time_in_force: String,
@@ -412,43 +543,151 @@ pub struct CanceledOrder {
     pub type_name: String,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub activate_price: Option<f64>,
+    pub activate_price: Option<Amount>,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub price_rate: Option<f64>,
+    pub price_rate: Option<Amount>,
     pub update_time: u64,
     pub working_type: String,
     price_protect: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IncomeType {
+    Transfer,
+    WelcomeBonus,
+    RealizedPnl,
+    FundingFee,
+    Commission,
+    InsuranceClear,
+    ReferralKickback,
+    CommissionRebate,
+    ApiRebate,
+    ContestReward,
+    CrossCollateralTransfer,
+    OptionsPremiumFee,
+    OptionsSettleProfit,
+    InternalTransfer,
+    AutoExchange,
+    #[serde(rename = "DELIVERED_SETTELMENT")]
+    DeliveredSettlement,
+    CoinSwapDeposit,
+    CoinSwapWithdraw,
+    PositionLimitIncreaseFee,
+    StrategyUmfuturesTransfer,
+    FeeReturn,
+    BfusdReward,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Income {
+    /// Empty for incomes not related to a symbol, e.g. transfers
+    pub symbol: String,
+    pub income_type: IncomeType,
+    #[serde(with = "string_or_float")]
+    pub income: Amount,
+    pub asset: String,
+    pub info: String,
+    pub time: u64,
+    #[serde(with = "string_or_u64")]
+    pub tran_id: u64,
+    /// Empty for incomes not related to a trade
+    #[serde(default)]
+    pub trade_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeDownloadId {
+    /// Average time taken to prepare a download over the last 30 days, in ms
+    pub avg_cost_timestamp_of_last30d: u64,
+    pub download_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadStatus {
+    Completed,
+    Processing,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeDownloadLink {
+    pub download_id: String,
+    pub status: DownloadStatus,
+    /// Empty until the download is completed
+    pub url: String,
+    #[serde(default)]
+    pub notified: bool,
+    /// -1 until the download is completed
+    pub expiration_timestamp: i64,
+    pub is_expired: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAmendment {
+    pub amendment_id: u64,
+    pub symbol: String,
+    pub pair: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub time: u64,
+    pub amendment: Amendment,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Amendment {
+    pub price: AmendedValue,
+    pub orig_qty: AmendedValue,
+    /// Number of amendments of the order so far
+    pub count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AmendedValue {
+    #[serde(with = "string_or_float")]
+    pub before: Amount,
+    #[serde(with = "string_or_float")]
+    pub after: Amount,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Position {
     #[serde(with = "string_or_float")]
-    pub entry_price: f64,
+    pub entry_price: Amount,
     pub margin_type: MarginType,
     #[serde(with = "string_or_bool")]
     pub is_auto_add_margin: bool,
     #[serde(with = "string_or_float")]
-    pub isolated_margin: f64,
+    pub isolated_margin: Amount,
     pub leverage: u64,
     #[serde(with = "string_or_float")]
-    pub liquidation_price: f64,
+    pub liquidation_price: Amount,
     #[serde(with = "string_or_float")]
-    pub mark_price: f64,
+    pub mark_price: Amount,
     #[serde(with = "string_or_float")]
-    pub max_notional_value: f64,
+    pub max_notional_value: Amount,
     #[serde(with = "string_or_float", rename = "positionAmt")]
-    pub position_amount: f64,
+    pub position_amount: Amount,
     pub symbol: String,
     #[serde(with = "string_or_float", rename = "unRealizedProfit")]
-    pub unrealized_profit: f64,
+    pub unrealized_profit: Amount,
     pub position_side: PositionSide,
     pub update_time: u64,
     #[serde(with = "string_or_float")]
-    pub notional: f64,
+    pub notional: Amount,
     #[serde(with = "string_or_float")]
-    pub isolated_wallet: f64,
+    pub isolated_wallet: Amount,
 }
 
 // https://binance-docs.github.io/apidocs/futures/en/#account-information-v2-user_data
@@ -458,29 +697,29 @@ pub struct Position {
 pub struct AccountPosition {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub initial_margin: f64,
+    pub initial_margin: Amount,
     #[serde(with = "string_or_float", rename = "maintMargin")]
-    pub maintenance_margin: f64,
+    pub maintenance_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub unrealized_profit: f64,
+    pub unrealized_profit: Amount,
     #[serde(with = "string_or_float")]
-    pub position_initial_margin: f64,
+    pub position_initial_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub open_order_initial_margin: f64,
+    pub open_order_initial_margin: Amount,
     #[serde(with = "string_or_u64")]
     pub leverage: u64,
     pub isolated: bool,
     #[serde(with = "string_or_float")]
-    pub entry_price: f64,
+    pub entry_price: Amount,
     #[serde(with = "string_or_float")]
-    pub max_notional: f64,
+    pub max_notional: Amount,
     #[serde(with = "string_or_float")]
-    pub bid_notional: f64,
+    pub bid_notional: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_notional: f64,
+    pub ask_notional: Amount,
     pub position_side: PositionSide,
     #[serde(with = "string_or_float", rename = "positionAmt")]
-    pub position_amount: f64,
+    pub position_amount: Amount,
     pub update_time: u64,
 }
 
@@ -489,27 +728,27 @@ pub struct AccountPosition {
 pub struct AccountAsset {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub wallet_balance: f64,
+    pub wallet_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub unrealized_profit: f64,
+    pub unrealized_profit: Amount,
     #[serde(with = "string_or_float")]
-    pub margin_balance: f64,
+    pub margin_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub maint_margin: f64,
+    pub maint_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub initial_margin: f64,
+    pub initial_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub position_initial_margin: f64,
+    pub position_initial_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub open_order_initial_margin: f64,
+    pub open_order_initial_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub cross_wallet_balance: f64,
+    pub cross_wallet_balance: Amount,
     #[serde(with = "string_or_float", rename = "crossUnPnl")]
-    pub cross_unrealized_pnl: f64,
+    pub cross_unrealized_pnl: Amount,
     #[serde(with = "string_or_float")]
-    pub available_balance: f64,
+    pub available_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub max_withdraw_amount: f64,
+    pub max_withdraw_amount: Amount,
     pub margin_available: bool,
     pub update_time: u64,
 }
@@ -524,27 +763,27 @@ pub struct AccountInformation {
     pub update_time: u64,
     pub multi_assets_margin: bool,
     #[serde(with = "string_or_float")]
-    pub total_initial_margin: f64,
+    pub total_initial_margin: Amount,
     #[serde(with = "string_or_float", rename = "totalMaintMargin")]
-    pub total_maintenance_margin: f64,
+    pub total_maintenance_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub total_wallet_balance: f64,
+    pub total_wallet_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub total_unrealized_profit: f64,
+    pub total_unrealized_profit: Amount,
     #[serde(with = "string_or_float")]
-    pub total_margin_balance: f64,
+    pub total_margin_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub total_position_initial_margin: f64,
+    pub total_position_initial_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub total_open_order_initial_margin: f64,
+    pub total_open_order_initial_margin: Amount,
     #[serde(with = "string_or_float")]
-    pub total_cross_wallet_balance: f64,
+    pub total_cross_wallet_balance: Amount,
     #[serde(with = "string_or_float", rename = "totalCrossUnPnl")]
-    pub total_cross_unrealized_pnl: f64,
+    pub total_cross_unrealized_pnl: Amount,
     #[serde(with = "string_or_float")]
-    pub available_balance: f64,
+    pub available_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub max_withdraw_amount: f64,
+    pub max_withdraw_amount: Amount,
     pub assets: Vec<AccountAsset>,
     pub positions: Vec<AccountPosition>,
 }
@@ -555,15 +794,15 @@ pub struct AccountBalance {
     pub account_alias: String,
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub balance: f64,
+    pub balance: Amount,
     #[serde(with = "string_or_float")]
-    pub cross_wallet_balance: f64,
+    pub cross_wallet_balance: Amount,
     #[serde(with = "string_or_float", rename = "crossUnPnl")]
-    pub cross_unrealized_pnl: f64,
+    pub cross_unrealized_pnl: Amount,
     #[serde(with = "string_or_float")]
-    pub available_balance: f64,
+    pub available_balance: Amount,
     #[serde(with = "string_or_float")]
-    pub max_withdraw_amount: f64,
+    pub max_withdraw_amount: Amount,
     pub margin_available: bool,
     pub update_time: u64,
 }
@@ -577,14 +816,61 @@ pub struct ChangeLeverageResponse {
     pub symbol: String,
 }
 
-fn default_stop_price() -> f64 {
-    0.0
+#[derive(Debug, Serialize_repr, Deserialize_repr, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PositionMarginAction {
+    Add = 1,
+    Reduce = 2,
 }
-fn default_activation_price() -> f64 {
-    0.0
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMarginResult {
+    #[serde(with = "string_or_float")]
+    pub amount: Amount,
+    pub code: i32,
+    pub msg: String,
+    #[serde(rename = "type")]
+    pub action: PositionMarginAction,
 }
-fn default_price_rate() -> f64 {
-    0.0
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMarginChange {
+    pub symbol: String,
+    #[serde(rename = "type")]
+    pub action: PositionMarginAction,
+    /// Cause of the change, e.g. `TRADE` or `USER_ADJUST`, not sent by COIN-M futures
+    #[serde(default)]
+    pub delta_type: String,
+    #[serde(with = "string_or_float")]
+    pub amount: Amount,
+    pub asset: String,
+    pub time: u64,
+    pub position_side: PositionSide,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMode {
+    /// Hedge mode if true, one-way mode otherwise
+    pub dual_side_position: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiAssetsMode {
+    pub multi_assets_margin: bool,
+}
+
+fn default_stop_price() -> Amount {
+    Amount::default()
+}
+fn default_activation_price() -> Amount {
+    Amount::default()
+}
+fn default_price_rate() -> Amount {
+    Amount::default()
 }
 
 #[derive(Serialize)]
@@ -595,7 +881,7 @@ pub(crate) struct HistoryQuery {
     pub from_id: Option<u64>,
     pub limit: u16,
     pub symbol: String,
-    pub interval: Option<String>,
+    pub interval: Option<KlineInterval>,
     pub period: Option<String>,
 }
 
@@ -662,3 +948,24 @@ pub struct SymbolBrackets {
     pub symbol: String,
     pub brackets: Vec<LeverageBracket>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::PriceMatch;
+
+    #[test]
+    fn numbered_price_matches_are_serialized_with_underscores() {
+        let price_matches = [
+            (PriceMatch::Opponent5, "OPPONENT_5"),
+            (PriceMatch::Opponent10, "OPPONENT_10"),
+            (PriceMatch::Opponent20, "OPPONENT_20"),
+            (PriceMatch::Queue5, "QUEUE_5"),
+            (PriceMatch::Queue10, "QUEUE_10"),
+            (PriceMatch::Queue20, "QUEUE_20"),
+        ];
+        for (price_match, name) in price_matches {
+            assert_eq!(serde_json::to_value(&price_match).unwrap(), name);
+            assert_eq!(serde_json::from_value::<PriceMatch>(name.into()).unwrap(), price_match);
+        }
+    }
+}
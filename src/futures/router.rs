@@ -44,6 +44,16 @@ pub enum FuturesRoute {
     OpenOrders,
     UserDataStream,
     Income,
+    IncomeDownloadId,
+    IncomeDownloadLink,
+    BatchOrders,
+    OrderAmendment,
+    MarginType,
+    PositionMargin,
+    PositionMarginHistory,
+    MultiAssetsMargin,
+    OpenOrder,
+    ForceOrders,
 }
 
 impl From<Router> for String {
@@ -88,6 +98,16 @@ impl From<Router> for String {
                 FuturesRoute::OpenOrders => "/fapi/v1/openOrders",
                 FuturesRoute::UserDataStream => "/fapi/v1/listenKey",
                 FuturesRoute::Income => "/fapi/v1/income",
+                FuturesRoute::IncomeDownloadId => "/fapi/v1/income/asyn",
+                FuturesRoute::IncomeDownloadLink => "/fapi/v1/income/asyn/id",
+                FuturesRoute::BatchOrders => "/fapi/v1/batchOrders",
+                FuturesRoute::OrderAmendment => "/fapi/v1/orderAmendment",
+                FuturesRoute::MarginType => "/fapi/v1/marginType",
+                FuturesRoute::PositionMargin => "/fapi/v1/positionMargin",
+                FuturesRoute::PositionMarginHistory => "/fapi/v1/positionMargin/history",
+                FuturesRoute::MultiAssetsMargin => "/fapi/v1/multiAssetsMargin",
+                FuturesRoute::OpenOrder => "/fapi/v1/openOrder",
+                FuturesRoute::ForceOrders => "/fapi/v1/forceOrders",
             },
             Router::Inverse(route) => match route {
                 FuturesRoute::Ping => "/dapi/v1/ping",
@@ -128,6 +148,16 @@ impl From<Router> for String {
                 FuturesRoute::OpenOrders => "/dapi/v1/openOrders",
                 FuturesRoute::UserDataStream => "/dapi/v1/listenKey",
                 FuturesRoute::Income => "/dapi/v1/income",
+                FuturesRoute::IncomeDownloadId => "/dapi/v1/income/asyn",
+                FuturesRoute::IncomeDownloadLink => "/dapi/v1/income/asyn/id",
+                FuturesRoute::BatchOrders => "/dapi/v1/batchOrders",
+                FuturesRoute::OrderAmendment => "/dapi/v1/orderAmendment",
+                FuturesRoute::MarginType => "/dapi/v1/marginType",
+                FuturesRoute::PositionMargin => "/dapi/v1/positionMargin",
+                FuturesRoute::PositionMarginHistory => "/dapi/v1/positionMargin/history",
+                FuturesRoute::MultiAssetsMargin => "null",
+                FuturesRoute::OpenOrder => "/dapi/v1/openOrder",
+                FuturesRoute::ForceOrders => "/dapi/v1/forceOrders",
             }
         })
     }
@@ -1,25 +1,27 @@
-use std::env::var;
-use std::mem::swap;
-use std::ops::Deref;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use futures::StreamExt;
+use futures::future::Future;
+use futures::stream::BoxStream;
+use futures::FutureExt;
 use serde_json::{from_str, from_value, Value};
-use tokio::net::TcpStream;
-use tokio_tungstenite::{connect_async, MaybeTlsStream};
-use tokio_tungstenite::tungstenite::handshake::client::Response;
-use tokio_tungstenite::tungstenite::Message;
-use tokio_tungstenite::WebSocketStream;
 use url::Url;
 
 use crate::config::Config;
 use crate::errors::*;
 use crate::futures::futures_type::FuturesType;
+use crate::futures::rest_model::KlineInterval;
+use crate::futures::ws_model::{
+    AccountConfigUpdateEvent, AccountUpdateEvent, AssetIndexEvent, CompositeIndexEvent, ContinuousKlineEvent,
+    ContractInfoEvent, LiquidationEvent, MarginCallEvent, MarkPriceEvent, OrderTradeUpdateEvent,
+};
+use crate::websockets::{
+    event_stream, ConnectionEvent, ConnectionState, ReconnectOptions, WebSocketConnection, WebSocketControl,
+};
 use crate::ws_model::*;
 
 pub fn agg_trade_stream(symbol: &str) -> String { format!("{symbol}@aggTrade") }
 
-pub fn kline_stream(symbol: &str, interval: &str) -> String { format!("{symbol}@kline_{interval}") }
+pub fn kline_stream(symbol: &str, interval: KlineInterval) -> String { format!("{symbol}@kline_{interval}") }
 
 pub fn mini_ticker_stream(symbol: &str) -> String { format!("{symbol}@miniTicker") }
 
@@ -50,6 +52,56 @@ pub fn partial_book_depth_stream(symbol: &str, levels: u16, update_speed: u16) -
 /// * `update_speed`: 1000 or 100
 pub fn diff_book_depth_stream(symbol: &str, update_speed: u16) -> String { format!("{symbol}@depth@{update_speed}ms") }
 
+/// # Arguments
+///
+/// * `symbol`: the market symbol
+/// * `update_speed`: 1 or 3 seconds
+pub fn mark_price_stream(symbol: &str, update_speed: u16) -> String {
+    match update_speed {
+        1 => format!("{symbol}@markPrice@1s"),
+        _ => format!("{symbol}@markPrice"),
+    }
+}
+
+/// Mark price of all symbols, USD-M only
+///
+/// # Arguments
+///
+/// * `update_speed`: 1 or 3 seconds
+pub fn all_mark_price_stream(update_speed: u16) -> String {
+    match update_speed {
+        1 => "!markPrice@arr@1s".to_string(),
+        _ => "!markPrice@arr".to_string(),
+    }
+}
+
+/// Liquidation orders of a symbol
+pub fn force_order_stream(symbol: &str) -> String { format!("{symbol}@forceOrder") }
+
+/// Liquidation orders of all symbols
+pub fn all_force_order_stream() -> &'static str { "!forceOrder@arr" }
+
+/// # Arguments
+///
+/// * `pair`: the pair, e.g. btcusdt
+/// * `contract_type`: perpetual, current_quarter or next_quarter
+/// * `interval`: the kline interval
+pub fn continuous_kline_stream(pair: &str, contract_type: &str, interval: KlineInterval) -> String {
+    format!("{pair}_{contract_type}@continuousKline_{interval}")
+}
+
+/// Composite index symbol information, USD-M only
+pub fn composite_index_stream(symbol: &str) -> String { format!("{symbol}@compositeIndex") }
+
+/// Contract info updates of all symbols
+pub fn contract_info_stream() -> &'static str { "!contractInfo" }
+
+/// Multi-assets mode asset index, USD-M only
+pub fn asset_index_stream(symbol: &str) -> String { format!("{symbol}@assetIndex") }
+
+/// Multi-assets mode asset index of all assets, USD-M only
+pub fn all_asset_index_stream() -> &'static str { "!assetIndex@arr" }
+
 fn combined_stream(streams: Vec<String>) -> String { streams.join("/") }
 
 
@@ -76,12 +128,38 @@ pub enum FuturesWebsocketEvent {
     OrderUpdate(Box<OrderUpdate>),
     #[serde(alias = "listStatus")]
     ListOrderUpdate(Box<OrderListUpdate>),
+    #[serde(alias = "ORDER_TRADE_UPDATE")]
+    OrderTradeUpdate(Box<OrderTradeUpdateEvent>),
+    #[serde(alias = "ACCOUNT_UPDATE")]
+    AccountUpdate(Box<AccountUpdateEvent>),
+    #[serde(alias = "MARGIN_CALL")]
+    MarginCall(Box<MarginCallEvent>),
+    #[serde(alias = "ACCOUNT_CONFIG_UPDATE")]
+    AccountConfigUpdate(Box<AccountConfigUpdateEvent>),
+    #[serde(alias = "listenKeyExpired")]
+    ListenKeyExpired(Box<ListenKeyExpired>),
+    #[serde(alias = "markPriceUpdate")]
+    MarkPrice(Box<MarkPriceEvent>),
+    #[serde(alias = "forceOrder")]
+    Liquidation(Box<LiquidationEvent>),
+    #[serde(alias = "continuous_kline")]
+    ContinuousKline(Box<ContinuousKlineEvent>),
+    #[serde(alias = "compositeIndex")]
+    CompositeIndex(Box<CompositeIndexEvent>),
+    #[serde(alias = "contractInfo")]
+    ContractInfo(Box<ContractInfoEvent>),
+    #[serde(alias = "assetIndUpdate")]
+    AssetIndex(Box<AssetIndexEvent>),
+    /// Events of array streams such as `!markPrice@arr`, `!ticker@arr` or `!assetIndex@arr`
+    #[serde(skip)]
+    Multiple(Vec<FuturesWebsocketEvent>),
 }
 
 pub struct FuturesWebSockets<'a, T: FuturesType> {
-    pub socket: Option<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)>,
+    pub socket: Option<WebSocketConnection>,
     handler: Box<dyn FnMut(FuturesWebsocketEvent) -> Result<()> + 'a + Send>,
     conf: Config,
+    state: ConnectionState<'a>,
     pub _marker: std::marker::PhantomData<T>,
 }
 
@@ -99,20 +177,37 @@ impl<'a, T> FuturesWebSockets<'a, T>
         where
             Callback: FnMut(FuturesWebsocketEvent) -> Result<()> + 'a + Send,
     {
-        conf.futures_ws_endpoint = if conf.futures_ws_endpoint == "" {
-            T::ws_endpoint()
-        } else {
-            conf.futures_ws_endpoint.clone()
-        };
+        if conf.futures_ws_endpoint.is_empty() {
+            conf.futures_ws_endpoint = T::ws_endpoint();
+        }
 
         Self {
             socket: None,
             handler: Box::new(handler),
             conf,
+            state: ConnectionState::new(),
             _marker: std::marker::PhantomData,
         }
     }
 
+    /// New websocket holder without a handler, to be consumed with `into_stream`
+    pub fn new_stream(conf: Config) -> Self { Self::new_with_options(|_| Ok(()), conf) }
+
+    /// Reconnect to the same endpoints when the connection drops instead of ending the event loop
+    pub fn with_reconnect(mut self, options: ReconnectOptions) -> Self {
+        self.state.set_reconnect(Some(options));
+        self
+    }
+
+    /// Handler receiving connection lifecycle events (connects, drops and reconnects)
+    pub fn on_connection_event<Callback>(mut self, handler: Callback) -> Self
+        where
+            Callback: FnMut(ConnectionEvent) + 'a + Send,
+    {
+        self.state.set_lifecycle_handler(handler);
+        self
+    }
+
     /// Connect to multiple websocket endpoints
     /// N.B: WE has to be CombinedStreamEvent
     pub async fn connect_multiple(&mut self, endpoints: Vec<String>) -> Result<()> {
@@ -120,9 +215,9 @@ impl<'a, T> FuturesWebSockets<'a, T>
         url.path_segments_mut()
             .map_err(|_| Error::UrlParserError(url::ParseError::RelativeUrlWithoutBase))?
             .push("stream");
-        url.set_query(Some(&format!("streams={}", combined_stream(endpoints))));
+        url.set_query(Some(&format!("streams={}", combined_stream(endpoints.clone()))));
 
-        self.handle_connect(url).await
+        self.state.connect(&mut self.socket, url, endpoints).await
     }
 
     /// Connect to a websocket endpoint
@@ -130,58 +225,195 @@ impl<'a, T> FuturesWebSockets<'a, T>
         let wss: String = format!("{}/ws/{}", self.conf.futures_ws_endpoint, endpoint);
         let url = Url::parse(&wss)?;
 
-        self.handle_connect(url).await
-    }
-
-    async fn handle_connect(&mut self, url: Url) -> Result<()> {
-        match connect_async(url).await {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => Err(Error::Msg(format!("Error during handshake {e}"))),
-        }
+        self.state.connect(&mut self.socket, url, vec![endpoint.to_string()]).await
     }
 
     /// Disconnect from the endpoint
-    pub async fn disconnect(&mut self) -> Result<()> {
-        if let Some(ref mut socket) = self.socket {
-            socket.0.close(None).await?;
-            Ok(())
-        } else {
-            Err(Error::Msg("Not able to close the connection".to_string()))
-        }
-    }
+    pub async fn disconnect(&mut self) -> Result<()> { self.state.disconnect(&mut self.socket).await }
 
-    pub fn socket(&self) -> &Option<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> { &self.socket }
+    pub fn socket(&self) -> &Option<WebSocketConnection> { &self.socket }
+
+    /// Handle to manage the subscriptions of the connection while the event loop or the stream runs
+    pub fn control(&self) -> WebSocketControl { self.state.control() }
 
     fn message_handler(&mut self, msg: &str) -> Result<()> {
-        let value: Value = from_str(msg)?;
-        let event = if let Some(data) = value.get("data").cloned() {
-            from_value::<FuturesWebsocketEvent>(data)?
-        } else {
-            from_value::<FuturesWebsocketEvent>(value)?
-        };
+        let event = parse_event(msg)?;
         (self.handler)(event)
     }
 
+    /// Dispatches events to the handler until `running` is cleared.
+    /// If reconnection is disabled, returns an error when the connection drops.
     pub async fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
         while running.load(Ordering::Relaxed) {
-            if let Some((ref mut socket, _)) = self.socket {
-                // TODO: return error instead of panic?
-                let message = socket.next().await.unwrap()?;
-
-                match message {
-                    Message::Text(msg) => {
-                        self.message_handler(msg.as_str())?;
-                    }
-                    Message::Ping(_) | Message::Pong(_) | Message::Binary(_) | Message::Frame(_) => {}
-                    Message::Close(e) => {
-                        return Err(Error::Msg(format!("Disconnected {e:?}")));
-                    }
-                }
-            }
+            let Some(msg) = self.state.next_text(&mut self.socket, Some(running)).await? else {
+                break;
+            };
+            self.message_handler(msg.as_str())?;
         }
         Ok(())
     }
+
+    /// Consumes the connected websocket into a stream of events, the handler is never called.
+    /// Dropping the stream drops the connection, see `into_stream_until` to close it gracefully.
+    pub fn into_stream(self) -> BoxStream<'a, Result<FuturesWebsocketEvent>> {
+        self.into_stream_until(futures::future::pending())
+    }
+
+    /// Consumes the connected websocket into a stream of events which ends once `shutdown` completes,
+    /// after sending a close frame to the server.
+    /// Deserialization errors are yielded without ending the stream, connection errors end it.
+    pub fn into_stream_until<F>(self, shutdown: F) -> BoxStream<'a, Result<FuturesWebsocketEvent>>
+        where F: Future<Output = ()> + Send + 'a,
+    {
+        event_stream(self.socket, self.state, shutdown.boxed(), |msg: String| Some(parse_event(&msg)))
+    }
+}
+
+/// Parses a raw or combined stream message
+fn parse_event(msg: &str) -> Result<FuturesWebsocketEvent> {
+    let mut value: Value = from_str(msg)?;
+    if let Some(data) = value.get_mut("data") {
+        value = data.take();
+    }
+    let event = match value {
+        Value::Array(values) => FuturesWebsocketEvent::Multiple(
+            values
+                .into_iter()
+                .map(from_value::<FuturesWebsocketEvent>)
+                .collect::<core::result::Result<_, _>>()?,
+        ),
+        value => from_value::<FuturesWebsocketEvent>(value)?,
+    };
+    Ok(event)
+}
+
+/// Connects to the futures user data stream of `listen_key`, obtained with `UserStream::start`,
+/// and returns its events as a stream
+pub async fn user_data_stream<T: FuturesType>(
+    conf: Config,
+    listen_key: &str,
+) -> Result<BoxStream<'static, Result<FuturesWebsocketEvent>>> {
+    let mut ws = FuturesWebSockets::<T>::new_stream(conf);
+    ws.connect(listen_key).await?;
+    Ok(ws.into_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::futures::rest_model::{MarginType, OrderStatus, PositionSide};
+    use crate::futures::ws_model::ExecutionType;
+    use crate::test_util::amount;
+
+    #[test]
+    fn order_trade_update() {
+        let msg = r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,"o":{"s":"BTCUSDT","c":"TEST","S":"SELL","o":"TRAILING_STOP_MARKET","f":"GTC","q":"0.001","p":"0","ap":"0","sp":"7103.04","x":"NEW","X":"NEW","i":8886774,"l":"0","z":"0","L":"0","N":"USDT","n":"0","T":1568879465650,"t":0,"b":"0","a":"9.91","m":false,"R":false,"wt":"CONTRACT_PRICE","ot":"TRAILING_STOP_MARKET","ps":"LONG","cp":false,"AP":"7476.89","cr":"5.0","rp":"0"}}"#;
+        let FuturesWebsocketEvent::OrderTradeUpdate(event) = parse_event(msg).unwrap() else {
+            panic!("ORDER_TRADE_UPDATE not parsed");
+        };
+        assert_eq!(event.order.symbol, "BTCUSDT");
+        assert_eq!(event.order.execution_type, ExecutionType::New);
+        assert_eq!(event.order.activation_price, Some(amount("7476.89")));
+        assert_eq!(event.order.commission_asset.as_deref(), Some("USDT"));
+    }
+
+    #[test]
+    fn self_trade_prevention_expiry() {
+        let msg = r#"{"e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,"o":{"s":"BTCUSDT","c":"TEST","S":"BUY","o":"LIMIT","f":"GTC","q":"0.001","p":"25000","ap":"0","sp":"0","x":"EXPIRED","X":"EXPIRED_IN_MATCH","i":8886775,"l":"0","z":"0","L":"0","T":1568879465650,"t":0,"b":"0","a":"0","m":false,"R":false,"wt":"CONTRACT_PRICE","ot":"LIMIT","ps":"BOTH","cp":false,"rp":"0","V":"EXPIRE_MAKER"}}"#;
+        let FuturesWebsocketEvent::OrderTradeUpdate(event) = parse_event(msg).unwrap() else {
+            panic!("ORDER_TRADE_UPDATE not parsed");
+        };
+        assert_eq!(event.order.order_status, OrderStatus::ExpiredInMatch);
+    }
+
+    #[test]
+    fn account_update() {
+        let msg = r#"{"stream":"listenkey","data":{"e":"ACCOUNT_UPDATE","E":1564745798939,"T":1564745798938,"a":{"m":"ORDER","B":[{"a":"USDT","wb":"122624.12345678","cw":"100.12345678","bc":"50.12345678"}],"P":[{"s":"BTCUSDT","pa":"0","ep":"0.00000","cr":"200","up":"0","mt":"isolated","iw":"0.00000000","ps":"BOTH"}]}}}"#;
+        let FuturesWebsocketEvent::AccountUpdate(event) = parse_event(msg).unwrap() else {
+            panic!("ACCOUNT_UPDATE not parsed");
+        };
+        assert_eq!(event.data.reason, "ORDER");
+        assert_eq!(event.data.balances[0].balance_change, Some(amount("50.12345678")));
+        assert!(matches!(event.data.positions[0].margin_type, MarginType::Isolated));
+    }
+
+    #[test]
+    fn margin_call() {
+        let msg = r#"{"e":"MARGIN_CALL","E":1587727187525,"cw":"3.16812045","p":[{"s":"ETHUSDT","ps":"LONG","pa":"1.327","mt":"CROSSED","iw":"0","mp":"187.17127","up":"-1.166074","mm":"1.614445"}]}"#;
+        let FuturesWebsocketEvent::MarginCall(event) = parse_event(msg).unwrap() else {
+            panic!("MARGIN_CALL not parsed");
+        };
+        assert_eq!(event.cross_wallet_balance, Some(amount("3.16812045")));
+        assert!(matches!(event.positions[0].margin_type, MarginType::Cross));
+        assert!(matches!(event.positions[0].position_side, PositionSide::Long));
+    }
+
+    #[test]
+    fn account_config_update_and_expiration() {
+        let msg = r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ac":{"s":"BTCUSDT","l":25}}"#;
+        let FuturesWebsocketEvent::AccountConfigUpdate(event) = parse_event(msg).unwrap() else {
+            panic!("ACCOUNT_CONFIG_UPDATE not parsed");
+        };
+        assert_eq!(event.leverage.unwrap().leverage, 25);
+        assert!(event.multi_assets.is_none());
+
+        let msg = r#"{"e":"listenKeyExpired","E":1576653824250,"listenKey":"WsCMN0a4KHUPTQuX6IUnqEZfB1inxmv1qR4kbf1LuEjur5VdbzqvyxqG9TSjVVxv"}"#;
+        assert!(matches!(
+            parse_event(msg).unwrap(),
+            FuturesWebsocketEvent::ListenKeyExpired(_)
+        ));
+    }
+
+    #[test]
+    fn market_stream_names() {
+        assert_eq!(mark_price_stream("btcusdt", 1), "btcusdt@markPrice@1s");
+        assert_eq!(mark_price_stream("btcusd_perp", 3), "btcusd_perp@markPrice");
+        assert_eq!(all_mark_price_stream(1), "!markPrice@arr@1s");
+        assert_eq!(force_order_stream("btcusdt"), "btcusdt@forceOrder");
+        assert_eq!(
+            continuous_kline_stream("btcusdt", "perpetual", KlineInterval::Minutes1),
+            "btcusdt_perpetual@continuousKline_1m"
+        );
+        assert_eq!(composite_index_stream("defiusdt"), "defiusdt@compositeIndex");
+        assert_eq!(asset_index_stream("adausd"), "adausd@assetIndex");
+    }
+
+    #[test]
+    fn mark_price_array() {
+        let msg = r#"{"stream":"!markPrice@arr","data":[{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"11185.87786614","i":"11784.62659091","P":"11784.25641265","r":"0.00030000","T":1562306400000}]}"#;
+        let FuturesWebsocketEvent::Multiple(events) = parse_event(msg).unwrap() else {
+            panic!("!markPrice@arr not parsed");
+        };
+        let FuturesWebsocketEvent::MarkPrice(event) = &events[0] else {
+            panic!("markPriceUpdate not parsed");
+        };
+        assert_eq!(event.mark_price, 11185.87786614);
+        assert_eq!(event.index_price, Some(11784.62659091));
+
+        // COIN-M delivery contracts have neither index price nor funding rate
+        let msg = r#"{"e":"markPriceUpdate","E":1596095725000,"s":"BTCUSD_201225","p":"10934.62615417","P":"10962.17178236","r":"","T":0}"#;
+        let FuturesWebsocketEvent::MarkPrice(event) = parse_event(msg).unwrap() else {
+            panic!("markPriceUpdate not parsed");
+        };
+        assert_eq!(event.index_price, None);
+        assert_eq!(event.funding_rate, None);
+    }
+
+    #[test]
+    fn market_events() {
+        let force_order = r#"{"e":"forceOrder","E":1568014460893,"o":{"s":"BTCUSDT","S":"SELL","o":"LIMIT","f":"IOC","q":"0.014","p":"9910","ap":"9910","X":"FILLED","l":"0.014","z":"0.014","T":1568014460893}}"#;
+        assert!(matches!(parse_event(force_order).unwrap(), FuturesWebsocketEvent::Liquidation(e) if e.order.price == 9910.0));
+
+        let kline = r#"{"e":"continuous_kline","E":1607443058651,"ps":"BTCUSDT","ct":"PERPETUAL","k":{"t":1607443020000,"T":1607443079999,"i":"1m","f":116467658886,"L":116468012423,"o":"18787.00","c":"18804.04","h":"18804.04","l":"18786.54","v":"197.664","n":543,"x":false,"q":"3715253.19494","V":"184.769","Q":"3472925.84746","B":"0"}}"#;
+        assert!(matches!(parse_event(kline).unwrap(), FuturesWebsocketEvent::ContinuousKline(e) if e.kline.close == 18804.04));
+
+        let composite = r#"{"e":"compositeIndex","E":1602310596000,"s":"DEFIUSDT","p":"554.41604065","C":"baseAsset","c":[{"b":"BAL","q":"USDT","w":"1.04884844","W":"0.01457800","i":"24.33521021"}]}"#;
+        assert!(matches!(parse_event(composite).unwrap(), FuturesWebsocketEvent::CompositeIndex(e) if e.composition.len() == 1));
+
+        let contract = r#"{"e":"contractInfo","E":1669356423908,"s":"IOTAUSDT","ps":"IOTAUSDT","ct":"PERPETUAL","dt":4133404800000,"ot":1569398400000,"cs":"TRADING","bks":[{"bs":1,"bnf":0,"bnc":5000,"mmr":0.01,"cf":0,"mi":21,"ma":50}]}"#;
+        assert!(matches!(parse_event(contract).unwrap(), FuturesWebsocketEvent::ContractInfo(e) if e.brackets[0].max_leverage == 50));
+
+        let asset_index = r#"[{"e":"assetIndUpdate","E":1686744360000,"s":"ADAUSD","i":"0.27462452","b":"0.10000000","a":"0.10000000","B":"0.24716207","A":"0.30208698","q":"0.05000000","g":"0.05000000","Q":"0.26089330","G":"0.28835575"}]"#;
+        assert!(matches!(parse_event(asset_index).unwrap(), FuturesWebsocketEvent::Multiple(e) if e.len() == 1));
+    }
 }
@@ -1,3 +1,8 @@
+use crate::futures::rest_model::{
+    ContractType, MarginType, OrderSide, OrderStatus, OrderType, PositionSide, TimeInForce, WorkingType,
+};
+use crate::rest_model::{string_or_float, string_or_float_opt, Amount};
+
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -25,4 +30,425 @@ pub struct TradeEvent {
 
     #[serde(rename = "m")]
     pub is_buyer_maker: bool,
-}
\ No newline at end of file
+}
+/// Futures order execution type
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExecutionType {
+    New,
+    Canceled,
+    /// Liquidation execution
+    Calculated,
+    Expired,
+    Trade,
+    /// Order modified
+    Amendment,
+}
+
+/// ORDER_TRADE_UPDATE, sent when an order is created, filled or its status changes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderTradeUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    /// Account alias, only sent on COIN-M streams
+    #[serde(rename = "i", default)]
+    pub account_alias: Option<String>,
+    #[serde(rename = "o")]
+    pub order: OrderTradeUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderTradeUpdate {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub original_qty: Amount,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub original_price: Amount,
+    #[serde(rename = "ap", with = "string_or_float")]
+    pub average_price: Amount,
+    #[serde(rename = "sp", with = "string_or_float")]
+    pub stop_price: Amount,
+    #[serde(rename = "x")]
+    pub execution_type: ExecutionType,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub last_filled_qty: Amount,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub cumulative_filled_qty: Amount,
+    #[serde(rename = "L", with = "string_or_float")]
+    pub last_filled_price: Amount,
+    /// Not sent when there is no commission
+    #[serde(rename = "N", default)]
+    pub commission_asset: Option<String>,
+    #[serde(rename = "n", default, with = "string_or_float_opt")]
+    pub commission: Option<Amount>,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    #[serde(rename = "t")]
+    pub trade_id: u64,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub bids_notional: Amount,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub asks_notional: Amount,
+    #[serde(rename = "m")]
+    pub is_maker: bool,
+    #[serde(rename = "R")]
+    pub is_reduce_only: bool,
+    #[serde(rename = "wt")]
+    pub working_type: WorkingType,
+    #[serde(rename = "ot")]
+    pub original_order_type: OrderType,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+    /// If close-all, pushed with conditional orders
+    #[serde(rename = "cp", default)]
+    pub close_position: bool,
+    /// Activation price, only pushed with TRAILING_STOP_MARKET orders
+    #[serde(rename = "AP", default, with = "string_or_float_opt")]
+    pub activation_price: Option<Amount>,
+    /// Callback rate, only pushed with TRAILING_STOP_MARKET orders
+    #[serde(rename = "cr", default, with = "string_or_float_opt")]
+    pub callback_rate: Option<Amount>,
+    #[serde(rename = "rp", default, with = "string_or_float_opt")]
+    pub realized_profit: Option<Amount>,
+}
+
+/// ACCOUNT_UPDATE, sent when a balance or a position changes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    /// Account alias, only sent on COIN-M streams
+    #[serde(rename = "i", default)]
+    pub account_alias: Option<String>,
+    #[serde(rename = "a")]
+    pub data: AccountUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdate {
+    /// Reason of the update, e.g. ORDER, FUNDING_FEE, DEPOSIT, WITHDRAW or MARGIN_TRANSFER
+    #[serde(rename = "m")]
+    pub reason: String,
+    #[serde(rename = "B")]
+    pub balances: Vec<AccountUpdateBalance>,
+    #[serde(rename = "P")]
+    pub positions: Vec<AccountUpdatePosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdateBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "wb", with = "string_or_float")]
+    pub wallet_balance: Amount,
+    #[serde(rename = "cw", with = "string_or_float")]
+    pub cross_wallet_balance: Amount,
+    /// Balance change except PnL and commission
+    #[serde(rename = "bc", default, with = "string_or_float_opt")]
+    pub balance_change: Option<Amount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountUpdatePosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "pa", with = "string_or_float")]
+    pub position_amount: Amount,
+    #[serde(rename = "ep", with = "string_or_float")]
+    pub entry_price: Amount,
+    #[serde(rename = "bep", default, with = "string_or_float_opt")]
+    pub breakeven_price: Option<Amount>,
+    /// Pre-fee accumulated realized
+    #[serde(rename = "cr", with = "string_or_float")]
+    pub accumulated_realized: Amount,
+    #[serde(rename = "up", with = "string_or_float")]
+    pub unrealized_profit: Amount,
+    #[serde(rename = "mt")]
+    pub margin_type: MarginType,
+    #[serde(rename = "iw", with = "string_or_float")]
+    pub isolated_wallet: Amount,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+}
+
+/// MARGIN_CALL, sent when the margin ratio of positions is too high
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarginCallEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    /// Only sent for cross positions
+    #[serde(rename = "cw", default, with = "string_or_float_opt")]
+    pub cross_wallet_balance: Option<Amount>,
+    #[serde(rename = "p")]
+    pub positions: Vec<MarginCallPosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarginCallPosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+    #[serde(rename = "pa", with = "string_or_float")]
+    pub position_amount: Amount,
+    #[serde(rename = "mt")]
+    pub margin_type: MarginType,
+    /// Only sent for isolated positions
+    #[serde(rename = "iw", default, with = "string_or_float_opt")]
+    pub isolated_wallet: Option<Amount>,
+    #[serde(rename = "mp", with = "string_or_float")]
+    pub mark_price: Amount,
+    #[serde(rename = "up", with = "string_or_float")]
+    pub unrealized_profit: Amount,
+    #[serde(rename = "mm", with = "string_or_float")]
+    pub maintenance_margin_required: Amount,
+}
+
+/// ACCOUNT_CONFIG_UPDATE, sent when the leverage of a symbol or the multi-assets mode changes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountConfigUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "ac", default)]
+    pub leverage: Option<LeverageUpdate>,
+    #[serde(rename = "ai", default)]
+    pub multi_assets: Option<MultiAssetsModeUpdate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeverageUpdate {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "l")]
+    pub leverage: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MultiAssetsModeUpdate {
+    #[serde(rename = "j")]
+    pub multi_assets_margin: bool,
+}
+
+/// markPriceUpdate, from `<symbol>@markPrice` and `!markPrice@arr`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkPriceEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub mark_price: f64,
+    /// Only sent on USD-M streams
+    #[serde(rename = "i", default, with = "string_or_float_opt")]
+    pub index_price: Option<f64>,
+    /// Estimated settle price, only useful in the last hour before the settlement starts
+    #[serde(rename = "P", with = "string_or_float")]
+    pub estimated_settle_price: f64,
+    /// Empty for delivery contracts
+    #[serde(rename = "r", with = "string_or_float_opt")]
+    pub funding_rate: Option<f64>,
+    /// 0 for delivery contracts
+    #[serde(rename = "T")]
+    pub next_funding_time: u64,
+}
+
+/// forceOrder, from `<symbol>@forceOrder` and `!forceOrder@arr`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiquidationEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "o")]
+    pub order: LiquidationOrder,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LiquidationOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    /// Only sent on COIN-M streams
+    #[serde(rename = "ps", default)]
+    pub pair: Option<String>,
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub original_qty: f64,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: f64,
+    #[serde(rename = "ap", with = "string_or_float")]
+    pub average_price: f64,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub last_filled_qty: f64,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub cumulative_filled_qty: f64,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+}
+
+/// continuous_kline, from `<pair>_<contract>@continuousKline_<interval>`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContinuousKlineEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "ct")]
+    pub contract_type: ContractType,
+    #[serde(rename = "k")]
+    pub kline: ContinuousKline,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContinuousKline {
+    #[serde(rename = "t")]
+    pub start_time: i64,
+    #[serde(rename = "T")]
+    pub end_time: i64,
+    #[serde(rename = "i")]
+    pub interval: String,
+    #[serde(rename = "f")]
+    pub first_update_id: i64,
+    #[serde(rename = "L")]
+    pub last_update_id: i64,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: f64,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub close: f64,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: f64,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: f64,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: f64,
+    #[serde(rename = "n")]
+    pub number_of_trades: i64,
+    #[serde(rename = "x")]
+    pub is_final_bar: bool,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quote_volume: f64,
+    #[serde(rename = "V", with = "string_or_float")]
+    pub taker_buy_volume: f64,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub taker_buy_quote_volume: f64,
+}
+
+/// compositeIndex, from `<symbol>@compositeIndex`, USD-M only
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CompositeIndexEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: f64,
+    /// Asset the composition weights are expressed in, e.g. baseAsset
+    #[serde(rename = "C")]
+    pub composition_type: String,
+    #[serde(rename = "c")]
+    pub composition: Vec<IndexComposition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IndexComposition {
+    #[serde(rename = "b")]
+    pub base_asset: String,
+    #[serde(rename = "q")]
+    pub quote_asset: String,
+    #[serde(rename = "w", with = "string_or_float")]
+    pub weight_in_quantity: f64,
+    #[serde(rename = "W", with = "string_or_float")]
+    pub weight_in_percentage: f64,
+    #[serde(rename = "i", with = "string_or_float")]
+    pub index_price: f64,
+}
+
+/// contractInfo, from `!contractInfo`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContractInfoEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "ct")]
+    pub contract_type: ContractType,
+    #[serde(rename = "dt")]
+    pub delivery_date: u64,
+    #[serde(rename = "ot")]
+    pub onboard_date: u64,
+    #[serde(rename = "cs")]
+    pub contract_status: String,
+    /// Only sent when the brackets changed
+    #[serde(rename = "bks", default)]
+    pub brackets: Vec<ContractBracket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContractBracket {
+    #[serde(rename = "bs")]
+    pub bracket: u32,
+    #[serde(rename = "bnf")]
+    pub notional_floor: f64,
+    #[serde(rename = "bnc")]
+    pub notional_cap: f64,
+    #[serde(rename = "mmr")]
+    pub maintenance_ratio: f64,
+    #[serde(rename = "cf")]
+    pub cum: f64,
+    #[serde(rename = "mi")]
+    pub min_leverage: u8,
+    #[serde(rename = "ma")]
+    pub max_leverage: u8,
+}
+
+/// assetIndUpdate, from `<symbol>@assetIndex` and `!assetIndex@arr`, USD-M multi-assets mode only
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssetIndexEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "i", with = "string_or_float")]
+    pub index_price: f64,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub bid_buffer: f64,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub ask_buffer: f64,
+    #[serde(rename = "B", with = "string_or_float")]
+    pub bid_rate: f64,
+    #[serde(rename = "A", with = "string_or_float")]
+    pub ask_rate: f64,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub auto_exchange_bid_buffer: f64,
+    #[serde(rename = "g", with = "string_or_float")]
+    pub auto_exchange_ask_buffer: f64,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub auto_exchange_bid_rate: f64,
+    #[serde(rename = "G", with = "string_or_float")]
+    pub auto_exchange_ask_rate: f64,
+}
@@ -1,4 +1,5 @@
 use serde_json::Value;
+use url::form_urlencoded;
 
 use crate::client::*;
 use crate::errors::*;
@@ -46,7 +47,46 @@ impl General {
     /// let exchange_info = tokio_test::block_on(general.exchange_info());
     /// assert!(exchange_info.is_ok(), "{:?}", exchange_info);
     /// ```
+    ///
+    /// The rate limits are loaded into the configured rate limiter, if any
     pub async fn exchange_info(&self) -> Result<ExchangeInformation> {
-        self.client.get("/api/v3/exchangeInfo", None).await
+        self.exchange_info_with(&ExchangeInfoQuery::default()).await
     }
+
+    /// Obtain exchange information restricted to some symbols or permissions
+    /// # Examples
+    /// ```rust
+    /// use binance::{api::*, general::*, config::*, rest_model::*};
+    /// let conf = Config::default().set_rest_api_endpoint(DATA_REST_ENDPOINT);
+    /// let general: General = Binance::new_with_env(&conf);
+    /// let query = ExchangeInfoQuery {
+    ///     symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+    ///     show_permission_sets: Some(true),
+    ///     ..ExchangeInfoQuery::default()
+    /// };
+    /// let exchange_info = tokio_test::block_on(general.exchange_info_with(&query));
+    /// assert!(exchange_info.is_ok(), "{:?}", exchange_info);
+    /// ```
+    pub async fn exchange_info_with(&self, query: &ExchangeInfoQuery) -> Result<ExchangeInformation> {
+        let request = exchange_info_request(query)?;
+        let request = (!request.is_empty()).then_some(request.as_str());
+        let info: ExchangeInformation = self.client.get("/api/v3/exchangeInfo", request).await?;
+        self.client.update_rate_limits(&info.rate_limits);
+        Ok(info)
+    }
+}
+
+/// Symbols and permissions are sent as url encoded JSON arrays
+fn exchange_info_request(query: &ExchangeInfoQuery) -> Result<String> {
+    let mut request = form_urlencoded::Serializer::new(String::new());
+    if !query.symbols.is_empty() {
+        request.append_pair("symbols", &serde_json::to_string(&query.symbols)?);
+    }
+    if !query.permissions.is_empty() {
+        request.append_pair("permissions", &serde_json::to_string(&query.permissions)?);
+    }
+    if let Some(show_permission_sets) = query.show_permission_sets {
+        request.append_pair("showPermissionSets", &show_permission_sets.to_string());
+    }
+    Ok(request.finish())
 }
@@ -77,16 +77,27 @@ pub mod util;
 
 pub mod account;
 pub mod api;
+pub mod backfill;
 pub mod config;
+pub mod filters;
 // #[cfg(feature = "futures_api")]
 pub mod futures;
 pub mod general;
 #[cfg(feature = "margin_api")]
 pub mod margin;
 pub mod market;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod orderbook;
+pub mod rate_limit;
 pub mod rest_model;
 #[cfg(feature = "savings_api")]
 pub mod savings;
+pub mod signer;
+pub mod symbol_registry;
+#[cfg(test)]
+mod test_util;
+pub mod time_sync;
 pub mod userstream;
 #[cfg(feature = "wallet_api")]
 pub mod wallet;
@@ -0,0 +1,585 @@
+//! In-process stand-in for the Binance REST and websocket endpoints, to test clients offline
+//!
+//! The [`MockServer`] serves canned responses for common spot, margin, wallet and futures routes,
+//! which can be replaced by other canned responses or by closures. Signed requests are verified like
+//! Binance does: the API key header, the signature of the query with the configured key and the
+//! timestamp against the receive window. Errors can be injected to test the error handling.
+//!
+//! ```rust
+//! use binance::{api::*, errors::*, general::*, mock::*};
+//! # tokio_test::block_on(async {
+//! let server = MockServer::start().await;
+//! let general: General = Binance::new_with_config(None, None, &server.config());
+//! assert!(general.ping().await.is_ok());
+//! server.inject(MockResponse::too_many_requests(30));
+//! assert!(matches!(general.ping().await, Err(Error::TooManyRequests(_))));
+//! # });
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use reqwest::Method;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::server::{
+    ErrorResponse, Request as HandshakeRequest, Response as HandshakeResponse,
+};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::Message;
+use url::form_urlencoded;
+
+use crate::config::Config;
+use crate::signer::{signer_or_invalid, KeyType, Signer};
+
+pub static MOCK_API_KEY: &str = "mock_api_key";
+pub static MOCK_SECRET_KEY: &str = "mock_secret_key";
+
+/// Request received by the mock server
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: Method,
+    pub path: String,
+    /// Parameters of the query string and of the form body
+    pub params: Vec<(String, String)>,
+    pub api_key: Option<String>,
+}
+
+impl MockRequest {
+    /// First value of a parameter
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Response served by the mock server
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    /// 200 response with a json body
+    pub fn json(body: Value) -> Self { Self::status(200, body.to_string()) }
+
+    pub fn status(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    /// 400 response with a Binance error
+    pub fn error(code: i32, msg: &str) -> Self { Self::status(400, json!({ "code": code, "msg": msg }).to_string()) }
+
+    /// 429 response asking to retry after `retry_after` seconds
+    pub fn too_many_requests(retry_after: u64) -> Self {
+        Self::status(429, json!({ "code": -1003, "msg": "Too many requests." }).to_string())
+            .header("Retry-After", &retry_after.to_string())
+    }
+
+    /// 418 response of a banned IP, banned for `retry_after` seconds
+    pub fn ip_banned(retry_after: u64) -> Self {
+        Self::status(418, json!({ "code": -1003, "msg": "Way too many requests; IP banned." }).to_string())
+            .header("Retry-After", &retry_after.to_string())
+    }
+
+    /// 5xx response
+    pub fn server_error(status: u16) -> Self { Self::status(status, "") }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+type MockHandler = Arc<dyn Fn(&MockRequest) -> MockResponse + Send + Sync>;
+
+struct MockState {
+    api_key: String,
+    signer: Arc<dyn Signer>,
+    /// Server time minus local time, in milliseconds
+    time_offset: i64,
+    routes: HashMap<(Method, String), MockHandler>,
+    once: HashMap<(Method, String), VecDeque<MockResponse>>,
+    injected: VecDeque<MockResponse>,
+    requests: Vec<MockRequest>,
+}
+
+/// Mock Binance server, stopped when dropped
+pub struct MockServer {
+    addr: SocketAddr,
+    ws_addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    events: broadcast::Sender<String>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts a server accepting requests signed with `MOCK_API_KEY` and `MOCK_SECRET_KEY`
+    pub async fn start() -> Self { Self::start_with_credentials(MOCK_API_KEY, MOCK_SECRET_KEY, KeyType::Hmac).await }
+
+    /// Starts a server accepting requests signed with the given key
+    pub async fn start_with_credentials(api_key: &str, secret_key: &str, key_type: KeyType) -> Self {
+        let mut routes: HashMap<(Method, String), MockHandler> = HashMap::new();
+        for (method, path, body) in canned_responses() {
+            let response = MockResponse::json(body);
+            routes.insert((method, path.to_string()), Arc::new(move |_| response.clone()));
+        }
+        let state = Arc::new(Mutex::new(MockState {
+            api_key: api_key.to_string(),
+            signer: signer_or_invalid(key_type, secret_key),
+            time_offset: 0,
+            routes,
+            once: HashMap::new(),
+            injected: VecDeque::new(),
+            requests: vec![],
+        }));
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+        });
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let addr = server.local_addr();
+        let http_task = tokio::spawn(async move {
+            let _ = server.await;
+        });
+
+        let (events, _) = broadcast::channel(1024);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_addr = listener.local_addr().unwrap();
+        let ws_events = events.clone();
+        let ws_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve_websocket(stream, ws_events.subscribe()));
+            }
+        });
+
+        Self {
+            addr,
+            ws_addr,
+            state,
+            events,
+            tasks: vec![http_task, ws_task],
+        }
+    }
+
+    /// REST endpoint, e.g. `http://127.0.0.1:34567`
+    pub fn rest_endpoint(&self) -> String { format!("http://{}", self.addr) }
+
+    /// Websocket endpoint, e.g. `ws://127.0.0.1:34568`
+    pub fn ws_endpoint(&self) -> String { format!("ws://{}", self.ws_addr) }
+
+    /// Default configuration with every endpoint pointing to the server
+    pub fn config(&self) -> Config {
+        Config::default()
+            .set_rest_api_endpoint(self.rest_endpoint())
+            .set_ws_endpoint(self.ws_endpoint())
+            .set_ws_api_endpoint(self.ws_endpoint())
+            .set_futures_rest_api_endpoint(self.rest_endpoint())
+            .set_futures_ws_endpoint(self.ws_endpoint())
+    }
+
+    pub fn api_key(&self) -> String { self.state.lock().unwrap().api_key.clone() }
+
+    /// Shifts the server clock by `offset` milliseconds, to test clients with a drifting clock
+    pub fn set_time_offset(&self, offset: i64) { self.state.lock().unwrap().time_offset = offset; }
+
+    /// Serves `response` to every request of a route, e.g. `(Method::GET, "/api/v3/account")`
+    pub fn mock(&self, method: Method, path: &str, response: MockResponse) {
+        self.mock_fn(method, path, move |_| response.clone());
+    }
+
+    /// Serves the responses of `handler` to the requests of a route
+    pub fn mock_fn<F>(&self, method: Method, path: &str, handler: F)
+    where
+        F: Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .routes
+            .insert((method, path.to_string()), Arc::new(handler));
+    }
+
+    /// Serves `response` to the next request of a route, before its other responses
+    pub fn mock_once(&self, method: Method, path: &str, response: MockResponse) {
+        self.state
+            .lock()
+            .unwrap()
+            .once
+            .entry((method, path.to_string()))
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Serves `response` to the next request whatever its route, e.g. to inject errors
+    pub fn inject(&self, response: MockResponse) { self.state.lock().unwrap().injected.push_back(response); }
+
+    /// Requests received so far
+    pub fn requests(&self) -> Vec<MockRequest> { self.state.lock().unwrap().requests.clone() }
+
+    /// Sends `event` to every open websocket
+    pub fn push_event(&self, event: Value) { let _ = self.events.send(event.to_string()); }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+async fn handle(state: Arc<Mutex<MockState>>, request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+    let query = parts.uri.query().unwrap_or_default().to_string();
+    let params = form_urlencoded::parse(query.as_bytes())
+        .chain(form_urlencoded::parse(&body))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    let request = MockRequest {
+        method: parts.method,
+        path: parts.uri.path().to_string(),
+        params,
+        api_key: parts
+            .headers
+            .get("x-mbx-apikey")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+    };
+
+    let response = respond(&state, &request, &query);
+    state.lock().unwrap().requests.push(request);
+    let mut builder = Response::builder().status(response.status);
+    if response.body.starts_with(['{', '[']) {
+        builder = builder.header("Content-Type", "application/json");
+    }
+    for (name, value) in &response.headers {
+        builder = builder.header(name, value);
+    }
+    Ok(builder.body(Body::from(response.body)).unwrap())
+}
+
+fn respond(state: &Mutex<MockState>, request: &MockRequest, query: &str) -> MockResponse {
+    let mut state = state.lock().unwrap();
+    if let Some(response) = state.injected.pop_front() {
+        return response;
+    }
+    if let Err(response) = verify(&state, request, query) {
+        return response;
+    }
+    let route = (request.method.clone(), request.path.clone());
+    if let Some(response) = state.once.get_mut(&route).and_then(VecDeque::pop_front) {
+        return response;
+    }
+    if let Some(handler) = state.routes.get(&route).cloned() {
+        drop(state);
+        return handler(request);
+    }
+    if request.path.ends_with("/time") {
+        let server_time = Utc::now().timestamp_millis() + state.time_offset;
+        return MockResponse::json(json!({ "serverTime": server_time }));
+    }
+    MockResponse::status(404, "")
+}
+
+/// Checks the API key and the signature of signed requests
+fn verify(state: &MockState, request: &MockRequest, query: &str) -> Result<(), MockResponse> {
+    let invalid_key = || {
+        MockResponse::status(
+            401,
+            json!({ "code": -2015, "msg": "Invalid API-key, IP, or permissions for action." }).to_string(),
+        )
+    };
+    if request.api_key.as_ref().is_some_and(|api_key| *api_key != state.api_key) {
+        return Err(invalid_key());
+    }
+    let Some(signature) = request.param("signature") else {
+        return Ok(());
+    };
+    if request.api_key.is_none() {
+        return Err(invalid_key());
+    }
+    let payload = query.split("&signature=").next().unwrap_or_default();
+    if state.signer.sign(payload.as_bytes()).ok().as_deref() != Some(signature) {
+        return Err(MockResponse::error(-1022, "Signature for this request is not valid."));
+    }
+    let server_time = Utc::now().timestamp_millis() + state.time_offset;
+    let timestamp: i64 = request.param("timestamp").and_then(|t| t.parse().ok()).unwrap_or_default();
+    let recv_window: i64 = request.param("recvWindow").and_then(|w| w.parse().ok()).unwrap_or(5000);
+    if timestamp >= server_time + 1000 || server_time - timestamp > recv_window {
+        return Err(MockResponse::error(
+            -1021,
+            "Timestamp for this request is outside of the recvWindow.",
+        ));
+    }
+    Ok(())
+}
+
+/// Forwards the pushed events and answers the subscription requests of a websocket
+async fn serve_websocket(stream: tokio::net::TcpStream, mut events: broadcast::Receiver<String>) {
+    let check_path = |request: &HandshakeRequest, response: HandshakeResponse| {
+        if is_websocket_path(request.uri().path(), request.uri().query()) {
+            Ok(response)
+        } else {
+            let mut error = ErrorResponse::new(Some(format!("unknown websocket path {}", request.uri())));
+            *error.status_mut() = StatusCode::NOT_FOUND;
+            Err(error)
+        }
+    };
+    let Ok(mut socket) = tokio_tungstenite::accept_hdr_async(stream, check_path).await else {
+        return;
+    };
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    if socket.send(Message::Text(event)).await.is_err() {
+                        return;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            message = socket.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let Ok(request) = serde_json::from_str::<Value>(&text) else {
+                        continue;
+                    };
+                    let reply = json!({ "result": null, "id": request["id"] });
+                    if socket.send(Message::Text(reply.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+/// Whether the path is a raw stream, `/ws/<listenKey>`, or combined streams, `/stream?streams=<a>/<b>`
+fn is_websocket_path(path: &str, query: Option<&str>) -> bool {
+    match path.strip_prefix('/') {
+        Some("stream") => query
+            .and_then(|query| query.strip_prefix("streams="))
+            .is_some_and(|streams| !streams.is_empty()),
+        Some(path) => path
+            .strip_prefix("ws/")
+            .is_some_and(|name| !name.is_empty() && !name.contains('/')),
+        None => false,
+    }
+}
+
+/// Default responses of the common routes
+fn canned_responses() -> Vec<(Method, &'static str, Value)> {
+    let listen_key = json!({ "listenKey": "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1" });
+    let order = json!({
+        "symbol": "BTCUSDT", "orderId": 28, "orderListId": -1, "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+        "transactTime": 1507725176595u64, "price": "25000.00000000", "origQty": "0.00100000",
+        "executedQty": "0.00000000", "cummulativeQuoteQty": "0.00000000", "status": "NEW", "timeInForce": "GTC",
+        "type": "LIMIT", "side": "BUY", "workingTime": 1507725176595u64, "fills": [],
+        "selfTradePreventionMode": "NONE"
+    });
+    let canceled = json!({
+        "symbol": "BTCUSDT", "origClientOrderId": "6gCrw2kRUAF9CvJDGP16IP", "orderId": 28, "orderListId": -1,
+        "clientOrderId": "cancelMyOrder1", "transactTime": 1684804350068u64, "price": "25000.00000000",
+        "origQty": "0.00100000", "executedQty": "0.00000000", "cummulativeQuoteQty": "0.00000000",
+        "status": "CANCELED", "timeInForce": "GTC", "type": "LIMIT", "side": "BUY",
+        "selfTradePreventionMode": "NONE"
+    });
+    let account = json!({
+        "makerCommission": 15, "takerCommission": 15, "buyerCommission": 0, "sellerCommission": 0,
+        "canTrade": true, "canWithdraw": true, "canDeposit": true, "accountType": "SPOT",
+        "balances": [
+            { "asset": "BTC", "free": "1.00000000", "locked": "0.00000000" },
+            { "asset": "USDT", "free": "10000.00000000", "locked": "0.00000000" }
+        ],
+        "permissions": ["SPOT"], "updateTime": 123456789
+    });
+    let exchange_info = json!({
+        "timezone": "UTC",
+        "serverTime": 1565246363776u64,
+        "rateLimits": [
+            { "rateLimitType": "REQUEST_WEIGHT", "interval": "MINUTE", "intervalNum": 1, "limit": 6000 },
+            { "rateLimitType": "ORDERS", "interval": "SECOND", "intervalNum": 10, "limit": 100 },
+            { "rateLimitType": "ORDERS", "interval": "DAY", "intervalNum": 1, "limit": 200000 },
+            { "rateLimitType": "RAW_REQUESTS", "interval": "MINUTE", "intervalNum": 5, "limit": 61000 }
+        ],
+        "exchangeFilters": [],
+        "symbols": [{
+            "symbol": "BTCUSDT", "status": "TRADING", "baseAsset": "BTC", "baseAssetPrecision": 8,
+            "quoteAsset": "USDT", "quotePrecision": 8, "quoteAssetPrecision": 8, "baseCommissionPrecision": 8,
+            "quoteCommissionPrecision": 8,
+            "orderTypes": ["LIMIT", "LIMIT_MAKER", "MARKET", "STOP_LOSS_LIMIT", "TAKE_PROFIT_LIMIT"],
+            "icebergAllowed": true, "ocoAllowed": true, "quoteOrderQtyMarketAllowed": true,
+            "isSpotTradingAllowed": true, "isMarginTradingAllowed": true,
+            "filters": [
+                { "filterType": "PRICE_FILTER", "minPrice": "0.01000000", "maxPrice": "1000000.00000000", "tickSize": "0.01000000" },
+                { "filterType": "LOT_SIZE", "minQty": "0.00001000", "maxQty": "9000.00000000", "stepSize": "0.00001000" },
+                { "filterType": "MARKET_LOT_SIZE", "minQty": "0.00000000", "maxQty": "100.00000000", "stepSize": "0.00000000" },
+                { "filterType": "MIN_NOTIONAL", "minNotional": "5.00000000", "applyToMarket": true, "avgPriceMins": 5 },
+                { "filterType": "MAX_NUM_ORDERS", "maxNumOrders": 200 }
+            ],
+            "permissions": ["SPOT", "MARGIN"]
+        }]
+    });
+    vec![
+        // spot
+        (Method::GET, "/api/v3/ping", json!({})),
+        (Method::GET, "/api/v3/exchangeInfo", exchange_info),
+        (Method::GET, "/api/v3/account", account),
+        (Method::GET, "/api/v3/openOrders", json!([])),
+        (Method::POST, "/api/v3/order/test", json!({})),
+        (Method::POST, "/api/v3/order", order),
+        (Method::DELETE, "/api/v3/order", canceled.clone()),
+        (Method::DELETE, "/api/v3/openOrders", json!([canceled])),
+        (Method::POST, "/api/v3/userDataStream", listen_key.clone()),
+        (Method::PUT, "/api/v3/userDataStream", json!({})),
+        (Method::DELETE, "/api/v3/userDataStream", json!({})),
+        // margin
+        (Method::POST, "/sapi/v1/userDataStream", listen_key.clone()),
+        (Method::PUT, "/sapi/v1/userDataStream", json!({})),
+        (Method::DELETE, "/sapi/v1/userDataStream", json!({})),
+        (Method::POST, "/sapi/v1/userDataStream/isolated", listen_key.clone()),
+        (Method::PUT, "/sapi/v1/userDataStream/isolated", json!({})),
+        (Method::DELETE, "/sapi/v1/userDataStream/isolated", json!({})),
+        // wallet
+        (Method::GET, "/sapi/v1/system/status", json!({ "status": 0, "msg": "normal" })),
+        (Method::GET, "/sapi/v1/capital/config/getall", json!([])),
+        // futures
+        (Method::GET, "/fapi/v1/ping", json!({})),
+        (Method::POST, "/fapi/v1/listenKey", listen_key.clone()),
+        (Method::PUT, "/fapi/v1/listenKey", json!({})),
+        (Method::DELETE, "/fapi/v1/listenKey", json!({})),
+        (Method::GET, "/dapi/v1/ping", json!({})),
+        (Method::POST, "/dapi/v1/listenKey", listen_key),
+        (Method::PUT, "/dapi/v1/listenKey", json!({})),
+        (Method::DELETE, "/dapi/v1/listenKey", json!({})),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::Account;
+    use crate::api::Binance;
+    use crate::errors::Error;
+    use crate::general::General;
+    use crate::time_sync::TimeSync;
+    use crate::userstream::UserStream;
+    use crate::websockets::{WebSockets, STREAM_ENDPOINT, WS_ENDPOINT};
+    use crate::ws_model::WebsocketEvent;
+
+    #[tokio::test]
+    async fn verifies_signed_requests() {
+        let server = MockServer::start().await;
+        let conf = server.config();
+        let account: Account = Binance::new_with_config(Some(server.api_key()), Some(MOCK_SECRET_KEY.into()), &conf);
+        assert_eq!(account.get_account().await.unwrap().balances.len(), 2);
+
+        let account: Account = Binance::new_with_config(Some(server.api_key()), Some("wrong".into()), &conf);
+        let error = account.get_account().await.unwrap_err();
+        assert!(matches!(error, Error::BinanceError { response } if response.code == -1022));
+
+        let account: Account = Binance::new_with_config(Some("wrong".into()), Some(MOCK_SECRET_KEY.into()), &conf);
+        assert!(matches!(account.get_account().await, Err(Error::Unauthorized)));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].path, "/api/v3/account");
+        assert!(requests[0].param("timestamp").is_some());
+    }
+
+    #[tokio::test]
+    async fn serves_mocked_and_injected_responses() {
+        let server = MockServer::start().await;
+        let general: General = Binance::new_with_config(None, None, &server.config());
+        server.mock_once(Method::GET, "/api/v3/ping", MockResponse::error(-1000, "An unknown error occurred."));
+        server.inject(MockResponse::ip_banned(120));
+        server.inject(MockResponse::server_error(503));
+
+        let error = general.ping().await.unwrap_err();
+        assert!(matches!(&error, Error::IpBanned(details) if details.retry_after.unwrap().as_secs() == 120));
+        assert!(matches!(general.ping().await, Err(Error::ServiceUnavailable)));
+        assert!(matches!(general.ping().await, Err(Error::BinanceError { .. })));
+        assert!(general.ping().await.is_ok());
+
+        server.mock_fn(Method::GET, "/api/v3/ping", |request| {
+            MockResponse::status(404, "").header("X-Path", &request.path)
+        });
+        assert!(matches!(general.ping().await, Err(Error::NotFound(_))));
+        assert_eq!(general.exchange_info().await.unwrap().symbols[0].symbol, "BTCUSDT");
+    }
+
+    #[tokio::test]
+    async fn drifting_server_clock_is_corrected_by_the_time_sync() {
+        let server = MockServer::start().await;
+        server.set_time_offset(-60_000);
+        let account: Account =
+            Binance::new_with_config(Some(server.api_key()), Some(MOCK_SECRET_KEY.into()), &server.config());
+        let error = account.get_account().await.unwrap_err();
+        assert!(matches!(error, Error::BinanceError { response } if response.code == -1021));
+
+        let conf = server.config().set_time_sync(TimeSync::default());
+        let account: Account = Binance::new_with_config(Some(server.api_key()), Some(MOCK_SECRET_KEY.into()), &conf);
+        assert!(account.get_account().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn pushes_websocket_events() {
+        let server = MockServer::start().await;
+        let conf = server.config();
+        let user_stream: UserStream = Binance::new_with_config(Some(server.api_key()), None, &conf);
+        let listen_key = user_stream.start().await.unwrap().listen_key;
+
+        let mut web_socket: WebSockets<'_, WebsocketEvent> = WebSockets::new_with_options(|_| Ok(()), conf);
+        web_socket.connect(&listen_key).await.unwrap();
+        let mut events = web_socket.into_stream();
+        server.push_event(json!({
+            "e": "listenKeyExpired", "E": 1576653824250u64, "listenKey": listen_key
+        }));
+        let event = events.next().await.unwrap().unwrap();
+        assert!(matches!(event, WebsocketEvent::ListenKeyExpired(_)));
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_websocket_paths() {
+        let server = MockServer::start().await;
+        let endpoint = server.ws_endpoint();
+        for path in [
+            format!("{WS_ENDPOINT}/{WS_ENDPOINT}/key"),
+            format!("{WS_ENDPOINT}/"),
+            STREAM_ENDPOINT.to_string(),
+            "key".to_string(),
+        ] {
+            let connection = tokio_tungstenite::connect_async(format!("{endpoint}/{path}")).await;
+            assert!(connection.is_err(), "{path} was accepted");
+        }
+        for path in [
+            format!("{WS_ENDPOINT}/key"),
+            format!("{STREAM_ENDPOINT}?streams=btcusdt@trade/ethusdt@trade"),
+        ] {
+            let connection = tokio_tungstenite::connect_async(format!("{endpoint}/{path}")).await;
+            assert!(connection.is_ok(), "{path} was rejected");
+        }
+    }
+}
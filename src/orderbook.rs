@@ -0,0 +1,471 @@
+//! Local order book synchronised from a REST depth snapshot and a diff depth stream.
+//!
+//! This implements Binance's "How to manage a local order book correctly" procedure :
+//! depth events are buffered until a snapshot is available, stale events are dropped using
+//! `first_update_id`/`final_update_id`, and any gap in the update sequence flags the book as
+//! out of sync so that a new snapshot is fetched.
+//!
+//! # Examples
+//! ```rust,no_run
+//! use binance::{api::*, config::*, orderbook::*, websockets::*, ws_model::*};
+//! use binance::market::Market;
+//!
+//! async fn run() -> binance::errors::Result<()> {
+//!     let market: Market = Binance::new_with_env(&Config::default());
+//!     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+//!     let mut ws: WebSockets<'_, WebsocketEvent> = WebSockets::new(move |event| {
+//!         if let WebsocketEvent::DepthOrderBook(depth) = event {
+//!             tx.send(*depth).ok();
+//!         }
+//!         Ok(())
+//!     });
+//!     ws.connect(&diff_book_depth_stream("btcusdt", 100)).await?;
+//!     let mut book = LocalOrderBook::new("BTCUSDT");
+//!     while let Some(depth) = rx.recv().await {
+//!         book.process(depth, &market).await?;
+//!         println!("{:?} / {:?}", book.best_bid(), book.best_ask());
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use futures::future::BoxFuture;
+
+use crate::errors::*;
+use crate::futures::futures_type::FuturesType;
+use crate::futures::market::FuturesMarket;
+use crate::rest_model::{Amount, Asks, Bids, OrderBook};
+use crate::ws_model::DepthOrderBookEvent;
+
+/// Number of levels requested when fetching a snapshot, unless configured otherwise
+pub static DEFAULT_SNAPSHOT_LIMIT: u16 = 1000;
+
+/// Sequencing rules differ between spot and futures diff depth streams
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookKind {
+    /// Every event must start at the previous event's `final_update_id + 1`
+    Spot,
+    /// Every event carries the previous event's `final_update_id` as `previous_final_update_id`
+    Futures,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStatus {
+    /// No snapshot has been applied yet, events are buffered
+    AwaitingSnapshot,
+    /// The book is consistent with the exchange
+    Synced,
+    /// A gap was detected, a new snapshot is required
+    OutOfSync,
+}
+
+/// What happened to a depth event handed to the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    /// Kept until the next snapshot is applied
+    Buffered,
+    /// Older than the current book, dropped
+    Stale,
+    /// Applied to the book
+    Applied,
+    /// The event does not follow the last applied update, the book is now out of sync
+    Gap { expected: u64, received: u64 },
+}
+
+/// Anything able to provide a depth snapshot for a symbol
+pub trait DepthSnapshotProvider {
+    fn depth_snapshot<'a>(&'a self, symbol: &'a str, limit: u16) -> BoxFuture<'a, Result<OrderBook>>;
+}
+
+impl DepthSnapshotProvider for crate::market::Market {
+    fn depth_snapshot<'a>(&'a self, symbol: &'a str, limit: u16) -> BoxFuture<'a, Result<OrderBook>> {
+        Box::pin(self.get_custom_depth(symbol, limit))
+    }
+}
+
+impl DepthSnapshotProvider for crate::spot::market::Market {
+    fn depth_snapshot<'a>(&'a self, symbol: &'a str, limit: u16) -> BoxFuture<'a, Result<OrderBook>> {
+        Box::pin(async move {
+            let depth = self.get_depth(symbol).limit(limit).send().await?;
+            Ok(OrderBook {
+                last_update_id: depth.last_update_id,
+                bids: depth.bids,
+                asks: depth.asks,
+            })
+        })
+    }
+}
+
+impl<T: FuturesType + Sync> DepthSnapshotProvider for FuturesMarket<T> {
+    fn depth_snapshot<'a>(&'a self, symbol: &'a str, limit: u16) -> BoxFuture<'a, Result<OrderBook>> {
+        Box::pin(async move {
+            let depth = self.get_custom_depth(symbol, limit).await?;
+            Ok(OrderBook {
+                last_update_id: depth.last_update_id,
+                bids: depth.bids,
+                asks: depth.asks,
+            })
+        })
+    }
+}
+
+/// Price used as an ordered map key
+#[derive(Debug, Clone, Copy)]
+struct Price(Amount);
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering { compare(&self.0, &other.0) }
+}
+
+#[cfg(not(feature = "decimal"))]
+fn compare(a: &Amount, b: &Amount) -> Ordering { a.total_cmp(b) }
+
+#[cfg(feature = "decimal")]
+fn compare(a: &Amount, b: &Amount) -> Ordering { a.cmp(b) }
+
+/// An order book kept in sync with the exchange
+#[derive(Debug, Clone)]
+pub struct LocalOrderBook {
+    symbol: String,
+    kind: BookKind,
+    snapshot_limit: u16,
+    status: SyncStatus,
+    last_update_id: u64,
+    /// True until the first event following a snapshot has been applied
+    first_event_pending: bool,
+    bids: BTreeMap<Price, Amount>,
+    asks: BTreeMap<Price, Amount>,
+    buffer: Vec<DepthOrderBookEvent>,
+}
+
+impl LocalOrderBook {
+    /// New spot order book
+    pub fn new<S: Into<String>>(symbol: S) -> Self { Self::with_kind(symbol, BookKind::Spot) }
+
+    /// New futures (USD-M or COIN-M) order book
+    pub fn new_futures<S: Into<String>>(symbol: S) -> Self { Self::with_kind(symbol, BookKind::Futures) }
+
+    pub fn with_kind<S: Into<String>>(symbol: S, kind: BookKind) -> Self {
+        Self {
+            symbol: symbol.into().to_uppercase(),
+            kind,
+            snapshot_limit: DEFAULT_SNAPSHOT_LIMIT,
+            status: SyncStatus::AwaitingSnapshot,
+            last_update_id: 0,
+            first_event_pending: true,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            buffer: vec![],
+        }
+    }
+
+    /// Sets the number of levels requested when fetching snapshots
+    pub fn snapshot_limit(mut self, limit: u16) -> Self {
+        self.snapshot_limit = limit;
+        self
+    }
+
+    pub fn symbol(&self) -> &str { &self.symbol }
+
+    pub fn kind(&self) -> BookKind { self.kind }
+
+    pub fn status(&self) -> SyncStatus { self.status }
+
+    pub fn is_synced(&self) -> bool { self.status == SyncStatus::Synced }
+
+    /// Id of the last update applied to the book
+    pub fn last_update_id(&self) -> u64 { self.last_update_id }
+
+    /// Replaces the book with a REST snapshot, then replays buffered events on top of it
+    pub fn apply_snapshot(&mut self, snapshot: OrderBook) -> SyncStatus {
+        self.bids = snapshot
+            .bids
+            .into_iter()
+            .filter(|b| b.qty > Amount::default())
+            .map(|b| (Price(b.price), b.qty))
+            .collect();
+        self.asks = snapshot
+            .asks
+            .into_iter()
+            .filter(|a| a.qty > Amount::default())
+            .map(|a| (Price(a.price), a.qty))
+            .collect();
+        self.last_update_id = snapshot.last_update_id;
+        self.first_event_pending = true;
+        self.status = SyncStatus::Synced;
+
+        let mut buffered = std::mem::take(&mut self.buffer).into_iter();
+        for event in buffered.by_ref() {
+            if let UpdateOutcome::Gap { .. } = self.apply(event) {
+                break;
+            }
+        }
+        // Events following a gap are kept for the next snapshot
+        self.buffer.extend(buffered);
+        self.status
+    }
+
+    /// Handles a diff depth event, buffering it while no consistent snapshot is available
+    pub fn update(&mut self, event: DepthOrderBookEvent) -> UpdateOutcome {
+        match self.status {
+            SyncStatus::Synced => self.apply(event),
+            SyncStatus::AwaitingSnapshot | SyncStatus::OutOfSync => {
+                self.buffer.push(event);
+                UpdateOutcome::Buffered
+            }
+        }
+    }
+
+    /// Handles a diff depth event and fetches a new snapshot from `provider` if the book is not in sync
+    pub async fn process<P>(&mut self, event: DepthOrderBookEvent, provider: &P) -> Result<UpdateOutcome>
+    where
+        P: DepthSnapshotProvider + ?Sized,
+    {
+        let outcome = self.update(event);
+        if !self.is_synced() {
+            self.resync(provider).await?;
+        }
+        Ok(outcome)
+    }
+
+    /// Fetches a new snapshot from `provider` and applies it
+    pub async fn resync<P>(&mut self, provider: &P) -> Result<SyncStatus>
+    where
+        P: DepthSnapshotProvider + ?Sized,
+    {
+        let snapshot = provider.depth_snapshot(&self.symbol, self.snapshot_limit).await?;
+        Ok(self.apply_snapshot(snapshot))
+    }
+
+    fn apply(&mut self, event: DepthOrderBookEvent) -> UpdateOutcome {
+        let is_stale = match self.kind {
+            BookKind::Spot => event.final_update_id <= self.last_update_id,
+            BookKind::Futures => event.final_update_id < self.last_update_id,
+        };
+        if is_stale {
+            return UpdateOutcome::Stale;
+        }
+
+        let in_sequence = match (self.kind, self.first_event_pending) {
+            (BookKind::Spot, true) => event.first_update_id <= self.last_update_id + 1,
+            (BookKind::Spot, false) => event.first_update_id == self.last_update_id + 1,
+            (BookKind::Futures, true) => event.first_update_id <= self.last_update_id,
+            (BookKind::Futures, false) => event.previous_final_update_id == Some(self.last_update_id),
+        };
+        if !in_sequence {
+            let (expected, received) = match self.kind {
+                BookKind::Spot => (self.last_update_id + 1, event.first_update_id),
+                BookKind::Futures => (
+                    self.last_update_id,
+                    event.previous_final_update_id.unwrap_or(event.first_update_id),
+                ),
+            };
+            self.status = SyncStatus::OutOfSync;
+            self.buffer.push(event);
+            return UpdateOutcome::Gap { expected, received };
+        }
+
+        for bid in event.bids {
+            Self::set_level(&mut self.bids, bid.price, bid.qty);
+        }
+        for ask in event.asks {
+            Self::set_level(&mut self.asks, ask.price, ask.qty);
+        }
+        self.last_update_id = event.final_update_id;
+        self.first_event_pending = false;
+        UpdateOutcome::Applied
+    }
+
+    fn set_level(side: &mut BTreeMap<Price, Amount>, price: Amount, qty: Amount) {
+        if qty == Amount::default() {
+            side.remove(&Price(price));
+        } else {
+            side.insert(Price(price), qty);
+        }
+    }
+
+    /// Highest bid
+    pub fn best_bid(&self) -> Option<Bids> {
+        self.bids
+            .iter()
+            .next_back()
+            .map(|(price, qty)| Bids { price: price.0, qty: *qty })
+    }
+
+    /// Lowest ask
+    pub fn best_ask(&self) -> Option<Asks> {
+        self.asks
+            .iter()
+            .next()
+            .map(|(price, qty)| Asks { price: price.0, qty: *qty })
+    }
+
+    /// Best `depth` bids, from the highest price down
+    pub fn bids(&self, depth: usize) -> Vec<Bids> {
+        self.bids
+            .iter()
+            .rev()
+            .take(depth)
+            .map(|(price, qty)| Bids { price: price.0, qty: *qty })
+            .collect()
+    }
+
+    /// Best `depth` asks, from the lowest price up
+    pub fn asks(&self, depth: usize) -> Vec<Asks> {
+        self.asks
+            .iter()
+            .take(depth)
+            .map(|(price, qty)| Asks { price: price.0, qty: *qty })
+            .collect()
+    }
+
+    /// Copy of the book limited to `depth` levels per side, all levels if `None`
+    pub fn snapshot(&self, depth: Option<usize>) -> OrderBook {
+        let depth = depth.unwrap_or(usize::MAX);
+        OrderBook {
+            last_update_id: self.last_update_id,
+            bids: self.bids(depth),
+            asks: self.asks(depth),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::amount;
+
+    fn level(price: &str, qty: &str) -> (Amount, Amount) { (amount(price), amount(qty)) }
+
+    fn snapshot(last_update_id: u64, bids: &[(Amount, Amount)], asks: &[(Amount, Amount)]) -> OrderBook {
+        OrderBook {
+            last_update_id,
+            bids: bids.iter().map(|&(price, qty)| Bids { price, qty }).collect(),
+            asks: asks.iter().map(|&(price, qty)| Asks { price, qty }).collect(),
+        }
+    }
+
+    fn event(
+        first: u64,
+        last: u64,
+        previous: Option<u64>,
+        bids: &[(Amount, Amount)],
+        asks: &[(Amount, Amount)],
+    ) -> DepthOrderBookEvent {
+        DepthOrderBookEvent {
+            event_time: 0,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: first,
+            final_update_id: last,
+            previous_final_update_id: previous,
+            bids: bids.iter().map(|&(price, qty)| Bids { price, qty }).collect(),
+            asks: asks.iter().map(|&(price, qty)| Asks { price, qty }).collect(),
+        }
+    }
+
+    #[test]
+    fn buffers_until_snapshot_and_drops_stale_events() {
+        let mut book = LocalOrderBook::new("btcusdt");
+        assert_eq!(
+            book.update(event(95, 99, None, &[level("10.0", "1.0")], &[])),
+            UpdateOutcome::Buffered
+        );
+        assert_eq!(
+            book.update(event(100, 104, None, &[level("11.0", "2.0")], &[])),
+            UpdateOutcome::Buffered
+        );
+        assert_eq!(
+            book.update(event(105, 106, None, &[], &[level("13.0", "0.0")])),
+            UpdateOutcome::Buffered
+        );
+
+        let status = book.apply_snapshot(snapshot(
+            101,
+            &[level("10.0", "5.0")],
+            &[level("12.0", "1.0"), level("13.0", "3.0")],
+        ));
+        assert_eq!(status, SyncStatus::Synced);
+        assert_eq!(book.last_update_id(), 106);
+        assert_eq!(book.best_bid().unwrap().price, amount("11.0"));
+        assert_eq!(book.bids(5).len(), 2);
+        assert_eq!(book.asks(5).len(), 1);
+        assert_eq!(book.best_ask().unwrap().price, amount("12.0"));
+    }
+
+    #[test]
+    fn detects_spot_gaps() {
+        let mut book = LocalOrderBook::new("BTCUSDT");
+        book.apply_snapshot(snapshot(10, &[level("1.0", "1.0")], &[level("2.0", "1.0")]));
+        assert_eq!(book.update(event(9, 12, None, &[], &[])), UpdateOutcome::Applied);
+        assert_eq!(book.update(event(13, 14, None, &[], &[])), UpdateOutcome::Applied);
+        assert_eq!(
+            book.update(event(16, 18, None, &[], &[])),
+            UpdateOutcome::Gap {
+                expected: 15,
+                received: 16
+            }
+        );
+        assert_eq!(book.status(), SyncStatus::OutOfSync);
+        assert_eq!(book.update(event(19, 20, None, &[], &[])), UpdateOutcome::Buffered);
+
+        assert_eq!(book.apply_snapshot(snapshot(17, &[], &[])), SyncStatus::Synced);
+        assert_eq!(book.last_update_id(), 20);
+    }
+
+    #[test]
+    fn follows_futures_sequencing() {
+        let mut book = LocalOrderBook::new_futures("BTCUSDT");
+        book.apply_snapshot(snapshot(100, &[level("1.0", "1.0")], &[level("2.0", "1.0")]));
+        assert_eq!(book.update(event(90, 99, Some(89), &[], &[])), UpdateOutcome::Stale);
+        assert_eq!(
+            book.update(event(98, 105, Some(97), &[level("1.5", "2.0")], &[])),
+            UpdateOutcome::Applied
+        );
+        assert_eq!(
+            book.update(event(106, 110, Some(105), &[level("1.0", "0.0")], &[])),
+            UpdateOutcome::Applied
+        );
+        assert_eq!(book.bids(10).len(), 1);
+        assert_eq!(
+            book.update(event(112, 115, Some(111), &[], &[])),
+            UpdateOutcome::Gap {
+                expected: 110,
+                received: 111
+            }
+        );
+        assert!(!book.is_synced());
+    }
+
+    #[test]
+    fn snapshot_view_is_sorted() {
+        let mut book = LocalOrderBook::new("BTCUSDT");
+        book.apply_snapshot(snapshot(
+            1,
+            &[level("1.0", "1.0"), level("3.0", "1.0"), level("2.0", "1.0")],
+            &[level("6.0", "1.0"), level("4.0", "1.0"), level("5.0", "1.0")],
+        ));
+        let view = book.snapshot(Some(2));
+        assert_eq!(
+            view.bids.iter().map(|b| b.price).collect::<Vec<_>>(),
+            vec![amount("3"), amount("2")]
+        );
+        assert_eq!(
+            view.asks.iter().map(|a| a.price).collect::<Vec<_>>(),
+            vec![amount("4"), amount("5")]
+        );
+        assert_eq!(book.snapshot(None).bids.len(), 3);
+    }
+}
@@ -0,0 +1,681 @@
+//! Client side rate limiting of REST requests
+//!
+//! A [`RateLimiter`] counts the weight of every request, and the number of orders placed,
+//! in the same fixed windows Binance uses. Requests which would exceed a limit are either
+//! delayed until the window resets or rejected with [`Error::RateLimitReached`].
+//!
+//! Limits are read from the `rate_limits` of the exchange information, which the limiter loads
+//! automatically when `exchange_info` is called through a client using it, and usage is reconciled
+//! with the `X-MBX-USED-WEIGHT-*` and `X-MBX-ORDER-COUNT-*` response headers. After a 429 or a 418
+//! response, every request is held back until its `Retry-After` elapsed.
+//!
+//! ```rust,no_run
+//! use binance::{api::*, config::*, general::*, rate_limit::*};
+//! # tokio_test::block_on(async {
+//! let limiter = RateLimiter::new(RateLimitMode::Delay);
+//! let conf = Config::default().set_rate_limiter(limiter.clone());
+//! let general: General = Binance::new_with_config(None, None, &conf);
+//! // loads the limits
+//! general.exchange_info().await.unwrap();
+//! # });
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+
+use crate::errors::*;
+use crate::rest_model::{RateLimit, RateLimitInterval, RateLimitType};
+
+/// Default weight of endpoints missing from the weight table
+pub static DEFAULT_WEIGHT: u32 = 1;
+
+/// Behaviour of the limiter when a request would exceed a limit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Wait until the window resets
+    Delay,
+    /// Fail with `Error::RateLimitReached`
+    Reject,
+}
+
+/// Fixed window counter
+#[derive(Debug, Clone)]
+struct Window {
+    rate_limit_type: RateLimitType,
+    interval: Duration,
+    /// Suffix of the usage headers, e.g. `1m` or `10s`
+    header_suffix: String,
+    limit: u32,
+    /// Index of the current window since the epoch
+    index: u64,
+    used: u32,
+}
+
+impl Window {
+    fn new(rate_limit: &RateLimit) -> Self {
+        let (seconds, unit) = match rate_limit.interval {
+            RateLimitInterval::Second => (1, 's'),
+            RateLimitInterval::Minute => (60, 'm'),
+            RateLimitInterval::Day => (86_400, 'd'),
+        };
+        let interval_num = rate_limit.interval_num.max(1) as u64;
+        Self {
+            rate_limit_type: rate_limit.rate_limit_type.clone(),
+            interval: Duration::from_secs(seconds * interval_num),
+            header_suffix: format!("{interval_num}{unit}"),
+            limit: rate_limit.limit.max(0) as u32,
+            index: 0,
+            used: 0,
+        }
+    }
+
+    /// Resets the counter if the window elapsed
+    fn roll(&mut self, now: Duration) {
+        let index = (now.as_millis() / self.interval.as_millis()) as u64;
+        if index != self.index {
+            self.index = index;
+            self.used = 0;
+        }
+    }
+
+    fn remaining(&self, now: Duration) -> Duration {
+        let interval = self.interval.as_millis();
+        Duration::from_millis((interval - now.as_millis() % interval) as u64)
+    }
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    windows: Vec<Window>,
+    weights: HashMap<String, u32>,
+    /// Time since the epoch until which requests are held back, after a 429 or a 418
+    blocked_until: Duration,
+}
+
+/// Shared rate limiter, clones use the same counters
+#[derive(Clone)]
+pub struct RateLimiter {
+    mode: RateLimitMode,
+    state: Arc<Mutex<LimiterState>>,
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("RateLimiter")
+            .field("mode", &self.mode)
+            .field("windows", &state.windows)
+            .finish()
+    }
+}
+
+impl PartialEq for RateLimiter {
+    fn eq(&self, other: &Self) -> bool { Arc::ptr_eq(&self.state, &other.state) }
+}
+
+impl Eq for RateLimiter {}
+
+impl RateLimiter {
+    /// New limiter without limits until they are loaded from the exchange information
+    pub fn new(mode: RateLimitMode) -> Self {
+        let weights = endpoint_weights()
+            .iter()
+            .map(|(endpoint, weight)| (endpoint.to_string(), *weight))
+            .collect();
+        Self {
+            mode,
+            state: Arc::new(Mutex::new(LimiterState {
+                windows: vec![],
+                weights,
+                blocked_until: Duration::ZERO,
+            })),
+        }
+    }
+
+    /// New limiter with the given limits
+    pub fn with_limits(mode: RateLimitMode, rate_limits: &[RateLimit]) -> Self {
+        let limiter = Self::new(mode);
+        limiter.set_limits(rate_limits);
+        limiter
+    }
+
+    pub fn mode(&self) -> RateLimitMode { self.mode }
+
+    /// Replaces the limits, keeping the usage of the windows which still exist
+    pub fn set_limits(&self, rate_limits: &[RateLimit]) {
+        let mut state = self.state.lock().unwrap();
+        let previous = std::mem::take(&mut state.windows);
+        state.windows = rate_limits
+            .iter()
+            .filter(|rate_limit| rate_limit.rate_limit_type != RateLimitType::Other)
+            .map(|rate_limit| {
+                let mut window = Window::new(rate_limit);
+                if let Some(old) = previous
+                    .iter()
+                    .find(|w| w.rate_limit_type == window.rate_limit_type && w.interval == window.interval)
+                {
+                    window.index = old.index;
+                    window.used = old.used;
+                }
+                window
+            })
+            .collect();
+    }
+
+    /// Overrides the weight of an endpoint, e.g. `/api/v3/account`
+    pub fn set_weight(&self, endpoint: &str, weight: u32) {
+        self.state.lock().unwrap().weights.insert(endpoint.to_string(), weight);
+    }
+
+    /// Weight of a `method` request to `endpoint` with the given query string
+    pub fn weight(&self, method: &Method, endpoint: &str, query: &str) -> u32 {
+        if let Some(weight) = order_weight(method, endpoint)
+            .or_else(|| depth_weight(endpoint, query))
+            .or_else(|| symbols_weight(endpoint, query))
+        {
+            return weight;
+        }
+        self.state
+            .lock()
+            .unwrap()
+            .weights
+            .get(endpoint)
+            .copied()
+            .unwrap_or(DEFAULT_WEIGHT)
+    }
+
+    /// Usage of the current window of each limit, as `(type, interval, used, limit)`
+    pub fn usage(&self) -> Vec<(RateLimitType, Duration, u32, u32)> {
+        let now = now();
+        let mut state = self.state.lock().unwrap();
+        state
+            .windows
+            .iter_mut()
+            .map(|w| {
+                w.roll(now);
+                (w.rate_limit_type.clone(), w.interval, w.used, w.limit)
+            })
+            .collect()
+    }
+
+    /// Reserves the weight of a request, waiting or failing depending on the mode if a limit would be exceeded
+    pub async fn acquire(&self, method: &Method, endpoint: &str, query: &str) -> Result<()> {
+        let is_order = is_order_request(method, endpoint);
+        let weight = self.weight(method, endpoint, query);
+        loop {
+            let wait = self.try_acquire(weight, is_order);
+            match (wait, self.mode) {
+                (None, _) => return Ok(()),
+                (Some((rate_limit_type, retry_after)), RateLimitMode::Reject) => {
+                    return Err(Error::RateLimitReached {
+                        rate_limit_type,
+                        retry_after,
+                    });
+                }
+                (Some((_, retry_after)), RateLimitMode::Delay) => tokio::time::sleep(retry_after).await,
+            }
+        }
+    }
+
+    /// Holds every request back for `retry_after`, e.g. the `Retry-After` of a 429 or a 418 response
+    pub fn block_for(&self, retry_after: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.blocked_until = state.blocked_until.max(now() + retry_after);
+    }
+
+    /// Reserves the request if all limits allow it, otherwise returns the exceeded limit and the time to wait
+    fn try_acquire(&self, weight: u32, is_order: bool) -> Option<(RateLimitType, Duration)> {
+        let now = now();
+        let mut state = self.state.lock().unwrap();
+        if state.blocked_until > now {
+            return Some((RateLimitType::RequestWeight, state.blocked_until - now));
+        }
+        let cost = |w: &Window| match w.rate_limit_type {
+            RateLimitType::RequestWeight => weight,
+            RateLimitType::Orders if is_order => 1,
+            RateLimitType::Orders => 0,
+            RateLimitType::RawRequests => 1,
+            RateLimitType::Other => 0,
+        };
+        let mut exceeded: Option<(RateLimitType, Duration)> = None;
+        for window in state.windows.iter_mut() {
+            window.roll(now);
+            let cost = cost(window);
+            // a request heavier than the limit is let through on a fresh window
+            if cost > 0 && window.used > 0 && window.used + cost > window.limit {
+                let remaining = window.remaining(now);
+                if exceeded.as_ref().is_none_or(|(_, wait)| remaining > *wait) {
+                    exceeded = Some((window.rate_limit_type.clone(), remaining));
+                }
+            }
+        }
+        if exceeded.is_none() {
+            for window in state.windows.iter_mut() {
+                window.used += cost(window);
+            }
+        }
+        exceeded
+    }
+
+    /// Reconciles the local usage with the usage reported by the server
+    pub fn update_from_headers(&self, headers: &HeaderMap) {
+        let now = now();
+        let mut state = self.state.lock().unwrap();
+        for (name, value) in headers {
+            let name = name.as_str();
+            let (rate_limit_type, suffix) = if let Some(suffix) = name.strip_prefix("x-mbx-used-weight-") {
+                (RateLimitType::RequestWeight, suffix)
+            } else if let Some(suffix) = name.strip_prefix("x-mbx-order-count-") {
+                (RateLimitType::Orders, suffix)
+            } else {
+                continue;
+            };
+            let Some(used) = value.to_str().ok().and_then(|v| v.parse::<u32>().ok()) else {
+                continue;
+            };
+            for window in state
+                .windows
+                .iter_mut()
+                .filter(|w| w.rate_limit_type == rate_limit_type && w.header_suffix.eq_ignore_ascii_case(suffix))
+            {
+                window.roll(now);
+                window.used = window.used.max(used);
+            }
+        }
+    }
+}
+
+/// Retries of GET requests rejected with a 429 or a 418, honouring their `Retry-After`
+///
+/// Signed requests are signed again with a new timestamp before being retried
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after which the error is returned
+    pub max_retries: u32,
+    /// Delay used when the response has no `Retry-After`
+    pub default_delay: Duration,
+    /// Longest delay waited for, the error is returned if `Retry-After` asks for more
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            default_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn set_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn set_default_delay(mut self, default_delay: Duration) -> Self {
+        self.default_delay = default_delay;
+        self
+    }
+
+    pub fn set_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Delay before retrying a request which failed with `error`, `None` if it must not be retried
+    pub(crate) fn delay(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        if attempt >= self.max_retries || !matches!(error, Error::TooManyRequests(_) | Error::IpBanned(_)) {
+            return None;
+        }
+        let delay = error.retry_after().unwrap_or(self.default_delay);
+        (delay <= self.max_delay).then_some(delay)
+    }
+}
+
+fn now() -> Duration { SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default() }
+
+/// Requests counted in the ORDERS limits
+fn is_order_request(method: &Method, endpoint: &str) -> bool {
+    match *method {
+        Method::POST => matches!(
+            endpoint,
+            "/api/v3/order"
+                | "/api/v3/order/oco"
+                | "/api/v3/orderList/oco"
+                | "/api/v3/orderList/oto"
+                | "/api/v3/orderList/otoco"
+                | "/api/v3/order/cancelReplace"
+                | "/sapi/v1/margin/order"
+                | "/sapi/v1/margin/order/oco"
+                | "/fapi/v1/order"
+                | "/fapi/v1/batchOrders"
+                | "/dapi/v1/order"
+                | "/dapi/v1/batchOrders"
+        ),
+        // order modifications
+        Method::PUT => matches!(
+            endpoint,
+            "/fapi/v1/order" | "/fapi/v1/batchOrders" | "/dapi/v1/order" | "/dapi/v1/batchOrders"
+        ),
+        _ => false,
+    }
+}
+
+/// Placing, modifying or cancelling orders weighs 1, the table weights of these endpoints are those of the queries
+fn order_weight(method: &Method, endpoint: &str) -> Option<u32> {
+    match (method, endpoint) {
+        (&Method::POST | &Method::DELETE, "/api/v3/order" | "/api/v3/orderList") => Some(DEFAULT_WEIGHT),
+        (&Method::PUT, "/fapi/v1/order" | "/dapi/v1/order") => Some(DEFAULT_WEIGHT),
+        (&Method::DELETE, "/api/v3/openOrders" | "/fapi/v1/batchOrders" | "/dapi/v1/batchOrders") => {
+            Some(DEFAULT_WEIGHT)
+        }
+        _ => None,
+    }
+}
+
+/// Weights of endpoints queried for all symbols when no `symbol` is given, the table weights being those of one symbol
+fn symbols_weight(endpoint: &str, query: &str) -> Option<u32> {
+    let param = |name: &str| query.split('&').find_map(|kv| kv.strip_prefix(name));
+    if param("symbol=").is_some() {
+        return None;
+    }
+    // `symbols` is a JSON array, whose separators may be url encoded
+    let symbols = param("symbols=").map(|symbols| symbols.matches(',').count() + symbols.matches("%2C").count() + 1);
+    match endpoint {
+        "/api/v3/ticker/24hr" => Some(match symbols {
+            Some(1..=20) => 2,
+            Some(21..=100) => 40,
+            _ => 80,
+        }),
+        "/api/v3/ticker" => symbols.map(|symbols| (4 * symbols as u32).min(200)),
+        "/api/v3/ticker/price" | "/api/v3/ticker/bookTicker" => Some(4),
+        "/api/v3/openOrders" => Some(80),
+        "/fapi/v1/openOrders" | "/fapi/v1/ticker/24hr" | "/dapi/v1/openOrders" | "/dapi/v1/ticker/24hr" => Some(40),
+        "/fapi/v1/ticker/price" | "/dapi/v1/ticker/price" => Some(2),
+        "/fapi/v1/ticker/bookTicker" | "/dapi/v1/ticker/bookTicker" => Some(5),
+        _ => None,
+    }
+}
+
+/// Order book weights depend on the requested limit
+fn depth_weight(endpoint: &str, query: &str) -> Option<u32> {
+    let limit = || -> u32 {
+        query
+            .split('&')
+            .find_map(|kv| kv.strip_prefix("limit="))
+            .and_then(|limit| limit.parse().ok())
+            .unwrap_or(100)
+    };
+    match endpoint {
+        "/api/v3/depth" => Some(match limit() {
+            0..=100 => 5,
+            101..=500 => 25,
+            501..=1000 => 50,
+            _ => 250,
+        }),
+        "/fapi/v1/depth" | "/dapi/v1/depth" => Some(match limit() {
+            0..=50 => 2,
+            51..=100 => 5,
+            101..=500 => 10,
+            _ => 20,
+        }),
+        _ => None,
+    }
+}
+
+/// Known endpoint weights, as documented by Binance
+fn endpoint_weights() -> &'static [(&'static str, u32)] {
+    &[
+        ("/api/v3/exchangeInfo", 20),
+        ("/api/v3/trades", 25),
+        ("/api/v3/historicalTrades", 25),
+        ("/api/v3/aggTrades", 2),
+        ("/api/v3/klines", 2),
+        ("/api/v3/uiKlines", 2),
+        ("/api/v3/avgPrice", 2),
+        ("/api/v3/ticker/24hr", 2),
+        ("/api/v3/ticker/price", 2),
+        ("/api/v3/ticker/bookTicker", 2),
+        ("/api/v3/ticker", 4),
+        ("/api/v3/account", 20),
+        ("/api/v3/myTrades", 20),
+        ("/api/v3/openOrders", 6),
+        ("/api/v3/allOrders", 20),
+        ("/api/v3/order", 4),
+        ("/api/v3/order/oco", 1),
+        ("/api/v3/orderList", 4),
+        ("/api/v3/allOrderList", 20),
+        ("/api/v3/openOrderList", 6),
+        ("/api/v3/rateLimit/order", 40),
+        ("/api/v3/userDataStream", 2),
+        ("/fapi/v1/exchangeInfo", 1),
+        ("/fapi/v1/trades", 5),
+        ("/fapi/v1/historicalTrades", 20),
+        ("/fapi/v1/aggTrades", 20),
+        ("/fapi/v1/klines", 5),
+        ("/fapi/v1/continuousKlines", 5),
+        ("/fapi/v1/indexPriceKlines", 5),
+        ("/fapi/v1/markPriceKlines", 5),
+        ("/fapi/v1/ticker/24hr", 1),
+        ("/fapi/v1/ticker/price", 1),
+        ("/fapi/v1/ticker/bookTicker", 2),
+        ("/fapi/v1/allOrders", 5),
+        ("/fapi/v1/openOrders", 1),
+        ("/fapi/v1/userTrades", 5),
+        ("/fapi/v1/income", 30),
+        ("/fapi/v1/income/asyn", 1000),
+        ("/fapi/v1/income/asyn/id", 10),
+        ("/fapi/v1/batchOrders", 5),
+        ("/fapi/v2/account", 5),
+        ("/fapi/v2/balance", 5),
+        ("/fapi/v2/positionRisk", 5),
+        ("/dapi/v1/exchangeInfo", 1),
+        ("/dapi/v1/trades", 5),
+        ("/dapi/v1/historicalTrades", 20),
+        ("/dapi/v1/aggTrades", 20),
+        ("/dapi/v1/klines", 5),
+        ("/dapi/v1/ticker/24hr", 1),
+        ("/dapi/v1/ticker/price", 1),
+        ("/dapi/v1/ticker/bookTicker", 2),
+        ("/dapi/v1/allOrders", 20),
+        ("/dapi/v1/openOrders", 1),
+        ("/dapi/v1/userTrades", 20),
+        ("/dapi/v1/income", 20),
+        ("/dapi/v1/account", 5),
+        ("/dapi/v1/balance", 1),
+        ("/dapi/v1/positionRisk", 1),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    use super::*;
+
+    fn limit(rate_limit_type: RateLimitType, interval: RateLimitInterval, interval_num: i32, limit: i32) -> RateLimit {
+        RateLimit {
+            interval,
+            rate_limit_type,
+            interval_num,
+            limit,
+        }
+    }
+
+    #[test]
+    fn endpoint_weights() {
+        let limiter = RateLimiter::new(RateLimitMode::Reject);
+        assert_eq!(limiter.weight(&Method::GET, "/api/v3/account", ""), 20);
+        assert_eq!(
+            limiter.weight(&Method::GET, "/api/v3/depth", "symbol=BTCUSDT&limit=1000"),
+            50
+        );
+        assert_eq!(
+            limiter.weight(&Method::GET, "/fapi/v1/depth", "symbol=BTCUSDT&limit=5"),
+            2
+        );
+        assert_eq!(limiter.weight(&Method::GET, "/api/v3/unknown", ""), DEFAULT_WEIGHT);
+        limiter.set_weight("/api/v3/unknown", 3);
+        assert_eq!(limiter.weight(&Method::GET, "/api/v3/unknown", ""), 3);
+    }
+
+    #[test]
+    fn weights_depend_on_the_symbol() {
+        let limiter = RateLimiter::new(RateLimitMode::Reject);
+        assert_eq!(limiter.weight(&Method::GET, "/api/v3/openOrders", "symbol=BTCUSDT"), 6);
+        assert_eq!(limiter.weight(&Method::GET, "/api/v3/openOrders", ""), 80);
+        assert_eq!(limiter.weight(&Method::GET, "/api/v3/ticker/24hr", "symbol=BTCUSDT"), 2);
+        assert_eq!(
+            limiter.weight(
+                &Method::GET,
+                "/api/v3/ticker/24hr",
+                "symbols=%5B%22BTCUSDT%22%2C%22ETHUSDT%22%5D"
+            ),
+            2
+        );
+        assert_eq!(limiter.weight(&Method::GET, "/api/v3/ticker/24hr", ""), 80);
+        assert_eq!(
+            limiter.weight(&Method::GET, "/api/v3/ticker/price", "symbol=BTCUSDT"),
+            2
+        );
+        assert_eq!(limiter.weight(&Method::GET, "/api/v3/ticker/price", ""), 4);
+        assert_eq!(
+            limiter.weight(&Method::GET, "/api/v3/ticker", "symbols=[\"BTCUSDT\",\"ETHUSDT\"]"),
+            8
+        );
+        assert_eq!(limiter.weight(&Method::GET, "/fapi/v1/openOrders", "symbol=BTCUSDT"), 1);
+        assert_eq!(limiter.weight(&Method::GET, "/fapi/v1/openOrders", ""), 40);
+        assert_eq!(
+            limiter.weight(&Method::GET, "/dapi/v1/ticker/24hr", "symbol=BTCUSD_PERP"),
+            1
+        );
+        assert_eq!(limiter.weight(&Method::GET, "/dapi/v1/ticker/24hr", ""), 40);
+    }
+
+    #[test]
+    fn weights_depend_on_the_method() {
+        let limiter = RateLimiter::new(RateLimitMode::Reject);
+        assert_eq!(
+            limiter.weight(&Method::GET, "/api/v3/order", "symbol=BTCUSDT&orderId=1"),
+            4
+        );
+        assert_eq!(
+            limiter.weight(&Method::POST, "/api/v3/order", "symbol=BTCUSDT"),
+            DEFAULT_WEIGHT
+        );
+        assert_eq!(
+            limiter.weight(&Method::DELETE, "/api/v3/order", "symbol=BTCUSDT&orderId=1"),
+            DEFAULT_WEIGHT
+        );
+        assert_eq!(
+            limiter.weight(&Method::DELETE, "/api/v3/openOrders", ""),
+            DEFAULT_WEIGHT
+        );
+        assert_eq!(limiter.weight(&Method::PUT, "/fapi/v1/order", ""), DEFAULT_WEIGHT);
+        assert_eq!(limiter.weight(&Method::POST, "/fapi/v1/batchOrders", ""), 5);
+        assert_eq!(
+            limiter.weight(&Method::DELETE, "/fapi/v1/batchOrders", ""),
+            DEFAULT_WEIGHT
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_over_the_limit() {
+        let limiter = RateLimiter::with_limits(
+            RateLimitMode::Reject,
+            &[
+                limit(RateLimitType::RequestWeight, RateLimitInterval::Day, 1, 30),
+                limit(RateLimitType::Orders, RateLimitInterval::Day, 1, 1),
+            ],
+        );
+        limiter.acquire(&Method::GET, "/api/v3/account", "").await.unwrap();
+        assert!(matches!(
+            limiter.acquire(&Method::GET, "/api/v3/account", "").await,
+            Err(Error::RateLimitReached {
+                rate_limit_type: RateLimitType::RequestWeight,
+                ..
+            })
+        ));
+        // a rejected request is not counted
+        limiter.acquire(&Method::POST, "/api/v3/order", "").await.unwrap();
+        assert!(matches!(
+            limiter.acquire(&Method::POST, "/api/v3/order", "").await,
+            Err(Error::RateLimitReached {
+                rate_limit_type: RateLimitType::Orders,
+                ..
+            })
+        ));
+        let usage = limiter.usage();
+        assert_eq!(usage[0].2, 21);
+        assert_eq!(usage[1].2, 1);
+    }
+
+    #[tokio::test]
+    async fn order_modifications_are_counted_as_orders() {
+        let limiter = RateLimiter::with_limits(RateLimitMode::Reject, &[limit(
+            RateLimitType::Orders,
+            RateLimitInterval::Second,
+            10,
+            1,
+        )]);
+        limiter.acquire(&Method::PUT, "/fapi/v1/order", "").await.unwrap();
+        assert!(matches!(
+            limiter.acquire(&Method::PUT, "/fapi/v1/batchOrders", "").await,
+            Err(Error::RateLimitReached {
+                rate_limit_type: RateLimitType::Orders,
+                ..
+            })
+        ));
+        // queries are not orders
+        limiter.acquire(&Method::GET, "/fapi/v1/order", "").await.unwrap();
+    }
+
+    #[test]
+    fn reconciles_with_response_headers() {
+        let limiter = RateLimiter::with_limits(
+            RateLimitMode::Delay,
+            &[
+                limit(RateLimitType::RequestWeight, RateLimitInterval::Minute, 1, 6000),
+                limit(RateLimitType::Orders, RateLimitInterval::Second, 10, 100),
+            ],
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("x-mbx-used-weight-1m"),
+            HeaderValue::from_static("1234"),
+        );
+        headers.insert(
+            HeaderName::from_static("x-mbx-order-count-10s"),
+            HeaderValue::from_static("7"),
+        );
+        headers.insert(
+            HeaderName::from_static("x-mbx-order-count-1d"),
+            HeaderValue::from_static("700"),
+        );
+        limiter.update_from_headers(&headers);
+        let usage = limiter.usage();
+        assert_eq!(usage[0].2, 1234);
+        assert_eq!(usage[1].2, 7);
+    }
+
+    #[tokio::test]
+    async fn delays_until_the_window_resets() {
+        let limiter = RateLimiter::with_limits(
+            RateLimitMode::Delay,
+            &[limit(RateLimitType::RawRequests, RateLimitInterval::Second, 1, 1)],
+        );
+        let started = std::time::Instant::now();
+        limiter.acquire(&Method::GET, "/api/v3/time", "").await.unwrap();
+        // waits for the next second
+        limiter.acquire(&Method::GET, "/api/v3/time", "").await.unwrap();
+        assert_eq!(limiter.usage()[0].2, 1);
+        assert!(started.elapsed() <= Duration::from_millis(1100));
+    }
+}
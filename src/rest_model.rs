@@ -1,6 +1,20 @@
 use chrono::{DateTime, Utc};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::Error;
+use crate::filters::OrderFilters;
+
+/// Prices, quantities and balances of orders and accounts.
+///
+/// `f64` by default, or `rust_decimal::Decimal` with the `decimal` feature in which case they are parsed and sent
+/// exactly as Binance formats them, e.g. `0.3` instead of `0.30000000000000004`.
+#[cfg(not(feature = "decimal"))]
+pub type Amount = f64;
+#[cfg(feature = "decimal")]
+pub type Amount = rust_decimal::Decimal;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -38,6 +52,9 @@ pub struct Symbol {
     pub is_margin_trading_allowed: bool,
     pub filters: Vec<Filters>,
     pub permissions: Vec<SymbolPermission>,
+    /// Only sent when requested with `show_permission_sets`, the symbol has all the permissions of one of the sets
+    #[serde(default)]
+    pub permission_sets: Vec<Vec<SymbolPermission>>,
 }
 
 impl Symbol {
@@ -54,6 +71,25 @@ impl Symbol {
             .find(|filter| matches!(filter, Filters::MarketLotSize { .. }))
             .cloned()
     }
+
+    /// Filters used to validate and round orders of this symbol
+    pub fn order_filters(&self) -> OrderFilters { OrderFilters::from(self.filters.as_slice()) }
+
+    /// Whether the symbol has the permission, directly or in one of its permission sets
+    pub fn has_permission(&self, permission: &SymbolPermission) -> bool {
+        self.permissions.contains(permission) || self.permission_sets.iter().any(|set| set.contains(permission))
+    }
+}
+
+/// Options of the exchange information query, all symbols are returned by default
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExchangeInfoQuery {
+    /// Only return these symbols
+    pub symbols: Vec<String>,
+    /// Only return symbols with one of these permissions
+    pub permissions: Vec<SymbolPermission>,
+    /// Return the permission sets of symbols
+    pub show_permission_sets: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -106,6 +142,38 @@ pub enum Filters {
         apply_to_market: bool,
         avg_price_mins: u64,
     },
+    #[serde(rename = "NOTIONAL")]
+    #[serde(rename_all = "camelCase")]
+    Notional {
+        #[serde(with = "string_or_float")]
+        min_notional: f64,
+        apply_min_to_market: bool,
+        #[serde(with = "string_or_float")]
+        max_notional: f64,
+        apply_max_to_market: bool,
+        avg_price_mins: u64,
+    },
+    #[serde(rename = "PERCENT_PRICE_BY_SIDE")]
+    #[serde(rename_all = "camelCase")]
+    PercentPriceBySide {
+        #[serde(with = "string_or_float")]
+        bid_multiplier_up: f64,
+        #[serde(with = "string_or_float")]
+        bid_multiplier_down: f64,
+        #[serde(with = "string_or_float")]
+        ask_multiplier_up: f64,
+        #[serde(with = "string_or_float")]
+        ask_multiplier_down: f64,
+        avg_price_mins: u64,
+    },
+    #[serde(rename = "TRAILING_DELTA")]
+    #[serde(rename_all = "camelCase")]
+    TrailingDelta {
+        min_trailing_above_delta: u64,
+        max_trailing_above_delta: u64,
+        min_trailing_below_delta: u64,
+        max_trailing_below_delta: u64,
+    },
     #[serde(rename = "ICEBERG_PARTS")]
     #[serde(rename_all = "camelCase")]
     IcebergParts { limit: u16 },
@@ -175,9 +243,9 @@ pub enum AccountType {
 pub struct Balance {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Amount,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -188,27 +256,27 @@ pub struct Order {
     pub order_list_id: i32,
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Amount,
     pub status: OrderStatus,
     pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub side: OrderSide,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     #[serde(with = "string_or_float")]
-    pub iceberg_qty: f64,
+    pub iceberg_qty: Amount,
     pub time: u64,
     pub update_time: u64,
     pub is_working: bool,
     #[serde(with = "string_or_float")]
-    pub orig_quote_order_qty: f64,
+    pub orig_quote_order_qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -233,11 +301,11 @@ pub struct OrderCanceledReplaced {
 #[serde(rename_all = "camelCase")]
 pub struct Fill {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
     #[serde(with = "string_or_float")]
-    pub commission: f64,
+    pub commission: Amount,
     pub commission_asset: String,
 }
 
@@ -249,13 +317,13 @@ pub struct Transaction {
     pub client_order_id: String,
     pub transact_time: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Amount,
     pub status: OrderStatus,
     pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
@@ -289,17 +357,17 @@ pub struct OrderBook {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Bids {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Asks {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -325,14 +393,14 @@ pub enum Prices {
 pub struct SymbolPrice {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AveragePrice {
     pub mins: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -352,13 +420,13 @@ pub enum KlineSummaries {
 pub struct Tickers {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Amount,
     #[serde(with = "string_or_float")]
-    pub bid_qty: f64,
+    pub bid_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_qty: f64,
+    pub ask_qty: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -366,9 +434,9 @@ pub struct Tickers {
 pub struct TradeHistory {
     pub id: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
     pub commission: String,
     pub commission_asset: String,
     pub time: u64,
@@ -384,21 +452,21 @@ pub struct PriceStats {
     pub price_change_percent: String,
     pub weighted_avg_price: String,
     #[serde(with = "string_or_float")]
-    pub prev_close_price: f64,
+    pub prev_close_price: Amount,
     #[serde(with = "string_or_float")]
-    pub last_price: f64,
+    pub last_price: Amount,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Amount,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Amount,
     #[serde(with = "string_or_float")]
-    pub open_price: f64,
+    pub open_price: Amount,
     #[serde(with = "string_or_float")]
-    pub high_price: f64,
+    pub high_price: Amount,
     #[serde(with = "string_or_float")]
-    pub low_price: f64,
+    pub low_price: Amount,
     #[serde(with = "string_or_float")]
-    pub volume: f64,
+    pub volume: Amount,
     pub open_time: u64,
     pub close_time: u64,
     pub first_id: u64,
@@ -421,9 +489,25 @@ pub struct AggTrade {
     #[serde(rename = "M")]
     pub best_match: bool,
     #[serde(rename = "p", with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(rename = "q", with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
+}
+
+/// Public trade of `/api/v3/trades` and `/api/v3/historicalTrades`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTrade {
+    pub id: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub quote_qty: Amount,
+    pub time: u64,
+    pub is_buyer_maker: bool,
+    pub is_best_match: bool,
 }
 
 #[derive(Debug, Serialize_repr, Deserialize_repr, Clone, PartialEq, Eq)]
@@ -437,7 +521,8 @@ pub enum MarginTransferType {
 #[serde(rename_all = "camelCase")]
 pub struct Transfer {
     pub asset: String,
-    pub amount: f64,
+    #[serde(with = "string_or_float")]
+    pub amount: Amount,
     #[serde(rename(serialize = "type", deserialize = "type"))]
     pub transfer_type: MarginTransferType,
 }
@@ -454,7 +539,8 @@ pub enum IsolatedMarginTransferType {
 pub struct IsolatedTransfer {
     pub asset: String,
     pub symbol: String,
-    pub amount: f64,
+    #[serde(with = "string_or_float")]
+    pub amount: Amount,
     pub trans_from: IsolatedMarginTransferType,
     pub trans_to: IsolatedMarginTransferType,
 }
@@ -463,7 +549,8 @@ pub struct IsolatedTransfer {
 #[serde(rename_all = "camelCase")]
 pub struct Loan {
     pub asset: String,
-    pub amount: f64,
+    #[serde(with = "string_or_float")]
+    pub amount: Amount,
     pub is_isolated: Option<String>,
     pub symbol: Option<String>,
 }
@@ -479,6 +566,8 @@ pub enum TimeInForce {
     FOK,
     /// Good till expired
     GTX,
+    /// Good till date, futures only
+    GTD,
     #[serde(other)]
     Other,
 }
@@ -570,14 +659,14 @@ pub struct MarginOrder {
     pub side: OrderSide,
     #[serde(rename(serialize = "type", deserialize = "type"))]
     pub order_type: OrderType,
-    pub quantity: Option<f64>,
-    pub quote_order_qty: Option<f64>,
-    pub price: Option<f64>,
+    pub quantity: Option<Amount>,
+    pub quote_order_qty: Option<Amount>,
+    pub price: Option<Amount>,
     /// Used with `OrderType::StopLoss`, `OrderType::StopLossLimit`, `OrderType::TakeProfit` and `OrderType::TakeProfitLimit`
-    pub stop_price: Option<f64>,
+    pub stop_price: Option<Amount>,
     pub new_client_order_id: Option<String>,
     /// Used with `OrderType::Limit`, `OrderType::StopLossLimit` and `OrderType::TakeProfitLimit` to create an iceberg order
-    pub iceberg_qty: Option<f64>,
+    pub iceberg_qty: Option<Amount>,
     /// Default is `OrderResponse::ACK`
     pub new_order_resp_type: OrderResponse,
     /// N.B. : do not set with `OrderType::Market`
@@ -644,16 +733,16 @@ pub struct MarginOCOOrder {
     /// A unique identifier that will be applied to all orders
     pub list_client_order_id: Option<String>,
     pub side: OrderSide,
-    pub quantity: f64,
+    pub quantity: Amount,
     /// A unique identifier that will be applied to the limit order
     pub limit_client_order_id: Option<String>,
-    pub price: f64,
-    pub limit_iceberg_qty: Option<f64>,
+    pub price: Amount,
+    pub limit_iceberg_qty: Option<Amount>,
     /// A unique identifier that will be applied to the stop order
     pub stop_client_order_id: Option<String>,
-    pub stop_price: f64,
-    pub stop_limit_price: Option<f64>,
-    pub stop_iceberg_qty: Option<f64>,
+    pub stop_price: Amount,
+    pub stop_limit_price: Option<Amount>,
+    pub stop_iceberg_qty: Option<Amount>,
     pub stop_limit_time_in_force: Option<TimeInForce>,
     /// Default is `OrderResponse::ACK`
     pub new_order_resp_type: Option<OrderResponse>,
@@ -672,7 +761,7 @@ pub struct MarginOCOOrderResult {
     pub transaction_time: u128,
     pub symbol: String,
     #[serde(default, with = "string_or_float_opt")]
-    pub margin_buy_borrow_amount: Option<f64>,
+    pub margin_buy_borrow_amount: Option<Amount>,
     pub margin_buy_borrow_asset: Option<String>,
     pub is_isolated: Option<bool>,
     pub orders: Vec<OCOOrderDetail>,
@@ -695,22 +784,22 @@ pub struct OCOOrderReport {
     pub client_order_id: Option<String>,
     pub transact_time: u128,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Amount,
     pub status: OrderStatus,
     pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub side: OrderSide,
     #[serde(default, with = "string_or_float_opt")]
-    pub stop_price: Option<f64>,
+    pub stop_price: Option<Amount>,
     #[serde(default, with = "string_or_float_opt")]
-    pub iceberg_qty: Option<f64>,
+    pub iceberg_qty: Option<Amount>,
 }
 
 /// archived and is_isolated are only applicable to certain endpoints
@@ -904,15 +993,15 @@ pub struct RecordsQueryResult<R> {
 pub struct UserAsset {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub borrowed: f64,
+    pub borrowed: Amount,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Amount,
     #[serde(with = "string_or_float")]
-    pub interest: f64,
+    pub interest: Amount,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Amount,
     #[serde(with = "string_or_float")]
-    pub net_asset: f64,
+    pub net_asset: Amount,
 }
 
 pub type UserAssets = Vec<UserAsset>;
@@ -924,11 +1013,11 @@ pub struct MarginAccountDetails {
     #[serde(with = "string_or_float")]
     pub margin_level: f64,
     #[serde(with = "string_or_float")]
-    pub total_asset_of_btc: f64,
+    pub total_asset_of_btc: Amount,
     #[serde(with = "string_or_float")]
-    pub total_liability_of_btc: f64,
+    pub total_liability_of_btc: Amount,
     #[serde(with = "string_or_float")]
-    pub total_net_asset_of_btc: f64,
+    pub total_net_asset_of_btc: Amount,
     pub trade_enabled: bool,
     pub transfer_enabled: bool,
     pub user_assets: UserAssets,
@@ -940,20 +1029,20 @@ pub struct IsolatedMarginAccountAsset {
     pub asset: String,
     pub borrow_enabled: bool,
     #[serde(with = "string_or_float")]
-    pub borrowed: f64,
+    pub borrowed: Amount,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Amount,
     #[serde(with = "string_or_float")]
-    pub interest: f64,
+    pub interest: Amount,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Amount,
     #[serde(with = "string_or_float")]
-    pub net_asset: f64,
+    pub net_asset: Amount,
     #[serde(with = "string_or_float")]
-    pub net_asset_of_btc: f64,
+    pub net_asset_of_btc: Amount,
     pub repay_enabled: bool,
     #[serde(with = "string_or_float")]
-    pub total_asset: f64,
+    pub total_asset: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -970,9 +1059,9 @@ pub struct IsolatedMarginAccountAssetDetails {
     pub margin_ratio: f64,
     pub margin_level_status: MarginLevelStatus,
     #[serde(with = "string_or_float")]
-    pub index_price: f64,
+    pub index_price: Amount,
     #[serde(with = "string_or_float")]
-    pub liquidate_price: f64,
+    pub liquidate_price: Amount,
     #[serde(with = "string_or_float")]
     pub liquidate_rate: f64,
     pub trade_enabled: bool,
@@ -995,11 +1084,11 @@ pub enum MarginLevelStatus {
 pub struct IsolatedMarginAccountDetails {
     pub assets: Vec<IsolatedMarginAccountAssetDetails>,
     #[serde(default, with = "string_or_float_opt")]
-    pub total_asset_of_btc: Option<f64>,
+    pub total_asset_of_btc: Option<Amount>,
     #[serde(default, with = "string_or_float_opt")]
-    pub total_liability_of_btc: Option<f64>,
+    pub total_liability_of_btc: Option<Amount>,
     #[serde(default, with = "string_or_float_opt")]
-    pub total_net_asset_of_btc: Option<f64>,
+    pub total_net_asset_of_btc: Option<Amount>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1109,20 +1198,20 @@ pub struct MarginOrderResult {
     pub client_order_id: String,
     pub transact_time: u128,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Amount,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Amount,
     pub status: OrderStatus,
     pub time_in_force: TimeInForce,
     #[serde(rename(serialize = "type", deserialize = "type"))]
     pub order_type: OrderType,
     pub side: OrderSide,
     #[serde(default, with = "string_or_float_opt")]
-    pub margin_buy_borrow_amount: Option<f64>,
+    pub margin_buy_borrow_amount: Option<Amount>,
     pub margin_buy_borrow_asset: Option<String>,
     pub is_isolated: Option<bool>,
     pub fills: Vec<Fill>,
@@ -1224,6 +1313,7 @@ pub enum SymbolStatus {
 pub enum SymbolPermission {
     Spot,
     Margin,
+    Leveraged,
     #[serde(other)]
     Other,
 }
@@ -1246,6 +1336,8 @@ pub enum OrderStatus {
     Rejected,
     /// The order was canceled according to the order type's rules (e.g. LIMIT FOK orders with no fill, LIMIT IOC or MARKET orders that partially fill) or by the exchange, (e.g. orders canceled during liquidation, orders canceled during maintenance)
     Expired,
+    /// The order was expired by the self-trade prevention of its `SelfTradePreventionMode`
+    ExpiredInMatch,
     /// Part of the order or all of the order's quantity has filled.
     Trade,
 }
@@ -1288,7 +1380,10 @@ pub struct MarginOCOOrderQuery {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ContingencyType {
+    #[serde(rename = "OCO")]
     OCO,
+    #[serde(rename = "OTO")]
+    OTO,
     #[serde(other)]
     Other,
 }
@@ -1387,6 +1482,174 @@ pub struct KlineSummary {
     pub taker_buy_quote_asset_volume: f64,
 }
 
+/// A kline as sent by the REST API, an array of values
+pub(crate) struct KlineRow(i64, f64, f64, f64, f64, f64, i64, f64, i64, f64, f64);
+
+/// Reads the first 11 values of the array and skips the rest, which are unused or added later by Binance
+impl<'de> serde::Deserialize<'de> for KlineRow {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{self, IgnoredAny, SeqAccess, Visitor};
+
+        #[derive(Deserialize)]
+        struct Float(#[serde(with = "string_or_float")] f64);
+
+        fn element<'de, A, T>(seq: &mut A, index: usize) -> Result<T, A::Error>
+        where
+            A: SeqAccess<'de>,
+            T: serde::Deserialize<'de>,
+        {
+            seq.next_element()?.ok_or_else(|| de::Error::invalid_length(index, &"a kline of at least 11 values"))
+        }
+
+        struct KlineRowVisitor;
+
+        impl<'de> Visitor<'de> for KlineRowVisitor {
+            type Value = KlineRow;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result { formatter.write_str("a kline array") }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<KlineRow, A::Error> {
+                let row = KlineRow(
+                    element(&mut seq, 0)?,
+                    element::<_, Float>(&mut seq, 1)?.0,
+                    element::<_, Float>(&mut seq, 2)?.0,
+                    element::<_, Float>(&mut seq, 3)?.0,
+                    element::<_, Float>(&mut seq, 4)?.0,
+                    element::<_, Float>(&mut seq, 5)?.0,
+                    element(&mut seq, 6)?,
+                    element::<_, Float>(&mut seq, 7)?.0,
+                    element(&mut seq, 8)?,
+                    element::<_, Float>(&mut seq, 9)?.0,
+                    element::<_, Float>(&mut seq, 10)?.0,
+                );
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(row)
+            }
+        }
+
+        deserializer.deserialize_seq(KlineRowVisitor)
+    }
+}
+
+impl KlineRow {
+    pub(crate) fn open_time(&self) -> i64 { self.0 }
+}
+
+impl From<KlineRow> for KlineSummary {
+    fn from(row: KlineRow) -> Self {
+        Self {
+            open_time: row.0,
+            open: row.1,
+            high: row.2,
+            low: row.3,
+            close: row.4,
+            volume: row.5,
+            close_time: row.6,
+            quote_asset_volume: row.7,
+            number_of_trades: row.8,
+            taker_buy_base_asset_volume: row.9,
+            taker_buy_quote_asset_volume: row.10,
+        }
+    }
+}
+
+/// Interval of klines, `Seconds1` is only supported by spot
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KlineInterval {
+    #[serde(rename = "1s")]
+    Seconds1,
+    #[serde(rename = "1m")]
+    Minutes1,
+    #[serde(rename = "3m")]
+    Minutes3,
+    #[serde(rename = "5m")]
+    Minutes5,
+    #[serde(rename = "15m")]
+    Minutes15,
+    #[serde(rename = "30m")]
+    Minutes30,
+    #[serde(rename = "1h")]
+    Hours1,
+    #[serde(rename = "2h")]
+    Hours2,
+    #[serde(rename = "4h")]
+    Hours4,
+    #[serde(rename = "6h")]
+    Hours6,
+    #[serde(rename = "8h")]
+    Hours8,
+    #[serde(rename = "12h")]
+    Hours12,
+    #[serde(rename = "1d")]
+    Days1,
+    #[serde(rename = "3d")]
+    Days3,
+    #[serde(rename = "1w")]
+    Weeks1,
+    #[serde(rename = "1M")]
+    Months1,
+}
+
+impl KlineInterval {
+    pub const ALL: [KlineInterval; 16] = [
+        Self::Seconds1,
+        Self::Minutes1,
+        Self::Minutes3,
+        Self::Minutes5,
+        Self::Minutes15,
+        Self::Minutes30,
+        Self::Hours1,
+        Self::Hours2,
+        Self::Hours4,
+        Self::Hours6,
+        Self::Hours8,
+        Self::Hours12,
+        Self::Days1,
+        Self::Days3,
+        Self::Weeks1,
+        Self::Months1,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Seconds1 => "1s",
+            Self::Minutes1 => "1m",
+            Self::Minutes3 => "3m",
+            Self::Minutes5 => "5m",
+            Self::Minutes15 => "15m",
+            Self::Minutes30 => "30m",
+            Self::Hours1 => "1h",
+            Self::Hours2 => "2h",
+            Self::Hours4 => "4h",
+            Self::Hours6 => "6h",
+            Self::Hours8 => "8h",
+            Self::Hours12 => "12h",
+            Self::Days1 => "1d",
+            Self::Days3 => "3d",
+            Self::Weeks1 => "1w",
+            Self::Months1 => "1M",
+        }
+    }
+}
+
+impl fmt::Display for KlineInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for KlineInterval {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|interval| interval.as_str() == s)
+            .ok_or_else(|| Error::InvalidPeriod(s.to_string()))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PropertyCmd {
     pub id: i32,
@@ -1686,7 +1949,8 @@ pub enum UniversalTransferType {
 #[serde(rename_all = "camelCase")]
 pub struct UniversalTransfer {
     pub asset: String,
-    pub amount: f64,
+    #[serde(with = "string_or_float")]
+    pub amount: Amount,
     pub from_symbol: Option<String>,
     pub to_symbol: Option<String>,
     #[serde(rename(serialize = "type", deserialize = "type"))]
@@ -1955,6 +2219,7 @@ pub struct ApiKeyPermissions {
 
 pub mod string_or_float {
     use std::fmt;
+    use std::str::FromStr;
 
     use serde::{de, Deserialize, Deserializer, Serializer};
 
@@ -1966,9 +2231,12 @@ pub mod string_or_float {
         serializer.collect_str(value)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    /// Deserializes an `f64`, or a `Decimal` from the shortest representation of a float
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
     where
         D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: fmt::Display,
     {
         #[derive(Deserialize)]
         #[serde(untagged)]
@@ -1979,15 +2247,16 @@ pub mod string_or_float {
 
         match StringOrFloat::deserialize(deserializer)? {
             StringOrFloat::String(s) => s.parse().map_err(de::Error::custom),
-            StringOrFloat::Float(i) => Ok(i),
+            StringOrFloat::Float(i) => i.to_string().parse().map_err(de::Error::custom),
         }
     }
 }
 
 pub(crate) mod string_or_float_opt {
     use std::fmt;
+    use std::str::FromStr;
 
-    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::{de, Deserialize, Deserializer, Serializer};
 
     pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -2000,9 +2269,12 @@ pub(crate) mod string_or_float_opt {
         }
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+    /// Empty strings and nulls are deserialized as `None`
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
     where
         D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: fmt::Display,
     {
         #[derive(Deserialize)]
         #[serde(untagged)]
@@ -2011,7 +2283,12 @@ pub(crate) mod string_or_float_opt {
             Float(f64),
         }
 
-        Ok(Some(crate::rest_model::string_or_float::deserialize(deserializer)?))
+        match Option::<StringOrFloat>::deserialize(deserializer)? {
+            Some(StringOrFloat::String(s)) if s.is_empty() => Ok(None),
+            Some(StringOrFloat::String(s)) => s.parse().map(Some).map_err(de::Error::custom),
+            Some(StringOrFloat::Float(f)) => f.to_string().parse().map(Some).map_err(de::Error::custom),
+            None => Ok(None),
+        }
     }
 }
 
@@ -2091,4 +2368,48 @@ mod test {
         let result = serde_json::from_str::<ExchangeInformation>(&fc);
         assert!(result.is_ok(), "{result:?}");
     }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn amounts_are_exact_decimals() {
+        use crate::account::OrderRequest;
+        use crate::rest_model::Balance;
+
+        let balance: Balance =
+            serde_json::from_str(r#"{"asset": "BTC", "free": "0.10000000", "locked": 0.2}"#).unwrap();
+        let total = balance.free + balance.locked;
+        assert_eq!(total.to_string(), "0.30000000");
+        let order = OrderRequest {
+            quantity: Some(total),
+            ..OrderRequest::default()
+        };
+        assert!(qs::to_string(&order).unwrap().contains("quantity=0.30000000"));
+    }
+
+    #[test]
+    fn kline_rows_and_intervals() {
+        use crate::errors::Error;
+        use crate::rest_model::{KlineInterval, KlineRow, KlineSummary};
+
+        let row = r#"[1499040000000, "0.01634790", "0.80000000", "0.01575800", "0.01577100", "148976.11427815",
+            1499644799999, "2434.19055334", 308, "1756.87402397", "28.46694368", "0"]"#;
+        let kline = KlineSummary::from(serde_json::from_str::<KlineRow>(row).unwrap());
+        assert_eq!(kline.open_time, 1499040000000);
+        assert_eq!(kline.high, 0.8);
+        assert_eq!(kline.taker_buy_quote_asset_volume, 28.46694368);
+        assert!(serde_json::from_str::<KlineRow>(r#"[1499040000000, "0.01634790"]"#).is_err());
+        assert!(serde_json::from_str::<KlineRow>(&row.replace("308", r#""many""#)).is_err());
+        let longer_row = row.replace(r#""0"]"#, r#""0", "1.5"]"#);
+        let kline = KlineSummary::from(serde_json::from_str::<KlineRow>(&longer_row).unwrap());
+        assert_eq!(kline.number_of_trades, 308);
+        let shorter_row = row.replace(r#", "0"]"#, "]");
+        assert!(serde_json::from_str::<KlineRow>(&shorter_row).is_ok());
+
+        for interval in KlineInterval::ALL {
+            assert_eq!(interval.as_str().parse::<KlineInterval>().unwrap(), interval);
+            assert_eq!(serde_json::to_string(&interval).unwrap(), format!("\"{interval}\""));
+        }
+        assert_eq!(KlineInterval::Months1.to_string(), "1M");
+        assert!(matches!("2m".parse::<KlineInterval>(), Err(Error::InvalidPeriod(_))));
+    }
 }
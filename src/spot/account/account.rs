@@ -1,7 +1,8 @@
 use crate::client::Client;
 
 use super::query::{
-    QueryAccountBuilder, QueryAllOrdersBuilder, QueryMyTradesBuilder, QueryOpenOrdersBuilder, QueryOrderBuilder,
+    QueryAccountBuilder, QueryAllOrderListsBuilder, QueryAllOrdersBuilder, QueryMyTradesBuilder,
+    QueryOpenOrderListsBuilder, QueryOpenOrdersBuilder, QueryOrderBuilder, QueryOrderListBuilder,
 };
 
 pub struct Account {
@@ -96,27 +97,86 @@ impl Account {
     {
         QueryMyTradesBuilder::new(&self.client, symbol.as_ref().to_string())
     }
+
+    /// # 查询订单列表
+    /// ## Example
+    /// ``` rust
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.account.get_order_list().order_list_id(1);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/en/#query-order-list-user_data
+    pub fn get_order_list(&self) -> QueryOrderListBuilder<'_> { QueryOrderListBuilder::new(&self.client) }
+
+    /// # 查询所有订单列表
+    /// ## Example
+    /// ``` rust
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.account.get_all_order_lists();
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/en/#query-all-order-lists-user_data
+    pub fn get_all_order_lists(&self) -> QueryAllOrderListsBuilder<'_> { QueryAllOrderListsBuilder::new(&self.client) }
+
+    /// # 查询订单列表挂单
+    /// ## Example
+    /// ``` rust
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.account.get_open_order_lists();
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/en/#query-open-order-lists-user_data
+    pub fn get_open_order_lists(&self) -> QueryOpenOrderListsBuilder<'_> { QueryOpenOrderListsBuilder::new(&self.client) }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "mock"))]
 mod tests {
+    use reqwest::Method;
+    use serde_json::json;
+
     use crate::api::Binance;
-    use crate::config::Config;
+    use crate::mock::{MockResponse, MockServer, MOCK_SECRET_KEY};
     use crate::Spot;
-    use dotenv::dotenv;
-    use std::env;
 
-    fn env() -> (String, String) {
-        dotenv().ok();
-        (env::var("API_KEY").unwrap(), env::var("API_SECRET").unwrap())
+    #[tokio::test]
+    async fn test_get_account() {
+        let server = MockServer::start().await;
+        let spot: Spot = Binance::new_with_config(Some(server.api_key()), Some(MOCK_SECRET_KEY.into()), &server.config());
+        let account = spot.account.get_account().send().await.unwrap();
+        assert_eq!(account.balances[0].asset, "BTC");
+        assert_eq!(server.requests()[0].path, "/api/v3/account");
     }
 
-    #[test]
-    fn test_get_account() {
-        let (key, secret) = env();
-        let spot: Spot = Binance::new_with_config(Some(key), Some(secret), &Config::testnet());
-        let builder = spot.account.get_account().send();
-        let resp = tokio_test::block_on(builder);
-        println!("{:?}", resp.unwrap());
+    #[tokio::test]
+    async fn test_get_order_lists() {
+        let server = MockServer::start().await;
+        let spot: Spot = Binance::new_with_config(Some(server.api_key()), Some(MOCK_SECRET_KEY.into()), &server.config());
+        let list = json!({
+            "orderListId": 1, "contingencyType": "OCO", "listStatusType": "EXEC_STARTED",
+            "listOrderStatus": "EXECUTING", "listClientOrderId": "list", "transactionTime": 1669277163808u64,
+            "symbol": "BTCUSDT", "orders": [{"symbol": "BTCUSDT", "orderId": 2, "clientOrderId": "order"}]
+        });
+        server.mock(Method::GET, "/api/v3/orderList", MockResponse::json(list.clone()));
+        server.mock(Method::GET, "/api/v3/allOrderList", MockResponse::json(json!([list.clone()])));
+        server.mock(Method::GET, "/api/v3/openOrderList", MockResponse::json(json!([list])));
+
+        let list = spot.account.get_order_list().order_list_id(1).send().await.unwrap();
+        assert_eq!(list.orders[0].order_id, 2);
+        assert_eq!(server.requests()[0].param("orderListId"), Some("1"));
+        let lists = spot.account.get_all_order_lists().from_id(1).limit(10).send().await.unwrap();
+        assert_eq!(lists.len(), 1);
+        assert_eq!(server.requests()[1].param("fromId"), Some("1"));
+        let lists = spot.account.get_open_order_lists().send().await.unwrap();
+        assert_eq!(lists[0].symbol, "BTCUSDT");
+        assert_eq!(server.requests()[2].path, "/api/v3/openOrderList");
     }
 }
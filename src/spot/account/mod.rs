@@ -1,4 +1,8 @@
 pub mod account;
 pub use account::Account;
+pub use query::{
+    AccountInformation, AllOrderListsRequest, AllOrdersRequest, Balance, MyTradeRequest, QueryOrder, QueryOrderListRequest,
+    QueryOrderRequest, TradeHistory,
+};
 
-mod query;
\ No newline at end of file
+mod query;
@@ -3,29 +3,30 @@ use rust_decimal::Decimal;
 use crate::client::Client;
 use crate::errors::*;
 use crate::rest_model::{AccountType, OrderSide, OrderStatus, OrderType, TimeInForce};
+use crate::spot::trade::OrderList;
 use crate::util::*;
 
 /// 查询订单
 /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-31
 pub struct QueryOrderBuilder<'a> {
     client: &'a Client,
-    playload: OrderRequest,
+    playload: QueryOrderRequest,
     recv_window: u64,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-struct OrderRequest {
-    symbol: String,
-    order_id: Option<String>,
-    orig_client_order_id: Option<String>,
+pub struct QueryOrderRequest {
+    pub symbol: String,
+    pub order_id: Option<String>,
+    pub orig_client_order_id: Option<String>,
 }
 
 impl<'a> QueryOrderBuilder<'a> {
     pub(crate) fn new(client: &'a Client, symbol: String) -> Self {
         Self {
             client,
-            playload: OrderRequest {
+            playload: QueryOrderRequest {
                 symbol,
                 ..Default::default()
             },
@@ -97,12 +98,12 @@ pub struct QueryAllOrdersBuilder<'a> {
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-struct AllOrdersRequest {
-    symbol: String,
-    order_id: Option<String>,
-    start_time: Option<u64>,
-    end_time: Option<u64>,
-    limit: Option<u16>,
+pub struct AllOrdersRequest {
+    pub symbol: String,
+    pub order_id: Option<String>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<u16>,
 }
 
 impl<'a> QueryAllOrdersBuilder<'a> {
@@ -186,13 +187,13 @@ pub struct QueryMyTradesBuilder<'a> {
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-struct MyTradeRequest {
-    symbol: String,
-    order_id: Option<String>,
-    start_time: Option<u64>,
-    end_time: Option<u64>,
-    from_id: Option<u64>,
-    limit: Option<u16>,
+pub struct MyTradeRequest {
+    pub symbol: String,
+    pub order_id: Option<String>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub from_id: Option<u64>,
+    pub limit: Option<u16>,
 }
 
 impl<'a> QueryMyTradesBuilder<'a> {
@@ -243,6 +244,134 @@ impl<'a> QueryMyTradesBuilder<'a> {
     }
 }
 
+/// 查询订单列表
+/// https://binance-docs.github.io/apidocs/spot/en/#query-order-list-user_data
+pub struct QueryOrderListBuilder<'a> {
+    client: &'a Client,
+    playload: QueryOrderListRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOrderListRequest {
+    pub order_list_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+}
+
+impl<'a> QueryOrderListBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            playload: QueryOrderListRequest::default(),
+            recv_window: 0,
+        }
+    }
+
+    pub fn order_list_id(mut self, order_list_id: u64) -> Self {
+        self.playload.order_list_id = Some(order_list_id);
+        self
+    }
+
+    pub fn orig_client_order_id(mut self, orig_client_order_id: String) -> Self {
+        self.playload.orig_client_order_id = Some(orig_client_order_id);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<OrderList> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client.get_signed("/api/v3/orderList", data.as_str()).await
+    }
+}
+
+/// 查询所有订单列表
+/// https://binance-docs.github.io/apidocs/spot/en/#query-all-order-lists-user_data
+pub struct QueryAllOrderListsBuilder<'a> {
+    client: &'a Client,
+    playload: AllOrderListsRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AllOrderListsRequest {
+    pub from_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<u16>,
+}
+
+impl<'a> QueryAllOrderListsBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            playload: AllOrderListsRequest::default(),
+            recv_window: 0,
+        }
+    }
+
+    pub fn from_id(mut self, from_id: u64) -> Self {
+        self.playload.from_id = Some(from_id);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<OrderList>> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client.get_signed("/api/v3/allOrderList", data.as_str()).await
+    }
+}
+
+/// 查询订单列表挂单
+/// https://binance-docs.github.io/apidocs/spot/en/#query-open-order-lists-user_data
+pub struct QueryOpenOrderListsBuilder<'a> {
+    client: &'a Client,
+    recv_window: u64,
+}
+
+impl<'a> QueryOpenOrderListsBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self { Self { client, recv_window: 0 } }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    fn get_params(&self) -> Result<String> {
+        let parameters = [("", "")];
+        build_signed_request(parameters, self.recv_window)
+    }
+
+    pub async fn send(self) -> Result<Vec<OrderList>> {
+        let data = self.get_params()?;
+        self.client.get_signed("/api/v3/openOrderList", data.as_str()).await
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryOrder {
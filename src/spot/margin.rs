@@ -1,6 +0,0 @@
-use crate::client::Client;
-
-pub struct Margin {
-    pub client: Client,
-    pub recv_window: u64,
-}
\ No newline at end of file
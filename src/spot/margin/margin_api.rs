@@ -0,0 +1,662 @@
+use crate::client::Client;
+use crate::rest_model::*;
+
+use super::order::{
+    CancelAllMarginOrdersBuilder, CancelMarginOCOOrderBuilder, CancelMarginOrderBuilder, IsolatedMarginTransferBuilder,
+    MarginLoanBuilder, MarginTransferBuilder, PlaceMarginOCOOrderBuilder, PlaceMarginOrderBuilder,
+};
+use super::query::{
+    QueryAllMarginOCOOrdersBuilder, QueryIsolatedMarginAccountBuilder, QueryMarginAccountBuilder,
+    QueryMarginAllOrdersBuilder, QueryMarginOCOOrderBuilder, QueryMarginOpenOrdersBuilder, QueryMarginOrderBuilder,
+    QueryMarginRecordsBuilder, QueryMarginTradesBuilder, QueryOpenMarginOCOOrdersBuilder,
+};
+use super::user_stream::MarginUserStreamBuilder;
+
+static SAPI_V1_MARGIN_TRANSFER: &str = "/sapi/v1/margin/transfer";
+static SAPI_V1_MARGIN_LOAN: &str = "/sapi/v1/margin/loan";
+static SAPI_V1_MARGIN_REPAY: &str = "/sapi/v1/margin/repay";
+static SAPI_V1_MARGIN_INTEREST_HISTORY: &str = "/sapi/v1/margin/interestHistory";
+static SAPI_V1_MARGIN_FORCED_LIQUIDATION_RECORD: &str = "/sapi/v1/margin/forceLiquidationRec";
+
+pub struct Margin {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl Margin {
+    /// # 杠杆账户下单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.place_order("BTCUSDT", OrderSide::Buy, OrderType::Market)
+    ///     .quantity("0.001".parse().unwrap())
+    ///     .side_effect_type(SideEffectType::MarginBuy);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#trade-8
+    pub fn place_order<S1, S2, S3>(&self, symbol: S1, side: S2, order_type: S3) -> PlaceMarginOrderBuilder<'_>
+    where
+        S1: AsRef<str>,
+        S2: Into<OrderSide>,
+        S3: Into<OrderType>,
+    {
+        PlaceMarginOrderBuilder::new(
+            &self.client,
+            symbol.as_ref().to_string(),
+            side.into(),
+            order_type.into(),
+        )
+    }
+
+    /// # 杠杆账户撤销订单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.cancel_order("BTCUSDT").order_id(28);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#trade-9
+    pub fn cancel_order<S>(&self, symbol: S) -> CancelMarginOrderBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        CancelMarginOrderBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
+
+    /// # 杠杆账户撤销单一交易对的所有挂单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.cancel_all_open_orders("BTCUSDT");
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#trade-10
+    pub fn cancel_all_open_orders<S>(&self, symbol: S) -> CancelAllMarginOrdersBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        CancelAllMarginOrdersBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
+
+    /// # 杠杆账户下OCO订单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.place_oco_order(
+    ///     "BTCUSDT",
+    ///     OrderSide::Sell,
+    ///     "0.001".parse().unwrap(),
+    ///     "26000".parse().unwrap(),
+    ///     "24000".parse().unwrap(),
+    /// );
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#oco-trade-2
+    pub fn place_oco_order<S1, S2>(
+        &self,
+        symbol: S1,
+        side: S2,
+        quantity: Amount,
+        price: Amount,
+        stop_price: Amount,
+    ) -> PlaceMarginOCOOrderBuilder<'_>
+    where
+        S1: AsRef<str>,
+        S2: Into<OrderSide>,
+    {
+        PlaceMarginOCOOrderBuilder::new(
+            &self.client,
+            symbol.as_ref().to_string(),
+            side.into(),
+            quantity,
+            price,
+            stop_price,
+        )
+    }
+
+    /// # 杠杆账户撤销OCO订单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.cancel_oco_order("BTCUSDT").order_list_id(1);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#oco-trade-3
+    pub fn cancel_oco_order<S>(&self, symbol: S) -> CancelMarginOCOOrderBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        CancelMarginOCOOrderBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
+
+    /// # 杠杆账户借贷
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.borrow("BTC", "0.001".parse().unwrap());
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#margin-2
+    pub fn borrow<S>(&self, asset: S, amount: Amount) -> MarginLoanBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        MarginLoanBuilder::new(&self.client, SAPI_V1_MARGIN_LOAN, asset.as_ref().to_string(), amount)
+    }
+
+    /// # 杠杆账户归还借贷
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.repay("BTC", "0.001".parse().unwrap());
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#margin-3
+    pub fn repay<S>(&self, asset: S, amount: Amount) -> MarginLoanBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        MarginLoanBuilder::new(&self.client, SAPI_V1_MARGIN_REPAY, asset.as_ref().to_string(), amount)
+    }
+
+    /// # 杠杆账户划转
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.transfer("BTC", "0.001".parse().unwrap(), MarginTransferType::FromMainToMargin);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#margin
+    pub fn transfer<S>(&self, asset: S, amount: Amount, transfer_type: MarginTransferType) -> MarginTransferBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        MarginTransferBuilder::new(&self.client, asset.as_ref().to_string(), amount, transfer_type)
+    }
+
+    /// # 杠杆逐仓账户划转
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.isolated_transfer(
+    ///     "BTC",
+    ///     "BTCUSDT",
+    ///     "0.001".parse().unwrap(),
+    ///     IsolatedMarginTransferType::Spot,
+    ///     IsolatedMarginTransferType::IsolatedMargin,
+    /// );
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#margin-4
+    pub fn isolated_transfer<S1, S2>(
+        &self,
+        asset: S1,
+        symbol: S2,
+        amount: Amount,
+        trans_from: IsolatedMarginTransferType,
+        trans_to: IsolatedMarginTransferType,
+    ) -> IsolatedMarginTransferBuilder<'_>
+    where
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+    {
+        IsolatedMarginTransferBuilder::new(
+            &self.client,
+            asset.as_ref().to_string(),
+            symbol.as_ref().to_string(),
+            amount,
+            trans_from,
+            trans_to,
+        )
+    }
+
+    /// # 杠杆账户详情
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_account();
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-4
+    pub fn get_account(&self) -> QueryMarginAccountBuilder<'_> {
+        QueryMarginAccountBuilder::new(&self.client)
+    }
+
+    /// # 杠杆逐仓账户信息
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_isolated_account().symbols(vec!["BTCUSDT".into()]);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-21
+    pub fn get_isolated_account(&self) -> QueryIsolatedMarginAccountBuilder<'_> {
+        QueryIsolatedMarginAccountBuilder::new(&self.client)
+    }
+
+    /// # 查询杠杆账户订单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_order("BTCUSDT").order_id("28".into());
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-5
+    pub fn get_order<S>(&self, symbol: S) -> QueryMarginOrderBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        QueryMarginOrderBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
+
+    /// # 杠杆账户当前挂单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_open_orders("BTCUSDT");
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-6
+    pub fn get_open_orders<S>(&self, symbol: S) -> QueryMarginOpenOrdersBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        QueryMarginOpenOrdersBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
+
+    /// # 杠杆账户所有订单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_all_orders("BTCUSDT").limit(10);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-7
+    pub fn get_all_orders<S>(&self, symbol: S) -> QueryMarginAllOrdersBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        QueryMarginAllOrdersBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
+
+    /// # 杠杆账户成交历史
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_my_trades("BTCUSDT").limit(10);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-8
+    pub fn get_my_trades<S>(&self, symbol: S) -> QueryMarginTradesBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        QueryMarginTradesBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
+
+    /// # 查询杠杆账户OCO订单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_oco_order().order_list_id("1".into());
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#oco-user_data
+    pub fn get_oco_order(&self) -> QueryMarginOCOOrderBuilder<'_> {
+        QueryMarginOCOOrderBuilder::new(&self.client)
+    }
+
+    /// # 查询杠杆账户所有OCO订单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_all_oco_orders().limit(10);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#oco-user_data-2
+    pub fn get_all_oco_orders(&self) -> QueryAllMarginOCOOrdersBuilder<'_> {
+        QueryAllMarginOCOOrdersBuilder::new(&self.client)
+    }
+
+    /// # 查询杠杆账户OCO挂单
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_open_oco_orders();
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#oco-user_data-3
+    pub fn get_open_oco_orders(&self) -> QueryOpenMarginOCOOrdersBuilder<'_> {
+        QueryOpenMarginOCOOrdersBuilder::new(&self.client)
+    }
+
+    /// # 查询借贷记录
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_loans("BTC");
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data
+    pub fn get_loans<S>(&self, asset: S) -> QueryMarginRecordsBuilder<'_, LoanState>
+    where
+        S: AsRef<str>,
+    {
+        QueryMarginRecordsBuilder::new(&self.client, SAPI_V1_MARGIN_LOAN).asset(asset.as_ref().to_string())
+    }
+
+    /// # 查询还贷记录
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_repays("BTC");
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-2
+    pub fn get_repays<S>(&self, asset: S) -> QueryMarginRecordsBuilder<'_, RepayState>
+    where
+        S: AsRef<str>,
+    {
+        QueryMarginRecordsBuilder::new(&self.client, SAPI_V1_MARGIN_REPAY).asset(asset.as_ref().to_string())
+    }
+
+    /// # 查询杠杆账户划转历史
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_transfers().transfer_type(TransferType::RollIn);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-9
+    pub fn get_transfers(&self) -> QueryMarginRecordsBuilder<'_, OrderState> {
+        QueryMarginRecordsBuilder::new(&self.client, SAPI_V1_MARGIN_TRANSFER)
+    }
+
+    /// # 查询利息历史
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_interests().asset("BTC".into());
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-10
+    pub fn get_interests(&self) -> QueryMarginRecordsBuilder<'_, InterestState> {
+        QueryMarginRecordsBuilder::new(&self.client, SAPI_V1_MARGIN_INTEREST_HISTORY)
+    }
+
+    /// # 查询强平记录
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::rest_model::*;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.margin.get_forced_liquidations();
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#user_data-11
+    pub fn get_forced_liquidations(&self) -> QueryMarginRecordsBuilder<'_, ForcedLiquidationState> {
+        QueryMarginRecordsBuilder::new(&self.client, SAPI_V1_MARGIN_FORCED_LIQUIDATION_RECORD)
+    }
+
+    /// # 杠杆账户用户数据流
+    /// ## Example
+    /// ``` rust,no_run
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let user_stream = spot.margin.user_stream();
+    /// let start = tokio_test::block_on(user_stream.start()).unwrap();
+    /// let data = tokio_test::block_on(user_stream.keep_alive(&start.listen_key));
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/cn/#listen-key-2
+    pub fn user_stream(&self) -> MarginUserStreamBuilder<'_> {
+        MarginUserStreamBuilder::new(&self.client)
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use reqwest::Method;
+    use serde_json::json;
+
+    use crate::mock::MockResponse;
+    use crate::rest_model::*;
+    use crate::test_util::{amount, spot};
+
+    #[tokio::test]
+    async fn test_place_order() {
+        let (server, spot) = spot().await;
+        server.mock(
+            Method::POST,
+            "/sapi/v1/margin/order",
+            MockResponse::json(json!({
+                "symbol": "BTCUSDT", "orderId": 28, "clientOrderId": "margin", "transactTime": 1669277163808u64,
+                "price": "25000.00000000", "origQty": "0.00100000", "executedQty": "0.00000000",
+                "cummulativeQuoteQty": "0.00000000", "status": "NEW", "timeInForce": "GTC", "type": "LIMIT",
+                "side": "BUY", "marginBuyBorrowAmount": "5", "marginBuyBorrowAsset": "USDT", "isIsolated": true,
+                "fills": []
+            })),
+        );
+        let order = spot
+            .margin
+            .place_order("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .time_in_force(TimeInForce::GTC)
+            .price(amount("25000"))
+            .quantity(amount("0.001"))
+            .side_effect_type(SideEffectType::MarginBuy)
+            .isolated(true)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(order.margin_buy_borrow_amount, Some(amount("5")));
+        let request = &server.requests()[0];
+        assert_eq!(request.param("symbol"), Some("BTCUSDT"));
+        assert_eq!(request.param("type"), Some("LIMIT"));
+        assert_eq!(request.param("price"), Some("25000"));
+        assert_eq!(request.param("sideEffectType"), Some("MARGIN_BUY"));
+        assert_eq!(request.param("isIsolated"), Some("TRUE"));
+    }
+
+    #[tokio::test]
+    async fn test_oco_orders() {
+        let (server, spot) = spot().await;
+        let list = json!({
+            "orderListId": 1, "contingencyType": "OCO", "listStatusType": "EXEC_STARTED",
+            "listOrderStatus": "EXECUTING", "listClientOrderId": "list", "transactionTime": 1669277163808u64,
+            "symbol": "BTCUSDT", "orders": [], "orderReports": []
+        });
+        server.mock(
+            Method::POST,
+            "/sapi/v1/margin/order/oco",
+            MockResponse::json(list.clone()),
+        );
+        server.mock(Method::DELETE, "/sapi/v1/margin/orderList", MockResponse::json(list));
+        let list = spot
+            .margin
+            .place_oco_order(
+                "BTCUSDT",
+                OrderSide::Sell,
+                amount("0.001"),
+                amount("26000"),
+                amount("24000"),
+            )
+            .stop_limit_price(amount("23900"))
+            .stop_limit_time_in_force(TimeInForce::GTC)
+            .side_effect_type(SideEffectType::AutoRepay)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(list.contingency_type, ContingencyType::OCO);
+        spot.margin
+            .cancel_oco_order("BTCUSDT")
+            .order_list_id(1)
+            .send()
+            .await
+            .unwrap();
+        let requests = server.requests();
+        assert_eq!(requests[0].param("stopPrice"), Some("24000"));
+        assert_eq!(requests[0].param("stopLimitPrice"), Some("23900"));
+        assert_eq!(requests[0].param("sideEffectType"), Some("AUTO_REPAY"));
+        assert_eq!(requests[1].method, Method::DELETE);
+        assert_eq!(requests[1].param("orderListId"), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn test_borrow_and_transfer() {
+        let (server, spot) = spot().await;
+        server.mock(
+            Method::POST,
+            "/sapi/v1/margin/loan",
+            MockResponse::json(json!({"tranId": 1})),
+        );
+        server.mock(
+            Method::POST,
+            "/sapi/v1/margin/transfer",
+            MockResponse::json(json!({"tranId": 2})),
+        );
+        let loan = spot
+            .margin
+            .borrow("BTC", amount("0.001"))
+            .isolated_symbol("BTCUSDT".into())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(loan.tran_id, 1);
+        let transfer = spot
+            .margin
+            .transfer("BTC", amount("0.001"), MarginTransferType::FromMarginToMain)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(transfer.tran_id, 2);
+        let requests = server.requests();
+        assert_eq!(requests[0].param("isIsolated"), Some("TRUE"));
+        assert_eq!(requests[0].param("symbol"), Some("BTCUSDT"));
+        assert_eq!(requests[1].param("type"), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn test_records_queries() {
+        let (server, spot) = spot().await;
+        server.mock(
+            Method::GET,
+            "/sapi/v1/margin/loan",
+            MockResponse::json(json!({
+                "rows": [{
+                    "isolatedSymbol": "BTCUSDT", "txId": 12807067523u64, "asset": "BTC", "principal": "0.84624403",
+                    "timestamp": 1555056425000u64, "status": "CONFIRMED"
+                }],
+                "total": 1
+            })),
+        );
+        let loans = spot.margin.get_loans("BTC").size(10).send().await.unwrap();
+        assert_eq!(loans.total, 1);
+        assert_eq!(loans.rows.unwrap()[0].principal, 0.84624403);
+        let request = &server.requests()[0];
+        assert_eq!(request.param("asset"), Some("BTC"));
+        assert_eq!(request.param("size"), Some("10"));
+    }
+
+    #[tokio::test]
+    async fn test_isolated_user_stream() {
+        let (server, spot) = spot().await;
+        let user_stream = spot.margin.user_stream().isolated_symbol("BTCUSDT".into());
+        let listen_key = user_stream.start().await.unwrap().listen_key;
+        user_stream.keep_alive(&listen_key).await.unwrap();
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/sapi/v1/userDataStream/isolated");
+        assert_eq!(requests[1].param("symbol"), Some("BTCUSDT"));
+        assert_eq!(requests[1].param("listenKey"), Some(listen_key.as_str()));
+    }
+}
@@ -0,0 +1,11 @@
+pub use margin_api::Margin;
+pub use order::{
+    CancelMarginOCOOrderRequest, CancelMarginOrderRequest, IsolatedMarginTransferRequest, MarginLoanRequest,
+    MarginOCOOrderRequest, MarginOrderRequest, MarginTransferRequest,
+};
+pub use query::{MarginAllOrdersRequest, MarginRecordsRequest, MarginTradesRequest, OpenMarginOCOOrdersRequest};
+
+mod margin_api;
+mod order;
+mod query;
+mod user_stream;
@@ -0,0 +1,543 @@
+use crate::client::Client;
+use crate::errors::*;
+use crate::filters::OrderFilters;
+use crate::rest_model::*;
+use crate::spot::trade::OrderRequest;
+use crate::util::*;
+
+/// 杠杆账户下单
+/// https://binance-docs.github.io/apidocs/spot/cn/#trade-8
+pub struct PlaceMarginOrderBuilder<'a> {
+    client: &'a Client,
+    playload: MarginOrderRequest,
+    recv_window: u64,
+    filters: Option<OrderFilters>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginOrderRequest {
+    #[serde(flatten)]
+    pub order: OrderRequest,
+    /// "TRUE" or "FALSE", default is "FALSE"
+    pub is_isolated: Option<String>,
+    /// Borrow or repay along with the order, default is `SideEffectType::NoSideEffect`
+    pub side_effect_type: Option<SideEffectType>,
+}
+
+impl<'a> PlaceMarginOrderBuilder<'a> {
+    pub fn new(client: &'a Client, symbol: String, side: OrderSide, order_type: OrderType) -> Self {
+        Self {
+            client,
+            playload: MarginOrderRequest {
+                order: OrderRequest {
+                    symbol,
+                    side,
+                    order_type,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            recv_window: 0,
+            filters: None,
+        }
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.playload.order.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn quantity(mut self, quantity: Amount) -> Self {
+        self.playload.order.quantity = Some(quantity);
+        self
+    }
+
+    pub fn quote_order_qty(mut self, quote_order_qty: Amount) -> Self {
+        self.playload.order.quote_order_qty = Some(quote_order_qty);
+        self
+    }
+
+    pub fn price(mut self, price: Amount) -> Self {
+        self.playload.order.price = Some(price);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: String) -> Self {
+        self.playload.order.new_client_order_id = Some(new_client_order_id);
+        self
+    }
+
+    pub fn stop_price(mut self, stop_price: Amount) -> Self {
+        self.playload.order.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn iceberg_qty(mut self, iceberg_qty: Amount) -> Self {
+        self.playload.order.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponse) -> Self {
+        self.playload.order.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn side_effect_type(mut self, side_effect_type: SideEffectType) -> Self {
+        self.playload.side_effect_type = Some(side_effect_type);
+        self
+    }
+
+    /// Validates the price and quantity against the filters of the symbol before sending,
+    /// rounding them to the tick and step sizes if the filters round.
+    pub fn filters(mut self, filters: OrderFilters) -> Self {
+        self.filters = Some(filters);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(mut self) -> Result<MarginOrderResult> {
+        if let Some(filters) = &self.filters {
+            self.playload.order.apply_filters(filters)?;
+        }
+        self.client
+            .post_signed_p("/sapi/v1/margin/order", &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 杠杆账户撤销订单
+/// https://binance-docs.github.io/apidocs/spot/cn/#trade-9
+pub struct CancelMarginOrderBuilder<'a> {
+    client: &'a Client,
+    playload: CancelMarginOrderRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelMarginOrderRequest {
+    pub symbol: String,
+    pub is_isolated: Option<String>,
+    pub order_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+    pub new_client_order_id: Option<String>,
+}
+
+impl<'a> CancelMarginOrderBuilder<'a> {
+    pub fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: CancelMarginOrderRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn order_id(mut self, order_id: u64) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn orig_client_order_id(mut self, orig_client_order_id: String) -> Self {
+        self.playload.orig_client_order_id = Some(orig_client_order_id);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: String) -> Self {
+        self.playload.new_client_order_id = Some(new_client_order_id);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<MarginOrderCancellationResult> {
+        self.client
+            .delete_signed_p("/sapi/v1/margin/order", &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 杠杆账户撤销单一交易对的所有挂单
+/// https://binance-docs.github.io/apidocs/spot/cn/#trade-10
+pub struct CancelAllMarginOrdersBuilder<'a> {
+    client: &'a Client,
+    playload: MarginPairQuery,
+    recv_window: u64,
+}
+
+impl<'a> CancelAllMarginOrdersBuilder<'a> {
+    pub fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: MarginPairQuery {
+                symbol,
+                is_isolated: None,
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<MarginOrdersCancellationResult> {
+        self.client
+            .delete_signed_p("/sapi/v1/margin/openOrders", &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 杠杆账户下OCO订单
+/// https://binance-docs.github.io/apidocs/spot/cn/#oco-trade-2
+pub struct PlaceMarginOCOOrderBuilder<'a> {
+    client: &'a Client,
+    playload: MarginOCOOrderRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginOCOOrderRequest {
+    pub symbol: String,
+    /// "TRUE" or "FALSE", default is "FALSE"
+    pub is_isolated: Option<String>,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub side: OrderSide,
+    pub quantity: Amount,
+    /// A unique id for the limit order, automatically generated if not sent.
+    pub limit_client_order_id: Option<String>,
+    pub price: Amount,
+    pub limit_iceberg_qty: Option<Amount>,
+    /// A unique id for the stop order, automatically generated if not sent.
+    pub stop_client_order_id: Option<String>,
+    pub stop_price: Amount,
+    /// Makes the stop order a stop loss limit order, `stop_limit_time_in_force` is then required.
+    pub stop_limit_price: Option<Amount>,
+    pub stop_iceberg_qty: Option<Amount>,
+    pub stop_limit_time_in_force: Option<TimeInForce>,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// Borrow or repay along with the orders, default is `SideEffectType::NoSideEffect`
+    pub side_effect_type: Option<SideEffectType>,
+}
+
+impl<'a> PlaceMarginOCOOrderBuilder<'a> {
+    pub fn new(
+        client: &'a Client,
+        symbol: String,
+        side: OrderSide,
+        quantity: Amount,
+        price: Amount,
+        stop_price: Amount,
+    ) -> Self {
+        Self {
+            client,
+            playload: MarginOCOOrderRequest {
+                symbol,
+                side,
+                quantity,
+                price,
+                stop_price,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: String) -> Self {
+        self.playload.list_client_order_id = Some(list_client_order_id);
+        self
+    }
+
+    pub fn limit_client_order_id(mut self, limit_client_order_id: String) -> Self {
+        self.playload.limit_client_order_id = Some(limit_client_order_id);
+        self
+    }
+
+    pub fn limit_iceberg_qty(mut self, limit_iceberg_qty: Amount) -> Self {
+        self.playload.limit_iceberg_qty = Some(limit_iceberg_qty);
+        self
+    }
+
+    pub fn stop_client_order_id(mut self, stop_client_order_id: String) -> Self {
+        self.playload.stop_client_order_id = Some(stop_client_order_id);
+        self
+    }
+
+    pub fn stop_limit_price(mut self, stop_limit_price: Amount) -> Self {
+        self.playload.stop_limit_price = Some(stop_limit_price);
+        self
+    }
+
+    pub fn stop_iceberg_qty(mut self, stop_iceberg_qty: Amount) -> Self {
+        self.playload.stop_iceberg_qty = Some(stop_iceberg_qty);
+        self
+    }
+
+    pub fn stop_limit_time_in_force(mut self, stop_limit_time_in_force: TimeInForce) -> Self {
+        self.playload.stop_limit_time_in_force = Some(stop_limit_time_in_force);
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponse) -> Self {
+        self.playload.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn side_effect_type(mut self, side_effect_type: SideEffectType) -> Self {
+        self.playload.side_effect_type = Some(side_effect_type);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<MarginOCOOrderResult> {
+        self.client
+            .post_signed_p("/sapi/v1/margin/order/oco", &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 杠杆账户撤销OCO订单
+/// https://binance-docs.github.io/apidocs/spot/cn/#oco-trade-3
+pub struct CancelMarginOCOOrderBuilder<'a> {
+    client: &'a Client,
+    playload: CancelMarginOCOOrderRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelMarginOCOOrderRequest {
+    pub symbol: String,
+    pub is_isolated: Option<String>,
+    pub order_list_id: Option<u64>,
+    pub list_client_order_id: Option<String>,
+    pub new_client_order_id: Option<String>,
+}
+
+impl<'a> CancelMarginOCOOrderBuilder<'a> {
+    pub fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: CancelMarginOCOOrderRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn order_list_id(mut self, order_list_id: u64) -> Self {
+        self.playload.order_list_id = Some(order_list_id);
+        self
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: String) -> Self {
+        self.playload.list_client_order_id = Some(list_client_order_id);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: String) -> Self {
+        self.playload.new_client_order_id = Some(new_client_order_id);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<MarginOCOOrderResult> {
+        self.client
+            .delete_signed_p("/sapi/v1/margin/orderList", &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 杠杆账户借贷、归还借贷
+/// https://binance-docs.github.io/apidocs/spot/cn/#margin-2
+pub struct MarginLoanBuilder<'a> {
+    client: &'a Client,
+    endpoint: &'static str,
+    playload: MarginLoanRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginLoanRequest {
+    pub asset: String,
+    pub amount: Amount,
+    /// "TRUE" or "FALSE", default is "FALSE"
+    pub is_isolated: Option<String>,
+    /// The isolated symbol, required for isolated margin
+    pub symbol: Option<String>,
+}
+
+impl<'a> MarginLoanBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, endpoint: &'static str, asset: String, amount: Amount) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: MarginLoanRequest {
+                asset,
+                amount,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    /// Borrows or repays in the isolated margin account of `symbol`
+    pub fn isolated_symbol(mut self, symbol: String) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(true));
+        self.playload.symbol = Some(symbol);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<TransactionId> {
+        self.client
+            .post_signed_p(self.endpoint, &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 杠杆账户划转
+/// https://binance-docs.github.io/apidocs/spot/cn/#margin
+pub struct MarginTransferBuilder<'a> {
+    client: &'a Client,
+    playload: MarginTransferRequest,
+    recv_window: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTransferRequest {
+    pub asset: String,
+    pub amount: Amount,
+    #[serde(rename = "type")]
+    pub transfer_type: MarginTransferType,
+}
+
+impl<'a> MarginTransferBuilder<'a> {
+    pub fn new(client: &'a Client, asset: String, amount: Amount, transfer_type: MarginTransferType) -> Self {
+        Self {
+            client,
+            playload: MarginTransferRequest {
+                asset,
+                amount,
+                transfer_type,
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<TransactionId> {
+        self.client
+            .post_signed_p("/sapi/v1/margin/transfer", &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/// 杠杆逐仓账户划转
+/// https://binance-docs.github.io/apidocs/spot/cn/#margin-4
+pub struct IsolatedMarginTransferBuilder<'a> {
+    client: &'a Client,
+    playload: IsolatedMarginTransferRequest,
+    recv_window: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginTransferRequest {
+    pub asset: String,
+    pub symbol: String,
+    pub amount: Amount,
+    pub trans_from: IsolatedMarginTransferType,
+    pub trans_to: IsolatedMarginTransferType,
+}
+
+impl<'a> IsolatedMarginTransferBuilder<'a> {
+    pub fn new(
+        client: &'a Client,
+        asset: String,
+        symbol: String,
+        amount: Amount,
+        trans_from: IsolatedMarginTransferType,
+        trans_to: IsolatedMarginTransferType,
+    ) -> Self {
+        Self {
+            client,
+            playload: IsolatedMarginTransferRequest {
+                asset,
+                symbol,
+                amount,
+                trans_from,
+                trans_to,
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<TransactionId> {
+        self.client
+            .post_signed_p("/sapi/v1/margin/isolated/transfer", &self.playload, self.recv_window)
+            .await
+    }
+}
@@ -0,0 +1,554 @@
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+
+use crate::client::Client;
+use crate::errors::*;
+use crate::rest_model::*;
+use crate::util::*;
+
+/// 查询杠杆账户详情
+/// https://binance-docs.github.io/apidocs/spot/cn/#user_data-4
+pub struct QueryMarginAccountBuilder<'a> {
+    client: &'a Client,
+    recv_window: u64,
+}
+
+impl<'a> QueryMarginAccountBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self { Self { client, recv_window: 0 } }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    fn get_params(&self) -> Result<String> {
+        let parameters = [("", "")];
+        build_signed_request(parameters, self.recv_window)
+    }
+
+    pub async fn send(self) -> Result<MarginAccountDetails> {
+        let data = self.get_params()?;
+        self.client.get_signed("/sapi/v1/margin/account", data.as_str()).await
+    }
+}
+
+/// 查询杠杆逐仓账户信息
+/// https://binance-docs.github.io/apidocs/spot/cn/#user_data-21
+pub struct QueryIsolatedMarginAccountBuilder<'a> {
+    client: &'a Client,
+    symbols: Vec<String>,
+    recv_window: u64,
+}
+
+impl<'a> QueryIsolatedMarginAccountBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            symbols: vec![],
+            recv_window: 0,
+        }
+    }
+
+    /// Only return these isolated symbols, at most 5
+    pub fn symbols(mut self, symbols: Vec<String>) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    fn get_params(&self) -> Result<String> {
+        let symbols = (!self.symbols.is_empty()).then(|| ("symbols", self.symbols.join(",")));
+        build_signed_request(symbols, self.recv_window)
+    }
+
+    pub async fn send(self) -> Result<IsolatedMarginAccountDetails> {
+        let data = self.get_params()?;
+        self.client
+            .get_signed("/sapi/v1/margin/isolated/account", data.as_str())
+            .await
+    }
+}
+
+/// 查询杠杆账户订单
+/// https://binance-docs.github.io/apidocs/spot/cn/#user_data-5
+pub struct QueryMarginOrderBuilder<'a> {
+    client: &'a Client,
+    playload: MarginOrderQuery,
+    recv_window: u64,
+}
+
+impl<'a> QueryMarginOrderBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: MarginOrderQuery {
+                symbol,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn order_id(mut self, order_id: String) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn orig_client_order_id(mut self, orig_client_order_id: String) -> Self {
+        self.playload.orig_client_order_id = Some(orig_client_order_id);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<MarginOrderState> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client.get_signed("/sapi/v1/margin/order", data.as_str()).await
+    }
+}
+
+/// 查询杠杆账户挂单
+/// https://binance-docs.github.io/apidocs/spot/cn/#user_data-6
+pub struct QueryMarginOpenOrdersBuilder<'a> {
+    client: &'a Client,
+    playload: MarginPairQuery,
+    recv_window: u64,
+}
+
+impl<'a> QueryMarginOpenOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: MarginPairQuery {
+                symbol,
+                is_isolated: None,
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<MarginOrderState>> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client
+            .get_signed("/sapi/v1/margin/openOrders", data.as_str())
+            .await
+    }
+}
+
+/// 查询杠杆账户所有订单
+/// https://binance-docs.github.io/apidocs/spot/cn/#user_data-7
+pub struct QueryMarginAllOrdersBuilder<'a> {
+    client: &'a Client,
+    playload: MarginAllOrdersRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAllOrdersRequest {
+    pub symbol: String,
+    pub is_isolated: Option<String>,
+    pub order_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<u16>,
+}
+
+impl<'a> QueryMarginAllOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: MarginAllOrdersRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn order_id(mut self, order_id: u64) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<MarginOrderState>> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client.get_signed("/sapi/v1/margin/allOrders", data.as_str()).await
+    }
+}
+
+/// 查询杠杆账户成交历史
+/// https://binance-docs.github.io/apidocs/spot/cn/#user_data-8
+pub struct QueryMarginTradesBuilder<'a> {
+    client: &'a Client,
+    playload: MarginTradesRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTradesRequest {
+    pub symbol: String,
+    pub is_isolated: Option<String>,
+    pub order_id: Option<u64>,
+    pub from_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<u16>,
+}
+
+impl<'a> QueryMarginTradesBuilder<'a> {
+    pub(crate) fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: MarginTradesRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn order_id(mut self, order_id: u64) -> Self {
+        self.playload.order_id = Some(order_id);
+        self
+    }
+
+    pub fn from_id(mut self, from_id: u64) -> Self {
+        self.playload.from_id = Some(from_id);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<OwnTradesState>> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client.get_signed("/sapi/v1/margin/myTrades", data.as_str()).await
+    }
+}
+
+/// 查询杠杆账户OCO订单
+/// https://binance-docs.github.io/apidocs/spot/cn/#oco-user_data
+pub struct QueryMarginOCOOrderBuilder<'a> {
+    client: &'a Client,
+    playload: MarginOCOOrderQuery,
+    recv_window: u64,
+}
+
+impl<'a> QueryMarginOCOOrderBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            playload: MarginOCOOrderQuery::default(),
+            recv_window: 0,
+        }
+    }
+
+    /// Required for isolated margin
+    pub fn symbol(mut self, symbol: String) -> Self {
+        self.playload.symbol = Some(symbol);
+        self
+    }
+
+    pub fn order_list_id(mut self, order_list_id: String) -> Self {
+        self.playload.order_list_id = Some(order_list_id);
+        self
+    }
+
+    pub fn orig_client_order_id(mut self, orig_client_order_id: String) -> Self {
+        self.playload.orig_client_order_id = Some(orig_client_order_id);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<MarginOCOOrderResult> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client.get_signed("/sapi/v1/margin/orderList", data.as_str()).await
+    }
+}
+
+/// 查询杠杆账户所有OCO订单
+/// https://binance-docs.github.io/apidocs/spot/cn/#oco-user_data-2
+pub struct QueryAllMarginOCOOrdersBuilder<'a> {
+    client: &'a Client,
+    playload: OCORecordsQuery,
+    recv_window: u64,
+}
+
+impl<'a> QueryAllMarginOCOOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            playload: OCORecordsQuery::default(),
+            recv_window: 0,
+        }
+    }
+
+    /// Required for isolated margin
+    pub fn symbol(mut self, symbol: String) -> Self {
+        self.playload.symbol = Some(symbol);
+        self
+    }
+
+    pub fn from_id(mut self, from_id: u64) -> Self {
+        self.playload.from_id = Some(from_id);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    pub fn limit(mut self, limit: u8) -> Self {
+        self.playload.limit = Some(limit);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<MarginOCOOrderResult>> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client
+            .get_signed("/sapi/v1/margin/allOrderList", data.as_str())
+            .await
+    }
+}
+
+/// 查询杠杆账户OCO挂单
+/// https://binance-docs.github.io/apidocs/spot/cn/#oco-user_data-3
+pub struct QueryOpenMarginOCOOrdersBuilder<'a> {
+    client: &'a Client,
+    playload: OpenMarginOCOOrdersRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenMarginOCOOrdersRequest {
+    pub symbol: Option<String>,
+    pub is_isolated: Option<String>,
+}
+
+impl<'a> QueryOpenMarginOCOOrdersBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            playload: OpenMarginOCOOrdersRequest::default(),
+            recv_window: 0,
+        }
+    }
+
+    /// Required for isolated margin
+    pub fn symbol(mut self, symbol: String) -> Self {
+        self.playload.symbol = Some(symbol);
+        self
+    }
+
+    pub fn isolated(mut self, is_isolated: bool) -> Self {
+        self.playload.is_isolated = Some(bool_to_string(is_isolated));
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<Vec<MarginOCOOrderResult>> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client
+            .get_signed("/sapi/v1/margin/openOrderList", data.as_str())
+            .await
+    }
+}
+
+/// 查询杠杆账户借贷、还贷、划转、利息及强平记录
+/// https://binance-docs.github.io/apidocs/spot/cn/#user_data
+pub struct QueryMarginRecordsBuilder<'a, R> {
+    client: &'a Client,
+    endpoint: &'static str,
+    playload: MarginRecordsRequest,
+    recv_window: u64,
+    _record: PhantomData<R>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginRecordsRequest {
+    /// Required for loan and repay records
+    pub asset: Option<String>,
+    pub isolated_symbol: Option<String>,
+    pub tx_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    /// The page, starting from 1
+    pub current: Option<u16>,
+    /// Page size, 10 by default and at most 100
+    pub size: Option<u8>,
+    /// Only used for transfer records
+    #[serde(rename = "type")]
+    pub transfer_type: Option<TransferType>,
+    /// Query records older than 6 months, only used for interest records
+    pub archived: Option<bool>,
+}
+
+impl<'a, R: DeserializeOwned> QueryMarginRecordsBuilder<'a, R> {
+    pub(crate) fn new(client: &'a Client, endpoint: &'static str) -> Self {
+        Self {
+            client,
+            endpoint,
+            playload: MarginRecordsRequest::default(),
+            recv_window: 0,
+            _record: PhantomData,
+        }
+    }
+
+    pub fn asset(mut self, asset: String) -> Self {
+        self.playload.asset = Some(asset);
+        self
+    }
+
+    pub fn isolated_symbol(mut self, isolated_symbol: String) -> Self {
+        self.playload.isolated_symbol = Some(isolated_symbol);
+        self
+    }
+
+    pub fn tx_id(mut self, tx_id: u64) -> Self {
+        self.playload.tx_id = Some(tx_id);
+        self
+    }
+
+    pub fn start_time(mut self, start_time: u64) -> Self {
+        self.playload.start_time = Some(start_time);
+        self
+    }
+
+    pub fn end_time(mut self, end_time: u64) -> Self {
+        self.playload.end_time = Some(end_time);
+        self
+    }
+
+    pub fn current(mut self, current: u16) -> Self {
+        self.playload.current = Some(current);
+        self
+    }
+
+    pub fn size(mut self, size: u8) -> Self {
+        self.playload.size = Some(size);
+        self
+    }
+
+    pub fn transfer_type(mut self, transfer_type: TransferType) -> Self {
+        self.playload.transfer_type = Some(transfer_type);
+        self
+    }
+
+    pub fn archived(mut self, archived: bool) -> Self {
+        self.playload.archived = Some(archived);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<RecordsQueryResult<R>> {
+        let data = build_signed_request_p(&self.playload, self.recv_window)?;
+        self.client.get_signed(self.endpoint, data.as_str()).await
+    }
+}
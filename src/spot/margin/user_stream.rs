@@ -0,0 +1,49 @@
+use crate::client::Client;
+use crate::errors::*;
+use crate::rest_model::{Success, UserDataStream};
+
+static SAPI_USER_DATA_STREAM: &str = "/sapi/v1/userDataStream";
+static SAPI_USER_DATA_STREAM_ISOLATED: &str = "/sapi/v1/userDataStream/isolated";
+
+/// 杠杆账户用户数据流的listen key
+/// https://binance-docs.github.io/apidocs/spot/cn/#listen-key-2
+pub struct MarginUserStreamBuilder<'a> {
+    client: &'a Client,
+    symbol: Option<String>,
+}
+
+impl<'a> MarginUserStreamBuilder<'a> {
+    pub(crate) fn new(client: &'a Client) -> Self { Self { client, symbol: None } }
+
+    /// Uses the listen key of the isolated margin account of `symbol`
+    pub fn isolated_symbol(mut self, symbol: String) -> Self {
+        self.symbol = Some(symbol);
+        self
+    }
+
+    fn endpoint(&self) -> &'static str {
+        if self.symbol.is_some() {
+            SAPI_USER_DATA_STREAM_ISOLATED
+        } else {
+            SAPI_USER_DATA_STREAM
+        }
+    }
+
+    /// Creates a listen key, valid for 60 minutes unless kept alive
+    pub async fn start(&self) -> Result<UserDataStream> {
+        self.client.post(self.endpoint(), self.symbol.as_deref()).await
+    }
+
+    /// Extends the validity of the listen key by 60 minutes
+    pub async fn keep_alive(&self, listen_key: &str) -> Result<Success> {
+        self.client
+            .put(self.endpoint(), listen_key, self.symbol.as_deref())
+            .await
+    }
+
+    pub async fn close(&self, listen_key: &str) -> Result<Success> {
+        self.client
+            .delete(self.endpoint(), listen_key, self.symbol.as_deref())
+            .await
+    }
+}
@@ -1,4 +1,5 @@
 use crate::client::Client;
+use crate::rest_model::KlineInterval;
 
 use super::depth::DepthBuilder;
 use super::klines::KlinesBuilder;
@@ -90,18 +91,38 @@ impl Market {
     /// ``` rust
     /// use binance::api::Binance;
     /// use binance::Spot;
+    /// use binance::rest_model::KlineInterval;
     /// let spot:Spot = Binance::new(None,None);
-    /// let builder = spot.market.get_klines("BTCUSDT","1m").limit(50);
+    /// let builder = spot.market.get_klines("BTCUSDT",KlineInterval::Minutes1).limit(50);
     /// let data = tokio_test::block_on(builder.send());
     /// assert!(data.is_ok(), "{:?}", data);
     /// ```
     /// https://binance-docs.github.io/apidocs/spot/cn/#k
-    pub fn get_klines<S1, S2>(&self, symbol: S1, interval: S2) -> KlinesBuilder
+    pub fn get_klines<S>(&self, symbol: S, interval: KlineInterval) -> KlinesBuilder<'_>
         where
-            S1: AsRef<str>,
-            S2: AsRef<str>,
+            S: AsRef<str>,
+    {
+        KlinesBuilder::new(&self.client, symbol.as_ref().to_string(), interval)
+    }
+
+    /// # UI K线数据
+    /// Same parameters and response as `get_klines`, with candlesticks modified for presentation
+    /// ## Example
+    /// ``` rust
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// use binance::rest_model::KlineInterval;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let builder = spot.market.get_ui_klines("BTCUSDT",KlineInterval::Hours1).time_zone("+8").limit(50);
+    /// let data = tokio_test::block_on(builder.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/en/#uiklines
+    pub fn get_ui_klines<S>(&self, symbol: S, interval: KlineInterval) -> KlinesBuilder<'_>
+        where
+            S: AsRef<str>,
     {
-        KlinesBuilder::new(&self.client, symbol.as_ref().to_string(), interval.as_ref().to_string())
+        KlinesBuilder::new(&self.client, symbol.as_ref().to_string(), interval).ui()
     }
 
     /// # 24hr 价格变动情况
@@ -209,6 +230,7 @@ impl Market {
 mod tests {
     use crate::api::Binance;
     use crate::config::Config;
+    use crate::rest_model::KlineInterval;
     use crate::Spot;
 
     #[test]
@@ -230,7 +252,7 @@ mod tests {
         let resp = tokio_test::block_on(builder);
         println!("{:?}", resp.unwrap());
 
-        let builder = spot.market.get_klines("ETHUSDT", "1m").limit(5).send();
+        let builder = spot.market.get_klines("ETHUSDT", KlineInterval::Minutes1).limit(5).send();
         let resp = tokio_test::block_on(builder);
         println!("{:?}", resp.unwrap());
 
@@ -259,4 +281,35 @@ mod tests {
         println!("{:?}", resp.unwrap());
         //assert!(depth.is_ok(), "{:?}", depth);
     }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn malformed_klines_are_errors() {
+        use crate::errors::Error;
+        use crate::mock::{MockResponse, MockServer};
+        use hyper::Method;
+        use serde_json::json;
+
+        let server = MockServer::start().await;
+        let spot: Spot = Binance::new_with_config(None, None, &server.config());
+        let row = json!([1499040000000i64, "0.01634790", "0.80000000", "0.01575800", "0.01577100", "148976.11427815",
+            1499644799999i64, "2434.19055334", 308, "1756.87402397", "28.46694368", "0"]);
+        server.mock(Method::GET, "/api/v3/uiKlines", MockResponse::json(json!([row])));
+        let klines = spot
+            .market
+            .get_ui_klines("BNBBTC", KlineInterval::Hours1)
+            .time_zone("+8")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(klines[0].close_time, 1499644799999);
+        assert_eq!(klines[0].number_of_trades, 308);
+        let request = &server.requests()[0];
+        assert_eq!(request.param("interval"), Some("1h"));
+        assert_eq!(request.param("timeZone"), Some("+8"));
+
+        server.mock(Method::GET, "/api/v3/klines", MockResponse::json(json!([[1499040000000i64, "0.01634790"]])));
+        let klines = spot.market.get_klines("BNBBTC", KlineInterval::Minutes1).send().await;
+        assert!(matches!(&klines, Err(Error::ReqError(e)) if e.is_decode()), "{:?}", klines);
+    }
 }
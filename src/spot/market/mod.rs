@@ -3,6 +3,6 @@ pub use market::Market;
 
 
 mod depth;
-mod klines;
+pub mod klines;
 mod tickers;
 mod trades;
\ No newline at end of file
@@ -8,3 +8,4 @@ pub mod account;
 pub mod margin;
 pub mod market;
 pub mod trade;
+pub mod ws_api;
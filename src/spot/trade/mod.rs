@@ -1,4 +1,11 @@
 pub mod trade;
+pub use order::{
+    CancelOrderRequest, CancelReplaceRequest, Fill, OrderCanceled, OrderCanceledReplaced, OrderRequest, PlaceOrder,
+};
+pub use order_list::{
+    CancelOrderListRequest, ListOrder, OCOOrderRequest, OTOCOOrderRequest, OTOOrderRequest, OrderList, OrderListReport,
+};
 pub use trade::Trade;
 
-mod order;
\ No newline at end of file
+mod order;
+mod order_list;
@@ -2,6 +2,7 @@ use rust_decimal::Decimal;
 
 use crate::client::Client;
 use crate::errors::*;
+use crate::filters::OrderFilters;
 use crate::rest_model::*;
 use crate::util::*;
 
@@ -11,6 +12,7 @@ pub struct TestPlaceOrderBuilder<'a> {
     client: &'a Client,
     playload: OrderRequest,
     recv_window: u64,
+    filters: Option<OrderFilters>,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
@@ -21,19 +23,36 @@ pub struct OrderRequest {
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub time_in_force: Option<TimeInForce>,
-    pub quantity: Option<f64>,
-    pub quote_order_qty: Option<f64>,
-    pub price: Option<f64>,
+    pub quantity: Option<Amount>,
+    pub quote_order_qty: Option<Amount>,
+    pub price: Option<Amount>,
     /// A unique id for the order, automatically generated if not sent.
     pub new_client_order_id: Option<String>,
     /// Used with stop loss, stop loss limit, take profit and take profit limit order types.
-    pub stop_price: Option<f64>,
+    pub stop_price: Option<Amount>,
     /// Used with limit, stop loss limit and take profit limit to create an iceberg order.
-    pub iceberg_qty: Option<f64>,
+    pub iceberg_qty: Option<Amount>,
     /// Set the response json, market and limit default to full others to ack.
     pub new_order_resp_type: Option<OrderResponse>,
 }
 
+impl OrderRequest {
+    pub(crate) fn apply_filters(&mut self, filters: &OrderFilters) -> Result<()> {
+        let market = matches!(
+            self.order_type,
+            OrderType::Market | OrderType::StopLoss | OrderType::TakeProfit
+        );
+        filters.apply(
+            &self.side,
+            market,
+            &mut self.price,
+            &mut self.stop_price,
+            &mut self.quantity,
+        )?;
+        Ok(())
+    }
+}
+
 impl<'a> TestPlaceOrderBuilder<'a> {
     pub fn new(client: &'a Client, symbol: String, side: OrderSide, order_type: OrderType) -> Self {
         Self {
@@ -45,6 +64,7 @@ impl<'a> TestPlaceOrderBuilder<'a> {
                 ..Default::default()
             },
             recv_window: 0,
+            filters: None,
         }
     }
 
@@ -53,17 +73,17 @@ impl<'a> TestPlaceOrderBuilder<'a> {
         self
     }
 
-    pub fn quantity(mut self, quantity: f64) -> Self {
+    pub fn quantity(mut self, quantity: Amount) -> Self {
         self.playload.quantity = Some(quantity);
         self
     }
 
-    pub fn quote_order_qty(mut self, quote_order_qty: f64) -> Self {
+    pub fn quote_order_qty(mut self, quote_order_qty: Amount) -> Self {
         self.playload.quote_order_qty = Some(quote_order_qty);
         self
     }
 
-    pub fn price(mut self, price: f64) -> Self {
+    pub fn price(mut self, price: Amount) -> Self {
         self.playload.price = Some(price);
         self
     }
@@ -73,12 +93,12 @@ impl<'a> TestPlaceOrderBuilder<'a> {
         self
     }
 
-    pub fn stop_price(mut self, stop_price: f64) -> Self {
+    pub fn stop_price(mut self, stop_price: Amount) -> Self {
         self.playload.stop_price = Some(stop_price);
         self
     }
 
-    pub fn iceberg_qty(mut self, iceberg_qty: f64) -> Self {
+    pub fn iceberg_qty(mut self, iceberg_qty: Amount) -> Self {
         self.playload.iceberg_qty = Some(iceberg_qty);
         self
     }
@@ -93,7 +113,17 @@ impl<'a> TestPlaceOrderBuilder<'a> {
         self
     }
 
-    pub async fn send(self) -> Result<Empty> {
+    /// Validates the price and quantity against the filters of the symbol before sending,
+    /// rounding them to the tick and step sizes if the filters round.
+    pub fn filters(mut self, filters: OrderFilters) -> Self {
+        self.filters = Some(filters);
+        self
+    }
+
+    pub async fn send(mut self) -> Result<Empty> {
+        if let Some(filters) = &self.filters {
+            self.playload.apply_filters(filters)?;
+        }
         self.client
             .post_signed_p("/api/v3/order/test", &self.playload, self.recv_window)
             .await
@@ -106,6 +136,7 @@ pub struct PlaceOrderBuilder<'a> {
     client: &'a Client,
     playload: OrderRequest,
     recv_window: u64,
+    filters: Option<OrderFilters>,
 }
 
 impl<'a> PlaceOrderBuilder<'a> {
@@ -119,6 +150,7 @@ impl<'a> PlaceOrderBuilder<'a> {
                 ..Default::default()
             },
             recv_window: 0,
+            filters: None,
         }
     }
 
@@ -127,17 +159,17 @@ impl<'a> PlaceOrderBuilder<'a> {
         self
     }
 
-    pub fn quantity(mut self, quantity: f64) -> Self {
+    pub fn quantity(mut self, quantity: Amount) -> Self {
         self.playload.quantity = Some(quantity);
         self
     }
 
-    pub fn quote_order_qty(mut self, quote_order_qty: f64) -> Self {
+    pub fn quote_order_qty(mut self, quote_order_qty: Amount) -> Self {
         self.playload.quote_order_qty = Some(quote_order_qty);
         self
     }
 
-    pub fn price(mut self, price: f64) -> Self {
+    pub fn price(mut self, price: Amount) -> Self {
         self.playload.price = Some(price);
         self
     }
@@ -147,12 +179,12 @@ impl<'a> PlaceOrderBuilder<'a> {
         self
     }
 
-    pub fn stop_price(mut self, stop_price: f64) -> Self {
+    pub fn stop_price(mut self, stop_price: Amount) -> Self {
         self.playload.stop_price = Some(stop_price);
         self
     }
 
-    pub fn iceberg_qty(mut self, iceberg_qty: f64) -> Self {
+    pub fn iceberg_qty(mut self, iceberg_qty: Amount) -> Self {
         self.playload.iceberg_qty = Some(iceberg_qty);
         self
     }
@@ -162,7 +194,17 @@ impl<'a> PlaceOrderBuilder<'a> {
         self
     }
 
-    pub async fn send(self) -> Result<PlaceOrder> {
+    /// Validates the price and quantity against the filters of the symbol before sending,
+    /// rounding them to the tick and step sizes if the filters round.
+    pub fn filters(mut self, filters: OrderFilters) -> Self {
+        self.filters = Some(filters);
+        self
+    }
+
+    pub async fn send(mut self) -> Result<PlaceOrder> {
+        if let Some(filters) = &self.filters {
+            self.playload.apply_filters(filters)?;
+        }
         self.client
             .post_signed_p("/api/v3/order", &self.playload, self.recv_window)
             .await
@@ -275,15 +317,15 @@ pub struct CancelReplaceRequest {
     pub order_type: OrderType,
     pub cancel_replace_mode: CancelReplaceMode,
     pub time_in_force: Option<TimeInForce>,
-    pub quantity: Option<f64>,
-    pub quote_order_qty: Option<f64>,
-    pub price: Option<f64>,
+    pub quantity: Option<Amount>,
+    pub quote_order_qty: Option<Amount>,
+    pub price: Option<Amount>,
     pub cancel_new_client_order_id: Option<String>,
     pub cancel_orig_client_order_id: Option<String>,
     pub cancel_order_id: Option<String>,
     pub new_client_order_id: Option<String>,
-    pub stop_price: Option<f64>,
-    pub iceberg_qty: Option<f64>,
+    pub stop_price: Option<Amount>,
+    pub iceberg_qty: Option<Amount>,
     pub new_order_resp_type: Option<OrderResponse>,
 }
 
@@ -313,17 +355,17 @@ impl<'a> CancelReplaceBuilder<'a> {
         self
     }
 
-    pub fn quantity(mut self, quantity: f64) -> Self {
+    pub fn quantity(mut self, quantity: Amount) -> Self {
         self.playload.quantity = Some(quantity);
         self
     }
 
-    pub fn quote_order_qty(mut self, quote_order_qty: f64) -> Self {
+    pub fn quote_order_qty(mut self, quote_order_qty: Amount) -> Self {
         self.playload.quote_order_qty = Some(quote_order_qty);
         self
     }
 
-    pub fn price(mut self, price: f64) -> Self {
+    pub fn price(mut self, price: Amount) -> Self {
         self.playload.price = Some(price);
         self
     }
@@ -348,12 +390,12 @@ impl<'a> CancelReplaceBuilder<'a> {
         self
     }
 
-    pub fn stop_price(mut self, stop_price: f64) -> Self {
+    pub fn stop_price(mut self, stop_price: Amount) -> Self {
         self.playload.stop_price = Some(stop_price);
         self
     }
 
-    pub fn iceberg_qty(mut self, iceberg_qty: f64) -> Self {
+    pub fn iceberg_qty(mut self, iceberg_qty: Amount) -> Self {
         self.playload.iceberg_qty = Some(iceberg_qty);
         self
     }
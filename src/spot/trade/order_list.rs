@@ -0,0 +1,397 @@
+use crate::client::Client;
+use crate::errors::*;
+use crate::rest_model::*;
+use crate::util::*;
+
+/// An order of an order list, its parameters are sent prefixed by its place in the list, e.g. `aboveType`
+///
+/// `side` and `quantity` are only sent for the orders of an OTO list and the working order of an OTOCO list,
+/// the other orders use the side and quantity of the list.
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListOrder {
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub side: Option<OrderSide>,
+    pub quantity: Option<Amount>,
+    /// A unique id for the order, automatically generated if not sent.
+    pub client_order_id: Option<String>,
+    pub price: Option<Amount>,
+    pub stop_price: Option<Amount>,
+    /// Trailing delta in basis points, for trailing stop orders
+    pub trailing_delta: Option<u64>,
+    pub iceberg_qty: Option<Amount>,
+    pub time_in_force: Option<TimeInForce>,
+}
+
+impl ListOrder {
+    fn query(&self, prefix: &str, with_side_and_quantity: bool) -> Result<String> {
+        let mut order = self.clone();
+        if !with_side_and_quantity {
+            order.side = None;
+            order.quantity = None;
+        }
+        let query = qs::to_string(&order)?;
+        let params: Vec<String> = query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let mut chars = param.chars();
+                let first = chars.next().map(|c| c.to_ascii_uppercase()).unwrap_or_default();
+                format!("{prefix}{first}{}", chars.as_str())
+            })
+            .collect();
+        Ok(params.join("&"))
+    }
+}
+
+/// Signed query of an order list, the list parameters followed by those of its orders
+fn order_list_query<P: serde::Serialize>(
+    payload: &P,
+    orders: &[(&str, Option<&ListOrder>, bool)],
+    recv_window: u64,
+) -> Result<String> {
+    let mut query = build_signed_request_p(payload, recv_window)?;
+    for (prefix, order, with_side_and_quantity) in orders {
+        if let Some(order) = order {
+            query.push('&');
+            query.push_str(&order.query(prefix, *with_side_and_quantity)?);
+        }
+    }
+    Ok(query)
+}
+
+/// 下OCO订单
+/// https://binance-docs.github.io/apidocs/spot/en/#new-order-list-oco-trade
+pub struct PlaceOCOOrderBuilder<'a> {
+    client: &'a Client,
+    playload: OCOOrderRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OCOOrderRequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub side: OrderSide,
+    pub quantity: Amount,
+    /// The order above the market price, a `LimitMaker`, `StopLoss` or `StopLossLimit` order
+    #[serde(skip)]
+    pub above: ListOrder,
+    /// The order below the market price, a `LimitMaker`, `StopLoss` or `StopLossLimit` order
+    #[serde(skip)]
+    pub below: ListOrder,
+    pub new_order_resp_type: Option<OrderResponse>,
+}
+
+impl<'a> PlaceOCOOrderBuilder<'a> {
+    pub fn new(client: &'a Client, symbol: String, side: OrderSide, quantity: Amount) -> Self {
+        Self {
+            client,
+            playload: OCOOrderRequest {
+                symbol,
+                side,
+                quantity,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: String) -> Self {
+        self.playload.list_client_order_id = Some(list_client_order_id);
+        self
+    }
+
+    pub fn above(mut self, above: ListOrder) -> Self {
+        self.playload.above = above;
+        self
+    }
+
+    pub fn below(mut self, below: ListOrder) -> Self {
+        self.playload.below = below;
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponse) -> Self {
+        self.playload.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<OrderList> {
+        let orders = [
+            ("above", Some(&self.playload.above), false),
+            ("below", Some(&self.playload.below), false),
+        ];
+        let data = order_list_query(&self.playload, &orders, self.recv_window)?;
+        self.client.post_signed("/api/v3/orderList/oco", data.as_str()).await
+    }
+}
+
+/// 下OTO订单，挂单成交后下单
+/// https://binance-docs.github.io/apidocs/spot/en/#new-order-list-oto-trade
+pub struct PlaceOTOOrderBuilder<'a> {
+    client: &'a Client,
+    playload: OTOOrderRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OTOOrderRequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    /// The order placed first, a `Limit` or `LimitMaker` order
+    #[serde(skip)]
+    pub working: ListOrder,
+    /// The order placed once the working order is filled
+    #[serde(skip)]
+    pub pending: ListOrder,
+    pub new_order_resp_type: Option<OrderResponse>,
+}
+
+impl<'a> PlaceOTOOrderBuilder<'a> {
+    pub fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: OTOOrderRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: String) -> Self {
+        self.playload.list_client_order_id = Some(list_client_order_id);
+        self
+    }
+
+    pub fn working(mut self, working: ListOrder) -> Self {
+        self.playload.working = working;
+        self
+    }
+
+    pub fn pending(mut self, pending: ListOrder) -> Self {
+        self.playload.pending = pending;
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponse) -> Self {
+        self.playload.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<OrderList> {
+        let orders = [
+            ("working", Some(&self.playload.working), true),
+            ("pending", Some(&self.playload.pending), true),
+        ];
+        let data = order_list_query(&self.playload, &orders, self.recv_window)?;
+        self.client.post_signed("/api/v3/orderList/oto", data.as_str()).await
+    }
+}
+
+/// 下OTOCO订单，挂单成交后下OCO订单
+/// https://binance-docs.github.io/apidocs/spot/en/#new-order-list-otoco-trade
+pub struct PlaceOTOCOOrderBuilder<'a> {
+    client: &'a Client,
+    playload: OTOCOOrderRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OTOCOOrderRequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    /// The order placed first, a `Limit` or `LimitMaker` order
+    #[serde(skip)]
+    pub working: ListOrder,
+    /// Side of the OCO orders placed once the working order is filled
+    pub pending_side: OrderSide,
+    /// Quantity of the OCO orders placed once the working order is filled
+    pub pending_quantity: Amount,
+    #[serde(skip)]
+    pub pending_above: ListOrder,
+    #[serde(skip)]
+    pub pending_below: Option<ListOrder>,
+    pub new_order_resp_type: Option<OrderResponse>,
+}
+
+impl<'a> PlaceOTOCOOrderBuilder<'a> {
+    pub fn new(client: &'a Client, symbol: String, pending_side: OrderSide, pending_quantity: Amount) -> Self {
+        Self {
+            client,
+            playload: OTOCOOrderRequest {
+                symbol,
+                pending_side,
+                pending_quantity,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: String) -> Self {
+        self.playload.list_client_order_id = Some(list_client_order_id);
+        self
+    }
+
+    pub fn working(mut self, working: ListOrder) -> Self {
+        self.playload.working = working;
+        self
+    }
+
+    pub fn pending_above(mut self, pending_above: ListOrder) -> Self {
+        self.playload.pending_above = pending_above;
+        self
+    }
+
+    pub fn pending_below(mut self, pending_below: ListOrder) -> Self {
+        self.playload.pending_below = Some(pending_below);
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponse) -> Self {
+        self.playload.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<OrderList> {
+        let orders = [
+            ("working", Some(&self.playload.working), true),
+            ("pendingAbove", Some(&self.playload.pending_above), false),
+            ("pendingBelow", self.playload.pending_below.as_ref(), false),
+        ];
+        let data = order_list_query(&self.playload, &orders, self.recv_window)?;
+        self.client.post_signed("/api/v3/orderList/otoco", data.as_str()).await
+    }
+}
+
+/// 取消订单列表
+/// https://binance-docs.github.io/apidocs/spot/en/#cancel-order-list-trade
+pub struct CancelOrderListBuilder<'a> {
+    client: &'a Client,
+    playload: CancelOrderListRequest,
+    recv_window: u64,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderListRequest {
+    pub symbol: String,
+    pub order_list_id: Option<u64>,
+    pub list_client_order_id: Option<String>,
+    pub new_client_order_id: Option<String>,
+}
+
+impl<'a> CancelOrderListBuilder<'a> {
+    pub fn new(client: &'a Client, symbol: String) -> Self {
+        Self {
+            client,
+            playload: CancelOrderListRequest {
+                symbol,
+                ..Default::default()
+            },
+            recv_window: 0,
+        }
+    }
+
+    pub fn order_list_id(mut self, order_list_id: u64) -> Self {
+        self.playload.order_list_id = Some(order_list_id);
+        self
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: String) -> Self {
+        self.playload.list_client_order_id = Some(list_client_order_id);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: String) -> Self {
+        self.playload.new_client_order_id = Some(new_client_order_id);
+        self
+    }
+
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    pub async fn send(self) -> Result<OrderList> {
+        self.client
+            .delete_signed_p("/api/v3/orderList", &self.playload, self.recv_window)
+            .await
+    }
+}
+
+/***
+ * response body
+ ***/
+
+/// An order list, queries of order lists have no order reports
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: i64,
+    pub contingency_type: ContingencyType,
+    pub list_status_type: OCOStatus,
+    pub list_order_status: OCOOrderStatus,
+    pub list_client_order_id: String,
+    pub transaction_time: u64,
+    pub symbol: String,
+    pub orders: Vec<OCOOrderDetail>,
+    #[serde(default)]
+    pub order_reports: Vec<OrderListReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListReport {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    /// Set in the reports of a cancellation
+    pub orig_client_order_id: Option<String>,
+    pub transact_time: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Amount,
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Amount,
+    #[serde(with = "string_or_float")]
+    pub cummulative_quote_qty: Amount,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type")]
+    pub order_type: OrderType,
+    pub side: OrderSide,
+    #[serde(default, with = "string_or_float_opt")]
+    pub stop_price: Option<Amount>,
+    #[serde(default, with = "string_or_float_opt")]
+    pub iceberg_qty: Option<Amount>,
+    /// -1 for orders of the list that are not working yet
+    pub working_time: Option<i64>,
+    pub self_trade_prevention_mode: Option<String>,
+}
@@ -4,6 +4,7 @@ use crate::rest_model::*;
 use super::order::{
     CancelAllOpenOrdersBuilder, CancelOrderBuilder, CancelReplaceBuilder, PlaceOrderBuilder, TestPlaceOrderBuilder,
 };
+use super::order_list::{CancelOrderListBuilder, PlaceOCOOrderBuilder, PlaceOTOCOOrderBuilder, PlaceOTOOrderBuilder};
 
 pub struct Trade {
     pub client: Client,
@@ -16,11 +17,12 @@ impl Trade {
     /// ``` rust
     /// use binance::api::Binance;
     /// use binance::Spot;
+    /// use binance::rest_model::{OrderSide, OrderType, TimeInForce};
     /// let spot:Spot = Binance::new(None,None);
     /// let bulider = spot.trade.test_place_order("BTCUSDT",OrderSide::Buy,OrderType::Limit)
     ///    .time_in_force(TimeInForce::GTC)
-    ///    .price(25000.0)
-    ///    .quantity(0.001)
+    ///    .price("25000".parse().unwrap())
+    ///    .quantity("0.001".parse().unwrap());
     /// let data = tokio_test::block_on(bulider.send());
     /// assert!(data.is_ok(), "{:?}", data);
     /// ```
@@ -44,11 +46,12 @@ impl Trade {
     /// ``` rust
     /// use binance::api::Binance;
     /// use binance::Spot;
+    /// use binance::rest_model::{OrderSide, OrderType, TimeInForce};
     /// let spot:Spot = Binance::new(None,None);
     /// let bulider = spot.trade.test_place_order("BTCUSDT",OrderSide::Buy,OrderType::Limit)
     ///    .time_in_force(TimeInForce::GTC)
-    ///    .price(25000.0)
-    ///    .quantity(0.001)
+    ///    .price("25000".parse().unwrap())
+    ///    .quantity("0.001".parse().unwrap());
     /// let data = tokio_test::block_on(bulider.send());
     /// assert!(data.is_ok(), "{:?}", data);
     /// ```
@@ -72,11 +75,12 @@ impl Trade {
     /// ``` rust
     /// use binance::api::Binance;
     /// use binance::Spot;
+    /// use binance::rest_model::{OrderSide, OrderType, TimeInForce};
     /// let spot:Spot = Binance::new(None,None);
     /// let bulider = spot.trade.test_place_order("BTCUSDT",OrderSide::Buy,OrderType::Limit)
     ///    .time_in_force(TimeInForce::GTC)
-    ///    .price(25000.0)
-    ///    .quantity(0.001)
+    ///    .price("25000".parse().unwrap())
+    ///    .quantity("0.001".parse().unwrap());
     /// let data = tokio_test::block_on(bulider.send());
     /// assert!(data.is_ok(), "{:?}", data);
     /// ```
@@ -138,75 +142,232 @@ impl Trade {
             cancel_replace_mode.into(),
         )
     }
+
+    /// # 下OCO订单
+    /// ## Example
+    /// ``` rust
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// use binance::rest_model::{OrderSide, OrderType};
+    /// use binance::spot::trade::ListOrder;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.trade.place_oco_order("BTCUSDT",OrderSide::Sell,"0.001".parse().unwrap())
+    ///    .above(ListOrder { order_type: OrderType::LimitMaker, price: Some("26000".parse().unwrap()), ..Default::default() })
+    ///    .below(ListOrder { order_type: OrderType::StopLoss, stop_price: Some("24000".parse().unwrap()), ..Default::default() });
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/en/#new-order-list-oco-trade
+    pub fn place_oco_order<S1, S2>(&self, symbol: S1, side: S2, quantity: Amount) -> PlaceOCOOrderBuilder<'_>
+    where
+        S1: AsRef<str>,
+        S2: Into<OrderSide>,
+    {
+        PlaceOCOOrderBuilder::new(&self.client, symbol.as_ref().to_string(), side.into(), quantity)
+    }
+
+    /// # 下OTO订单
+    /// ## Example
+    /// ``` rust
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// use binance::rest_model::{OrderSide, OrderType};
+    /// use binance::spot::trade::ListOrder;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.trade.place_oto_order("BTCUSDT")
+    ///    .working(ListOrder { order_type: OrderType::Limit, side: Some(OrderSide::Buy), ..Default::default() })
+    ///    .pending(ListOrder { order_type: OrderType::Market, side: Some(OrderSide::Sell), ..Default::default() });
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/en/#new-order-list-oto-trade
+    pub fn place_oto_order<S>(&self, symbol: S) -> PlaceOTOOrderBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        PlaceOTOOrderBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
+
+    /// # 下OTOCO订单
+    /// ## Example
+    /// ``` rust
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// use binance::rest_model::{OrderSide, OrderType};
+    /// use binance::spot::trade::ListOrder;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.trade.place_otoco_order("BTCUSDT",OrderSide::Sell,"0.001".parse().unwrap())
+    ///    .working(ListOrder { order_type: OrderType::Limit, side: Some(OrderSide::Buy), ..Default::default() })
+    ///    .pending_above(ListOrder { order_type: OrderType::LimitMaker, price: Some("26000".parse().unwrap()), ..Default::default() })
+    ///    .pending_below(ListOrder { order_type: OrderType::StopLoss, stop_price: Some("24000".parse().unwrap()), ..Default::default() });
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/en/#new-order-list-otoco-trade
+    pub fn place_otoco_order<S1, S2>(
+        &self,
+        symbol: S1,
+        pending_side: S2,
+        pending_quantity: Amount,
+    ) -> PlaceOTOCOOrderBuilder<'_>
+    where
+        S1: AsRef<str>,
+        S2: Into<OrderSide>,
+    {
+        PlaceOTOCOOrderBuilder::new(
+            &self.client,
+            symbol.as_ref().to_string(),
+            pending_side.into(),
+            pending_quantity,
+        )
+    }
+
+    /// # 取消订单列表
+    /// ## Example
+    /// ``` rust
+    /// use binance::api::Binance;
+    /// use binance::Spot;
+    /// let spot:Spot = Binance::new(None,None);
+    /// let bulider = spot.trade.cancel_order_list("BTCUSDT").order_list_id(1);
+    /// let data = tokio_test::block_on(bulider.send());
+    /// assert!(data.is_ok(), "{:?}", data);
+    /// ```
+    /// https://binance-docs.github.io/apidocs/spot/en/#cancel-order-list-trade
+    pub fn cancel_order_list<S>(&self, symbol: S) -> CancelOrderListBuilder<'_>
+    where
+        S: AsRef<str>,
+    {
+        CancelOrderListBuilder::new(&self.client, symbol.as_ref().to_string())
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "mock"))]
 mod tests {
-    use crate::api::Binance;
-    use crate::config::Config;
+    use reqwest::Method;
+    use serde_json::json;
+
+    use crate::errors::{Error, FilterViolation};
+    use crate::filters::OrderFilters;
+    use crate::mock::MockResponse;
     use crate::rest_model::*;
-    use crate::Spot;
-    use dotenv::dotenv;
-    use std::env;
+    use crate::spot::trade::ListOrder;
+    use crate::test_util::{amount, spot};
 
-    fn env() -> (String,String) {
-        dotenv().ok();
-       (env::var("API_KEY").unwrap(), env::var("API_SECRET").unwrap())
+    #[tokio::test]
+    async fn test_test_place_order() {
+        let (server, spot) = spot().await;
+        spot.trade
+            .test_place_order("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .time_in_force(TimeInForce::GTC)
+            .price(amount("25000"))
+            .quantity(amount("0.001"))
+            .send()
+            .await
+            .unwrap();
+        let request = &server.requests()[0];
+        assert_eq!(request.path, "/api/v3/order/test");
+        assert_eq!(request.param("price"), Some("25000"));
+        assert_eq!(request.param("timeInForce"), Some("GTC"));
     }
 
-    #[test]
-    fn test_test_place_order() {
-        let (key,secret) = env();
-        let spot: Spot = Binance::new_with_config(Some(key), Some(secret), &Config::testnet());
-        let builder = spot
+    #[tokio::test]
+    async fn test_place_order() {
+        let (server, spot) = spot().await;
+        let order = spot
             .trade
-            .test_place_order("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .place_order("BTCUSDT", OrderSide::Buy, OrderType::Limit)
             .time_in_force(TimeInForce::GTC)
-            .price(25000.0)
-            .quantity(0.001)
-            .send();
-        let resp = tokio_test::block_on(builder);
-        println!("{:?}", resp.unwrap());
-    }
-
-    #[test]
-    fn test_place_order() {
-        let (key,secret) = env();
-        let spot: Spot = Binance::new_with_config(Some(key), Some(secret), &Config::testnet());
-        let builder = spot
+            .price(amount("25000"))
+            .quantity(amount("0.001"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(order.status, OrderStatus::New);
+        assert_eq!(server.requests()[0].param("quantity"), Some("0.001"));
+    }
+
+    #[tokio::test]
+    async fn orders_breaking_filters_are_not_sent() {
+        let (server, spot) = spot().await;
+        let filters = OrderFilters::from(
+            &[Filters::LotSize {
+                min_qty: 0.00001,
+                max_qty: 9000.0,
+                step_size: 0.00001,
+            }][..],
+        );
+        let order = spot
             .trade
             .place_order("BTCUSDT", OrderSide::Buy, OrderType::Limit)
             .time_in_force(TimeInForce::GTC)
-            .price(25000.0)
-            .quantity(0.001)
-            .send();
-        let resp = tokio_test::block_on(builder);
-        println!("{:?}", resp.unwrap());
-    }
-
-    #[test]
-    fn test_cancel_order() {
-        let (key,secret) = env();
-        let spot: Spot = Binance::new_with_config(Some(key), Some(secret), &Config::testnet());
-        let builder = spot.trade.cancel_order("BTCUSDT").order_id("3229491".into()).send();
-        let resp = tokio_test::block_on(builder);
-        println!("{:?}", resp.unwrap());
-    }
-
-    #[test]
-    fn test_cancel_all_open_orders() {
-        let (key,secret) = env();
-        let spot: Spot = Binance::new_with_config(Some(key), Some(secret), &Config::testnet());
-        let builder = spot.trade.cancel_all_open_orders("BTCUSDT").send();
-        let resp = tokio_test::block_on(builder);
-        println!("{:?}", resp.unwrap());
-    }
-
-    #[test]
-    fn test_cancel_replace_order() {
-        let (key,secret) = env();
-        let spot: Spot = Binance::new_with_config(Some(key), Some(secret), &Config::testnet());
-        let builder = spot
+            .price(amount("25000"))
+            .quantity(amount("0.0012345"))
+            .filters(filters.clone())
+            .send()
+            .await;
+        assert!(matches!(
+            order,
+            Err(Error::FilterViolation(FilterViolation::QuantityNotOnStep { .. }))
+        ));
+        assert!(server.requests().is_empty());
+
+        spot.trade
+            .place_order("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .time_in_force(TimeInForce::GTC)
+            .price(amount("25000"))
+            .quantity(amount("0.0012345"))
+            .filters(filters.set_rounding(true))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(server.requests()[0].param("quantity"), Some("0.00123"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order() {
+        let (server, spot) = spot().await;
+        let canceled = spot.trade.cancel_order("BTCUSDT").order_id("28".into()).send().await.unwrap();
+        assert_eq!(canceled.order_id, "28");
+        let request = &server.requests()[0];
+        assert_eq!(request.method, Method::DELETE);
+        assert_eq!(request.param("orderId"), Some("28"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_open_orders() {
+        let (_server, spot) = spot().await;
+        let canceled = spot.trade.cancel_all_open_orders("BTCUSDT").send().await.unwrap();
+        assert_eq!(canceled.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_replace_order() {
+        let (server, spot) = spot().await;
+        let order = json!({
+            "symbol": "BTCUSDT", "orderId": 29, "orderListId": -1, "clientOrderId": "test_cancel_replace_order",
+            "transactTime": 1669277163808u64, "price": "24000.00000000", "origQty": "0.00100000",
+            "executedQty": "0.00000000", "cummulativeQuoteQty": "0.00000000", "status": "NEW",
+            "timeInForce": "GTC", "type": "LIMIT", "side": "BUY", "workingTime": 1669277163808u64, "fills": [],
+            "selfTradePreventionMode": "NONE"
+        });
+        let canceled = json!({
+            "symbol": "BTCUSDT", "origClientOrderId": "6gCrw2kRUAF9CvJDGP16IP", "orderId": 28, "orderListId": -1,
+            "clientOrderId": "91fe37ce9e69c90d6358c0", "transactTime": 1669277163808u64, "price": "25000.00000000",
+            "origQty": "0.00100000", "executedQty": "0.00000000", "cummulativeQuoteQty": "0.00000000",
+            "status": "CANCELED", "timeInForce": "GTC", "type": "LIMIT", "side": "BUY",
+            "selfTradePreventionMode": "NONE"
+        });
+        server.mock(
+            Method::POST,
+            "/api/v3/order/cancelReplace",
+            MockResponse::json(json!({
+                "cancelResult": "SUCCESS",
+                "newOrderResult": "SUCCESS",
+                "cancelResponse": canceled,
+                "newOrderResponse": order
+            })),
+        );
+        let replaced = spot
             .trade
             .cancel_replace_order(
                 "BTCUSDT",
@@ -214,13 +375,138 @@ mod tests {
                 OrderType::Limit,
                 CancelReplaceMode::StopOnFailure,
             )
-            .cancel_order_id("3251081".into())
+            .cancel_order_id("28".into())
             .time_in_force(TimeInForce::GTC)
             .new_client_order_id("test_cancel_replace_order".into())
-            .price(24000.0)
-            .quantity(0.001)
-            .send();
-        let resp = tokio_test::block_on(builder);
-        println!("{:?}", resp.unwrap());
+            .price(amount("24000.0"))
+            .quantity(amount("0.001"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(replaced.new_order_response.client_order_id, "test_cancel_replace_order");
+        assert_eq!(server.requests()[0].param("cancelReplaceMode"), Some("STOP_ON_FAILURE"));
+    }
+
+    fn order_list(contingency_type: &str, list_status_type: &str, orders: usize) -> serde_json::Value {
+        json!({
+            "orderListId": 1, "contingencyType": contingency_type, "listStatusType": list_status_type,
+            "listOrderStatus": "EXECUTING", "listClientOrderId": "list", "transactionTime": 1669277163808u64,
+            "symbol": "BTCUSDT",
+            "orders": (0..orders)
+                .map(|i| json!({"symbol": "BTCUSDT", "orderId": 2 + i, "clientOrderId": format!("order{i}")}))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_place_oco_order() {
+        let (server, spot) = spot().await;
+        let mut list = order_list("OCO", "EXEC_STARTED", 2);
+        list["orderReports"] = json!([{
+            "symbol": "BTCUSDT", "orderId": 2, "orderListId": 1, "clientOrderId": "order0",
+            "transactTime": 1669277163808u64, "price": "24000.00000000", "origQty": "0.00100000",
+            "executedQty": "0.00000000", "cummulativeQuoteQty": "0.00000000", "status": "NEW",
+            "timeInForce": "GTC", "type": "STOP_LOSS", "side": "SELL", "stopPrice": "24000.00000000",
+            "workingTime": -1, "selfTradePreventionMode": "NONE"
+        }]);
+        server.mock(Method::POST, "/api/v3/orderList/oco", MockResponse::json(list));
+        let list = spot
+            .trade
+            .place_oco_order("BTCUSDT", OrderSide::Sell, amount("0.001"))
+            .above(ListOrder {
+                order_type: OrderType::LimitMaker,
+                price: Some(amount("26000")),
+                ..Default::default()
+            })
+            .below(ListOrder {
+                order_type: OrderType::StopLoss,
+                side: Some(OrderSide::Buy),
+                stop_price: Some(amount("24000")),
+                ..Default::default()
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(list.contingency_type, ContingencyType::OCO);
+        assert_eq!(list.orders.len(), 2);
+        assert_eq!(list.order_reports[0].stop_price, Some("24000".parse().unwrap()));
+        let request = &server.requests()[0];
+        assert_eq!(request.param("side"), Some("SELL"));
+        assert_eq!(request.param("quantity"), Some("0.001"));
+        assert_eq!(request.param("aboveType"), Some("LIMIT_MAKER"));
+        assert_eq!(request.param("abovePrice"), Some("26000"));
+        assert_eq!(request.param("belowType"), Some("STOP_LOSS"));
+        assert_eq!(request.param("belowStopPrice"), Some("24000"));
+        assert_eq!(request.param("belowSide"), None);
+    }
+
+    #[tokio::test]
+    async fn test_place_oto_and_otoco_orders() {
+        let (server, spot) = spot().await;
+        server.mock(Method::POST, "/api/v3/orderList/oto", MockResponse::json(order_list("OTO", "EXEC_STARTED", 2)));
+        server.mock(
+            Method::POST,
+            "/api/v3/orderList/otoco",
+            MockResponse::json(order_list("OTO", "EXEC_STARTED", 3)),
+        );
+        let working = ListOrder {
+            order_type: OrderType::Limit,
+            side: Some(OrderSide::Buy),
+            quantity: Some(amount("0.001")),
+            price: Some(amount("25000")),
+            time_in_force: Some(TimeInForce::GTC),
+            ..Default::default()
+        };
+        let list = spot
+            .trade
+            .place_oto_order("BTCUSDT")
+            .working(working.clone())
+            .pending(ListOrder {
+                order_type: OrderType::Market,
+                side: Some(OrderSide::Sell),
+                quantity: Some(amount("0.001")),
+                ..Default::default()
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(list.contingency_type, ContingencyType::OTO);
+        assert!(list.order_reports.is_empty());
+        let request = &server.requests()[0];
+        assert_eq!(request.param("workingSide"), Some("BUY"));
+        assert_eq!(request.param("workingTimeInForce"), Some("GTC"));
+        assert_eq!(request.param("pendingType"), Some("MARKET"));
+        assert_eq!(request.param("pendingQuantity"), Some("0.001"));
+
+        let list = spot
+            .trade
+            .place_otoco_order("BTCUSDT", OrderSide::Sell, amount("0.001"))
+            .working(working)
+            .pending_above(ListOrder {
+                order_type: OrderType::LimitMaker,
+                price: Some(amount("26000")),
+                ..Default::default()
+            })
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(list.orders.len(), 3);
+        let request = &server.requests()[1];
+        assert_eq!(request.path, "/api/v3/orderList/otoco");
+        assert_eq!(request.param("workingQuantity"), Some("0.001"));
+        assert_eq!(request.param("pendingSide"), Some("SELL"));
+        assert_eq!(request.param("pendingAboveType"), Some("LIMIT_MAKER"));
+        assert_eq!(request.param("pendingBelowType"), None);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order_list() {
+        let (server, spot) = spot().await;
+        server.mock(Method::DELETE, "/api/v3/orderList", MockResponse::json(order_list("OCO", "ALL_DONE", 2)));
+        let list = spot.trade.cancel_order_list("BTCUSDT").order_list_id(1).send().await.unwrap();
+        assert_eq!(list.list_status_type, OCOStatus::AllDone);
+        let request = &server.requests()[0];
+        assert_eq!(request.method, Method::DELETE);
+        assert_eq!(request.param("orderListId"), Some("1"));
     }
 }
@@ -0,0 +1,260 @@
+//! Cached exchange information of spot, USD-M and COIN-M futures symbols.
+//!
+//! A [`SymbolRegistry`] loads the exchange information of the markets it is given and indexes their symbols
+//! by name and by base and quote asset. Clones of a registry share the same symbols, a single registry can be
+//! refreshed in the background and queried from anywhere. Futures markets are added with
+//! [`SymbolRegistry::with_usd_m_futures`] and [`SymbolRegistry::with_coin_m_futures`], whose clients require the
+//! `futures_api` feature.
+//!
+//! ```rust,no_run
+//! use binance::api::Binance;
+//! use binance::symbol_registry::SymbolRegistry;
+//! use std::time::Duration;
+//!
+//! # async fn run() -> binance::errors::Result<()> {
+//! let registry = SymbolRegistry::default().with_spot(Binance::new(None, None));
+//! registry.refresh().await?;
+//! registry.spawn_refresh(Duration::from_secs(3600));
+//!
+//! let spot = registry.spot();
+//! let btc_usdt = spot.get("BTCUSDT").unwrap();
+//! let filters = btc_usdt.order_filters();
+//! let usdt_markets = spot.with_quote_asset("USDT");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::errors::*;
+use crate::futures::futures_type::{FuturesInverseType, FuturesLinearType};
+use crate::futures::general::FuturesGeneral;
+use crate::futures::rest_model::Symbol as FuturesSymbol;
+use crate::general::General;
+use crate::rest_model::{ExchangeInfoQuery, Symbol};
+
+/// A symbol of the exchange information
+pub trait ExchangeSymbol {
+    fn symbol(&self) -> &str;
+    fn base_asset(&self) -> &str;
+    fn quote_asset(&self) -> &str;
+}
+
+impl ExchangeSymbol for Symbol {
+    fn symbol(&self) -> &str { &self.symbol }
+
+    fn base_asset(&self) -> &str { &self.base_asset }
+
+    fn quote_asset(&self) -> &str { &self.quote_asset }
+}
+
+impl ExchangeSymbol for FuturesSymbol {
+    fn symbol(&self) -> &str { &self.symbol }
+
+    fn base_asset(&self) -> &str { &self.base_asset }
+
+    fn quote_asset(&self) -> &str { &self.quote_asset }
+}
+
+/// Symbols of a market indexed by name and by base and quote asset, names and assets are case insensitive
+#[derive(Debug)]
+pub struct SymbolIndex<S> {
+    symbols: Vec<S>,
+    by_symbol: HashMap<String, usize>,
+    by_base_asset: HashMap<String, Vec<usize>>,
+    by_quote_asset: HashMap<String, Vec<usize>>,
+}
+
+impl<S> Default for SymbolIndex<S> {
+    fn default() -> Self {
+        Self {
+            symbols: vec![],
+            by_symbol: HashMap::new(),
+            by_base_asset: HashMap::new(),
+            by_quote_asset: HashMap::new(),
+        }
+    }
+}
+
+impl<S: ExchangeSymbol> SymbolIndex<S> {
+    pub fn new(symbols: Vec<S>) -> Self {
+        let mut index = Self::default();
+        for (i, symbol) in symbols.iter().enumerate() {
+            index.by_symbol.insert(symbol.symbol().to_uppercase(), i);
+            index
+                .by_base_asset
+                .entry(symbol.base_asset().to_uppercase())
+                .or_default()
+                .push(i);
+            index
+                .by_quote_asset
+                .entry(symbol.quote_asset().to_uppercase())
+                .or_default()
+                .push(i);
+        }
+        index.symbols = symbols;
+        index
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&S> {
+        self.by_symbol.get(&symbol.to_uppercase()).map(|&i| &self.symbols[i])
+    }
+
+    /// Symbols trading `asset` as base asset, e.g. BTCUSDT and BTCBUSD for BTC
+    pub fn with_base_asset(&self, asset: &str) -> Vec<&S> { self.lookup(&self.by_base_asset, asset) }
+
+    /// Symbols trading `asset` as quote asset, e.g. BTCUSDT and ETHUSDT for USDT
+    pub fn with_quote_asset(&self, asset: &str) -> Vec<&S> { self.lookup(&self.by_quote_asset, asset) }
+
+    pub fn symbols(&self) -> &[S] { &self.symbols }
+
+    pub fn len(&self) -> usize { self.symbols.len() }
+
+    pub fn is_empty(&self) -> bool { self.symbols.is_empty() }
+
+    fn lookup(&self, index: &HashMap<String, Vec<usize>>, asset: &str) -> Vec<&S> {
+        index
+            .get(&asset.to_uppercase())
+            .map(|symbols| symbols.iter().map(|&i| &self.symbols[i]).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct Symbols {
+    spot: Arc<SymbolIndex<Symbol>>,
+    usd_m_futures: Arc<SymbolIndex<FuturesSymbol>>,
+    coin_m_futures: Arc<SymbolIndex<FuturesSymbol>>,
+}
+
+/// Registry of the symbols of spot, USD-M and COIN-M futures, only the markets it is given are loaded
+#[derive(Clone, Default)]
+pub struct SymbolRegistry {
+    spot: Option<(General, ExchangeInfoQuery)>,
+    usd_m_futures: Option<Arc<FuturesGeneral<FuturesLinearType>>>,
+    coin_m_futures: Option<Arc<FuturesGeneral<FuturesInverseType>>>,
+    symbols: Arc<RwLock<Symbols>>,
+}
+
+impl SymbolRegistry {
+    /// Loads spot symbols with this client
+    pub fn with_spot(self, general: General) -> Self { self.with_spot_query(general, ExchangeInfoQuery::default()) }
+
+    /// Loads the spot symbols matching the query with this client, e.g. only the symbols with a permission
+    pub fn with_spot_query(mut self, general: General, query: ExchangeInfoQuery) -> Self {
+        self.spot = Some((general, query));
+        self
+    }
+
+    /// Loads USD-M futures symbols with this client
+    pub fn with_usd_m_futures(mut self, general: FuturesGeneral<FuturesLinearType>) -> Self {
+        self.usd_m_futures = Some(Arc::new(general));
+        self
+    }
+
+    /// Loads COIN-M futures symbols with this client
+    pub fn with_coin_m_futures(mut self, general: FuturesGeneral<FuturesInverseType>) -> Self {
+        self.coin_m_futures = Some(Arc::new(general));
+        self
+    }
+
+    /// Spot symbols of the last refresh
+    pub fn spot(&self) -> Arc<SymbolIndex<Symbol>> { self.symbols.read().unwrap().spot.clone() }
+
+    /// USD-M futures symbols of the last refresh
+    pub fn usd_m_futures(&self) -> Arc<SymbolIndex<FuturesSymbol>> {
+        self.symbols.read().unwrap().usd_m_futures.clone()
+    }
+
+    /// COIN-M futures symbols of the last refresh
+    pub fn coin_m_futures(&self) -> Arc<SymbolIndex<FuturesSymbol>> {
+        self.symbols.read().unwrap().coin_m_futures.clone()
+    }
+
+    /// Loads the exchange information of every market, the symbols of a market are replaced once it is loaded
+    pub async fn refresh(&self) -> Result<()> {
+        if let Some((general, query)) = &self.spot {
+            let info = general.exchange_info_with(query).await?;
+            self.symbols.write().unwrap().spot = Arc::new(SymbolIndex::new(info.symbols));
+        }
+        if let Some(general) = &self.usd_m_futures {
+            let info = general.exchange_info().await?;
+            self.symbols.write().unwrap().usd_m_futures = Arc::new(SymbolIndex::new(info.symbols));
+        }
+        if let Some(general) = &self.coin_m_futures {
+            let info = general.exchange_info().await?;
+            self.symbols.write().unwrap().coin_m_futures = Arc::new(SymbolIndex::new(info.symbols));
+        }
+        Ok(())
+    }
+
+    /// Refreshes the registry now and then every `period` until the task is aborted.
+    /// A failed refresh keeps the symbols of the previous one.
+    pub fn spawn_refresh(&self, period: Duration) -> JoinHandle<()> {
+        let registry = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                let _ = registry.refresh().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::rest_model::ExchangeInformation;
+
+    #[test]
+    fn symbols_are_indexed_by_name_and_asset() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("test_data/exchangeInfo.json");
+        let info: ExchangeInformation = serde_json::from_str(&std::fs::read_to_string(d).unwrap()).unwrap();
+        let index = SymbolIndex::new(info.symbols);
+
+        let symbol = index.get("ethbtc").unwrap();
+        assert_eq!(symbol.symbol, "ETHBTC");
+        assert!(index.get("UNKNOWN").is_none());
+        assert!(index
+            .with_base_asset("ETH")
+            .iter()
+            .all(|symbol| symbol.base_asset == "ETH"));
+        assert!(index
+            .with_quote_asset("btc")
+            .iter()
+            .any(|symbol| symbol.symbol == "ETHBTC"));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn refresh_loads_the_queried_symbols() {
+        use crate::api::Binance;
+        use crate::mock::MockServer;
+        use crate::rest_model::SymbolPermission;
+
+        let server = MockServer::start().await;
+        let query = ExchangeInfoQuery {
+            symbols: vec!["BTCUSDT".to_string()],
+            permissions: vec![],
+            show_permission_sets: Some(true),
+        };
+        let registry =
+            SymbolRegistry::default().with_spot_query(Binance::new_with_config(None, None, &server.config()), query);
+        assert!(registry.spot().is_empty());
+
+        registry.refresh().await.unwrap();
+        let symbol = registry.spot().get("BTCUSDT").cloned().unwrap();
+        assert!(symbol.has_permission(&SymbolPermission::Spot));
+        let request = &server.requests()[0];
+        assert_eq!(request.param("symbols"), Some(r#"["BTCUSDT"]"#));
+        assert_eq!(request.param("showPermissionSets"), Some("true"));
+    }
+}
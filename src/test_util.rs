@@ -0,0 +1,34 @@
+//! Fixtures shared by the unit tests
+
+#[cfg(feature = "mock")]
+use crate::api::Binance;
+#[cfg(all(feature = "mock", feature = "futures_api"))]
+use crate::futures::{account::FuturesAccount, futures_type::FuturesType};
+#[cfg(feature = "mock")]
+use crate::mock::{MockRequest, MockServer, MOCK_SECRET_KEY};
+use crate::rest_model::Amount;
+#[cfg(feature = "mock")]
+use crate::Spot;
+
+/// Amount parsed from its decimal representation, whether amounts are floats or decimals
+pub(crate) fn amount(value: &str) -> Amount { value.parse().unwrap() }
+
+/// Numeric parameter of a request received by the mock server
+#[cfg(feature = "mock")]
+pub(crate) fn param(request: &MockRequest, name: &str) -> Option<u64> { request.param(name).map(|v| v.parse().unwrap()) }
+
+/// Mock server and a spot client authenticated against it
+#[cfg(feature = "mock")]
+pub(crate) async fn spot() -> (MockServer, Spot) {
+    let server = MockServer::start().await;
+    let spot = Binance::new_with_config(Some(server.api_key()), Some(MOCK_SECRET_KEY.into()), &server.config());
+    (server, spot)
+}
+
+/// Mock server and a futures account client authenticated against it
+#[cfg(all(feature = "mock", feature = "futures_api"))]
+pub(crate) async fn account<T: FuturesType>() -> (MockServer, FuturesAccount<T>) {
+    let server = MockServer::start().await;
+    let account = Binance::new_with_config(Some(server.api_key()), Some(MOCK_SECRET_KEY.into()), &server.config());
+    (server, account)
+}
@@ -0,0 +1,134 @@
+//! Synchronisation of request timestamps with the server time
+//!
+//! Signed requests are rejected with a -1021 error when their timestamp is outside of the `recvWindow`,
+//! which happens on hosts whose clock drifts. A [`TimeSync`] measures the offset between the local clock
+//! and the server time, and clients using it correct the timestamp of signed requests with this offset.
+//!
+//! The offset is measured on the first signed request and again once the sync interval elapsed, or after
+//! a -1021 error. It is the offset of the local clock, so the same `TimeSync` serves spot and futures clients.
+//!
+//! ```rust,no_run
+//! use binance::{account::*, api::*, config::*, time_sync::*};
+//! # tokio_test::block_on(async {
+//! let conf = Config::default().set_time_sync(TimeSync::new(DEFAULT_SYNC_INTERVAL));
+//! let account: Account = Binance::new_with_config(None, None, &conf);
+//! account.get_account().await.unwrap();
+//! # });
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+
+/// Default interval between two measures of the clock offset
+pub static DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Default)]
+struct SyncState {
+    /// Server time minus local time, in milliseconds
+    offset: i64,
+    rtt: Duration,
+    last_sync: Option<Instant>,
+}
+
+/// Shared clock offset, clones use the same measure
+#[derive(Clone)]
+pub struct TimeSync {
+    interval: Duration,
+    state: Arc<Mutex<SyncState>>,
+}
+
+impl fmt::Debug for TimeSync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("TimeSync")
+            .field("interval", &self.interval)
+            .field("offset", &state.offset)
+            .field("rtt", &state.rtt)
+            .finish()
+    }
+}
+
+impl PartialEq for TimeSync {
+    fn eq(&self, other: &Self) -> bool { Arc::ptr_eq(&self.state, &other.state) }
+}
+
+impl Eq for TimeSync {}
+
+impl Default for TimeSync {
+    fn default() -> Self { Self::new(DEFAULT_SYNC_INTERVAL) }
+}
+
+impl TimeSync {
+    /// New time sync measuring the offset every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            state: Arc::new(Mutex::new(SyncState::default())),
+        }
+    }
+
+    pub fn interval(&self) -> Duration { self.interval }
+
+    /// Server time minus local time in milliseconds, `None` until measured
+    pub fn offset(&self) -> Option<i64> {
+        let state = self.state.lock().unwrap();
+        state.last_sync.map(|_| state.offset)
+    }
+
+    /// Round trip time of the last measure, `None` until measured
+    pub fn rtt(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap();
+        state.last_sync.map(|_| state.rtt)
+    }
+
+    /// Estimated server time in milliseconds
+    pub fn timestamp(&self) -> u64 { (Utc::now().timestamp_millis() + self.state.lock().unwrap().offset) as u64 }
+
+    /// Whether the offset must be measured before signing a request
+    pub(crate) fn is_stale(&self) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .last_sync
+            .is_none_or(|last_sync| last_sync.elapsed() >= self.interval)
+    }
+
+    /// Forces a new measure before the next signed request
+    pub(crate) fn invalidate(&self) { self.state.lock().unwrap().last_sync = None; }
+
+    /// Records a measure, `sent` and `received` being the local times in milliseconds around the request
+    /// which returned `server_time`
+    pub(crate) fn record(&self, sent: i64, received: i64, server_time: i64) {
+        let mut state = self.state.lock().unwrap();
+        // the server time is assumed to be read halfway through the round trip
+        state.offset = server_time - (sent + received) / 2;
+        state.rtt = Duration::from_millis((received - sent).max(0) as u64);
+        state.last_sync = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_is_measured_at_half_the_round_trip() {
+        let time_sync = TimeSync::default();
+        assert!(time_sync.is_stale());
+        assert_eq!(time_sync.offset(), None);
+
+        let now = Utc::now().timestamp_millis();
+        time_sync.record(now - 100, now, now + 5_000);
+        assert!(!time_sync.is_stale());
+        assert_eq!(time_sync.offset(), Some(5_050));
+        assert_eq!(time_sync.rtt(), Some(Duration::from_millis(100)));
+        let drift = time_sync.timestamp() as i64 - Utc::now().timestamp_millis();
+        assert!((5_000..=5_100).contains(&drift), "{drift}");
+
+        time_sync.invalidate();
+        assert!(time_sync.is_stale());
+    }
+}
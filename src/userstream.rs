@@ -1,6 +1,17 @@
+use std::time::Duration;
+
+use futures::future::{BoxFuture, Future};
+use futures::stream::BoxStream;
+use futures::{FutureExt, StreamExt};
+use tokio::sync::oneshot;
+use tokio::time::{Instant, Interval};
+
 use crate::client::*;
+use crate::config::Config;
 use crate::errors::*;
 use crate::rest_model::*;
+use crate::websockets::{ReconnectOptions, WebSockets};
+use crate::ws_model::WebsocketEvent;
 
 static USER_DATA_STREAM: &str = "/api/v3/userDataStream";
 
@@ -50,3 +61,334 @@ impl UserStream {
         self.client.delete(USER_DATA_STREAM, listen_key, None).await
     }
 }
+
+/// Interval at which the listen key of a `UserDataSession` is kept alive, keys expire after 60mn
+pub static KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Managed user data stream, which creates the listen key, connects the websocket and keeps the key alive.
+/// The key is transparently recreated and the websocket reconnected when it expires.
+/// # Examples
+/// ```rust,no_run
+/// use binance::{api::*, config::*, userstream::*};
+/// use futures::StreamExt;
+/// # tokio_test::block_on(async {
+/// let conf = Config::default();
+/// let user_stream: UserStream = Binance::new_with_config(Some("YOUR_API_KEY".into()), None, &conf);
+/// let mut events = UserDataSession::new(user_stream, conf)
+///     .into_stream_until(async {
+///         tokio::signal::ctrl_c().await.unwrap();
+///     })
+///     .await
+///     .unwrap();
+/// while let Some(event) = events.next().await {
+///     println!("{event:?}");
+/// }
+/// # });
+/// ```
+pub struct UserDataSession {
+    user_stream: UserStream,
+    conf: Config,
+    keep_alive_interval: Duration,
+    reconnect: ReconnectOptions,
+}
+
+impl UserDataSession {
+    pub fn new(user_stream: UserStream, conf: Config) -> Self {
+        Self {
+            user_stream,
+            conf,
+            keep_alive_interval: KEEP_ALIVE_INTERVAL,
+            reconnect: ReconnectOptions::default(),
+        }
+    }
+
+    /// Sets the listen key keep alive interval, defaults to `KEEP_ALIVE_INTERVAL`
+    pub fn set_keep_alive_interval(mut self, keep_alive_interval: Duration) -> Self {
+        self.keep_alive_interval = keep_alive_interval;
+        self
+    }
+
+    /// Sets the reconnection policy of the websocket
+    pub fn set_reconnect(mut self, reconnect: ReconnectOptions) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Starts the session and returns its events
+    pub async fn into_stream(self) -> Result<BoxStream<'static, Result<WebsocketEvent>>> {
+        self.into_stream_until(futures::future::pending()).await
+    }
+
+    /// Starts the session and returns its events until `shutdown` completes,
+    /// at which point the websocket is closed and the listen key invalidated.
+    /// The stream ends with an error if the listen key cannot be recreated.
+    pub async fn into_stream_until<F>(self, shutdown: F) -> Result<BoxStream<'static, Result<WebsocketEvent>>>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let connection = SessionConnection::open(&self.user_stream, &self.conf, &self.reconnect).await?;
+        let period = self.keep_alive_interval;
+        let state = SessionState {
+            session: self,
+            connection,
+            keep_alive: tokio::time::interval_at(Instant::now() + period, period),
+            shutdown: shutdown.boxed(),
+        };
+        Ok(futures::stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            let event = state.next_event().await?;
+            match event {
+                Ok(event) => Some((Ok(event), Some(state))),
+                // the session cannot be recovered
+                Err(e @ SessionError::Fatal(_)) => Some((Err(e.into()), None)),
+                Err(e) => Some((Err(e.into()), Some(state))),
+            }
+        })
+        .boxed())
+    }
+}
+
+/// Listen key and websocket of a session
+struct SessionConnection {
+    listen_key: String,
+    events: BoxStream<'static, Result<WebsocketEvent>>,
+    close: Option<oneshot::Sender<()>>,
+}
+
+impl SessionConnection {
+    async fn open(user_stream: &UserStream, conf: &Config, reconnect: &ReconnectOptions) -> Result<Self> {
+        let listen_key = user_stream.start().await?.listen_key;
+        let mut ws = WebSockets::new_stream(conf.clone()).with_reconnect(reconnect.clone());
+        ws.connect(&listen_key).await?;
+        let (close, closed) = oneshot::channel();
+        Ok(Self {
+            listen_key,
+            events: ws.into_stream_until(closed.map(|_| ())),
+            close: Some(close),
+        })
+    }
+
+    /// Closes the websocket gracefully
+    async fn close(&mut self) {
+        if let Some(close) = self.close.take() {
+            let _ = close.send(());
+            while self.events.next().await.is_some() {}
+        }
+    }
+}
+
+enum SessionError {
+    Recoverable(Error),
+    Fatal(Error),
+}
+
+impl From<SessionError> for Error {
+    fn from(e: SessionError) -> Self {
+        match e {
+            SessionError::Recoverable(e) | SessionError::Fatal(e) => e,
+        }
+    }
+}
+
+struct SessionState {
+    session: UserDataSession,
+    connection: SessionConnection,
+    keep_alive: Interval,
+    shutdown: BoxFuture<'static, ()>,
+}
+
+impl SessionState {
+    /// Next event of the session, `None` once shut down
+    async fn next_event(&mut self) -> Option<core::result::Result<WebsocketEvent, SessionError>> {
+        loop {
+            tokio::select! {
+                _ = &mut self.shutdown => {
+                    self.connection.close().await;
+                    let _ = self.session.user_stream.close(&self.connection.listen_key).await;
+                    return None;
+                }
+                _ = self.keep_alive.tick() => {
+                    match self.session.user_stream.keep_alive(&self.connection.listen_key).await {
+                        Ok(_) => continue,
+                        Err(Error::InvalidListenKey(_)) => {
+                            if let Err(e) = self.renew().await {
+                                return Some(Err(SessionError::Fatal(e)));
+                            }
+                        }
+                        Err(e) => return Some(Err(SessionError::Recoverable(e))),
+                    }
+                }
+                event = self.connection.events.next() => {
+                    return match event {
+                        Some(Ok(WebsocketEvent::ListenKeyExpired(expired))) => match self.renew().await {
+                            Ok(()) => Some(Ok(WebsocketEvent::ListenKeyExpired(expired))),
+                            Err(e) => Some(Err(SessionError::Fatal(e))),
+                        },
+                        Some(Ok(event)) => Some(Ok(event)),
+                        Some(Err(e)) => Some(Err(SessionError::Recoverable(e))),
+                        // the websocket gave up reconnecting
+                        None => match self.renew().await {
+                            Ok(()) => continue,
+                            Err(e) => Some(Err(SessionError::Fatal(e))),
+                        },
+                    };
+                }
+            }
+        }
+    }
+
+    /// Replaces the listen key and the websocket
+    async fn renew(&mut self) -> Result<()> {
+        let connection =
+            SessionConnection::open(&self.session.user_stream, &self.session.conf, &self.session.reconnect).await?;
+        let mut previous = std::mem::replace(&mut self.connection, connection);
+        previous.close().await;
+        self.keep_alive.reset();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::amount;
+    use crate::ws_model::WebsocketEvent;
+
+    #[test]
+    fn user_data_events() {
+        let expired = r#"{"e":"listenKeyExpired","E":1576653824250,"listenKey":"OfYGbUzi3PraNagEkdKuFwUHn48brFsItTdsuiIXrucEvD0rhRXZ7I6URWfE8YE8"}"#;
+        let Ok(WebsocketEvent::ListenKeyExpired(event)) = serde_json::from_str(expired) else {
+            panic!("listenKeyExpired event not parsed");
+        };
+        assert_eq!(event.event_time, 1576653824250);
+        assert!(event.listen_key.starts_with("OfYGbUzi"));
+
+        let balance = r#"{"e":"balanceUpdate","E":1573200697110,"a":"BTC","d":"100.00000000","T":1573200697068}"#;
+        let Ok(WebsocketEvent::BalanceUpdate(event)) = serde_json::from_str(balance) else {
+            panic!("balanceUpdate event not parsed");
+        };
+        assert_eq!(event.asset, "BTC");
+        assert_eq!(event.delta, amount("100"));
+    }
+
+    #[cfg(feature = "mock")]
+    mod session {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        use futures::StreamExt;
+        use reqwest::Method;
+        use serde_json::json;
+        use tokio::time::timeout;
+
+        use crate::api::Binance;
+        use crate::mock::{MockResponse, MockServer};
+        use crate::userstream::{UserDataSession, UserStream, KEEP_ALIVE_INTERVAL, USER_DATA_STREAM};
+        use crate::ws_model::WebsocketEvent;
+
+        /// Hands out `key1`, `key2`... as listen keys
+        fn mock_listen_keys(server: &MockServer) {
+            let created = AtomicUsize::new(0);
+            server.mock_fn(Method::POST, USER_DATA_STREAM, move |_| {
+                let count = created.fetch_add(1, Ordering::SeqCst) + 1;
+                MockResponse::json(json!({ "listenKey": format!("key{count}") }))
+            });
+        }
+
+        /// Listen keys of the user data stream requests sent with `method`
+        fn listen_keys(server: &MockServer, method: Method) -> Vec<String> {
+            server
+                .requests()
+                .into_iter()
+                .filter(|request| request.method == method && request.path == USER_DATA_STREAM)
+                .map(|request| request.param("listenKey").unwrap_or_default().to_string())
+                .collect()
+        }
+
+        fn session(server: &MockServer, keep_alive_interval: Duration) -> UserDataSession {
+            let conf = server.config();
+            let user_stream: UserStream = Binance::new_with_config(Some(server.api_key()), None, &conf);
+            UserDataSession::new(user_stream, conf).set_keep_alive_interval(keep_alive_interval)
+        }
+
+        #[tokio::test]
+        async fn listen_key_is_kept_alive_every_interval() {
+            let server = MockServer::start().await;
+            mock_listen_keys(&server);
+            let mut events = session(&server, Duration::from_millis(100))
+                .into_stream()
+                .await
+                .unwrap();
+            assert!(listen_keys(&server, Method::PUT).is_empty());
+
+            // nothing is pushed, polling the stream only drives the keep alive
+            assert!(timeout(Duration::from_millis(350), events.next()).await.is_err());
+            let keep_alives = listen_keys(&server, Method::PUT);
+            assert!((2..=3).contains(&keep_alives.len()), "{keep_alives:?}");
+            assert!(keep_alives.iter().all(|key| key == "key1"));
+            assert_eq!(listen_keys(&server, Method::POST).len(), 1);
+        }
+
+        #[tokio::test]
+        async fn listen_key_is_renewed_when_expired() {
+            let server = MockServer::start().await;
+            mock_listen_keys(&server);
+            let mut events = session(&server, Duration::from_millis(100))
+                .into_stream()
+                .await
+                .unwrap();
+
+            server.push_event(json!({ "e": "listenKeyExpired", "E": 1576653824250u64, "listenKey": "key1" }));
+            let event = events.next().await.unwrap().unwrap();
+            assert!(matches!(event, WebsocketEvent::ListenKeyExpired(_)));
+            assert_eq!(listen_keys(&server, Method::POST).len(), 2);
+
+            // events of the new websocket are forwarded and the new key is kept alive
+            server.push_event(json!({ "e": "balanceUpdate", "E": 1573200697110u64, "a": "BTC", "d": "100.00000000", "T": 1573200697068u64 }));
+            let event = events.next().await.unwrap().unwrap();
+            assert!(matches!(event, WebsocketEvent::BalanceUpdate(_)));
+            assert!(timeout(Duration::from_millis(250), events.next()).await.is_err());
+            let keep_alives = listen_keys(&server, Method::PUT);
+            assert!(!keep_alives.is_empty());
+            assert!(keep_alives.iter().all(|key| key == "key2"));
+        }
+
+        #[tokio::test]
+        async fn listen_key_is_renewed_when_keep_alive_is_rejected() {
+            let server = MockServer::start().await;
+            mock_listen_keys(&server);
+            server.mock_once(
+                Method::PUT,
+                USER_DATA_STREAM,
+                MockResponse::error(-1125, "This listenKey does not exist."),
+            );
+            let mut events = session(&server, Duration::from_millis(100))
+                .into_stream()
+                .await
+                .unwrap();
+
+            assert!(timeout(Duration::from_millis(250), events.next()).await.is_err());
+            assert_eq!(listen_keys(&server, Method::POST).len(), 2);
+            let keep_alives = listen_keys(&server, Method::PUT);
+            assert_eq!(keep_alives[0], "key1");
+            assert!(keep_alives[1..].iter().all(|key| key == "key2"));
+        }
+
+        #[tokio::test]
+        async fn shutdown_closes_the_listen_key() {
+            let server = MockServer::start().await;
+            mock_listen_keys(&server);
+            let (shutdown, stopped) = tokio::sync::oneshot::channel::<()>();
+            let mut events = session(&server, KEEP_ALIVE_INTERVAL)
+                .into_stream_until(async {
+                    let _ = stopped.await;
+                })
+                .await
+                .unwrap();
+
+            shutdown.send(()).unwrap();
+            assert!(timeout(Duration::from_secs(5), events.next()).await.unwrap().is_none());
+            assert_eq!(listen_keys(&server, Method::DELETE), vec!["key1".to_string()]);
+            assert!(listen_keys(&server, Method::PUT).is_empty());
+        }
+    }
+}
@@ -22,6 +22,9 @@ where
     Ok(qs::to_string(&payload)?)
 }
 
+/// Builds the query of a signed request, with the local time as timestamp
+///
+/// The timestamp is refreshed when the request is signed, with the server time for clients configured with a `TimeSync`
 pub fn build_signed_request(
     parameters: impl IntoIterator<Item = (impl AsRef<str>, impl AsRef<str>)>,
     recv_window: u64,
@@ -46,6 +49,7 @@ pub fn build_signed_request(
     Ok(s)
 }
 
+/// Builds the query of a signed request from a payload, see [`build_signed_request`]
 pub fn build_signed_request_p<S>(payload: S, recv_window: u64) -> Result<String>
 where
     S: serde::Serialize,
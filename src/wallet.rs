@@ -291,13 +291,13 @@ impl Wallet {
     /// ```rust,no_run
     /// use binance::{api::*, wallet::*, config::*, rest_model::*};
     /// let wallet: Wallet = Binance::new_with_env(&Config::testnet());
-    /// let records = tokio_test::block_on(wallet.universal_transfer("BTC".to_string(), 1.0, None, None, UniversalTransferType::FundingMain));
+    /// let records = tokio_test::block_on(wallet.universal_transfer("BTC".to_string(), "1.0".parse::<Amount>().unwrap(), None, None, UniversalTransferType::FundingMain));
     /// assert!(records.is_ok(), "{:?}", records);
     /// ```
     pub async fn universal_transfer(
         &self,
         asset: String,
-        amount: f64,
+        amount: Amount,
         from_symbol: Option<String>,
         to_symbol: Option<String>,
         transfer_type: UniversalTransferType,
@@ -1,8 +1,16 @@
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{BuildHasher, Hasher};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-use futures::StreamExt;
-use serde_json::from_str;
+use futures::future::{BoxFuture, Future};
+use futures::stream::BoxStream;
+use futures::{FutureExt, SinkExt, StreamExt};
+use serde_json::{from_str, json, Value};
 use tokio::net::TcpStream;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
 use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::WebSocketStream;
@@ -11,6 +19,8 @@ use url::Url;
 
 use crate::config::Config;
 use crate::errors::*;
+use crate::rest_model::KlineInterval;
+use crate::ws_model::{QueryResult, WebsocketEvent};
 
 pub static STREAM_ENDPOINT: &str = "stream";
 pub static WS_ENDPOINT: &str = "ws";
@@ -31,7 +41,7 @@ pub fn agg_trade_stream(symbol: &str) -> String { format!("{symbol}@aggTrade") }
 
 pub fn trade_stream(symbol: &str) -> String { format!("{symbol}@trade") }
 
-pub fn kline_stream(symbol: &str, interval: &str) -> String { format!("{symbol}@kline_{interval}") }
+pub fn kline_stream(symbol: &str, interval: KlineInterval) -> String { format!("{symbol}@kline_{interval}") }
 
 pub fn book_ticker_stream(symbol: &str) -> String { format!("{symbol}@bookTicker") }
 
@@ -58,10 +68,434 @@ pub fn diff_book_depth_stream(symbol: &str, update_speed: u16) -> String { forma
 
 fn combined_stream(streams: Vec<String>) -> String { streams.join("/") }
 
+pub(crate) type WebSocketConnection = (WebSocketStream<MaybeTlsStream<TcpStream>>, Response);
+
+/// Reconnection policy used when a websocket connection is dropped,
+/// e.g. by the 24 hours limit Binance enforces on every connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReconnectOptions {
+    /// Delay before the first reconnection attempt
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two attempts
+    pub max_backoff: Duration,
+    /// Factor applied to the delay after each failed attempt
+    pub multiplier: f64,
+    /// Fraction of the delay randomly added or removed, between 0 and 1
+    pub jitter: f64,
+    /// Consecutive failed attempts after which the connection is given up, unlimited if `None`
+    pub max_retries: Option<u32>,
+}
+
+impl Default for ReconnectOptions {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_retries: None,
+        }
+    }
+}
+
+impl ReconnectOptions {
+    pub fn set_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn set_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn set_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn set_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn set_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Delay to wait before the given attempt, starting at 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let base = (self.initial_backoff.as_secs_f64() * self.multiplier.max(1.0).powi(exponent))
+            .min(self.max_backoff.as_secs_f64());
+        let spread = base * self.jitter * (2.0 * random_unit() - 1.0);
+        Duration::from_secs_f64((base + spread).max(0.0))
+    }
+}
+
+/// Uniform value in [0, 1) drawn from the randomly seeded std hasher
+fn random_unit() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Connection lifecycle notifications, sent to the handler registered with `on_connection_event`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The connection was opened by `connect` or `connect_multiple`
+    Connected,
+    /// The connection was dropped by the server or the network
+    Disconnected { reason: String },
+    /// A reconnection attempt will be made after `delay`
+    Reconnecting { attempt: u32, delay: Duration },
+    /// The connection was restored to the same streams after `attempts` attempts
+    Reconnected { attempts: u32 },
+}
+
+/// Control frame sent to the server by a `WebSocketControl`
+pub(crate) struct Command {
+    method: &'static str,
+    params: Vec<Value>,
+    reply: oneshot::Sender<Result<Option<Value>>>,
+}
+
+/// Request sent on the current connection and waiting for its reply
+struct PendingCommand {
+    method: &'static str,
+    params: Vec<Value>,
+    reply: Option<oneshot::Sender<Result<Option<Value>>>>,
+}
+
+/// Handle to send SUBSCRIBE, UNSUBSCRIBE, LIST_SUBSCRIPTIONS, SET_PROPERTY and GET_PROPERTY requests
+/// to an open websocket.
+/// Replies are read by the connection, so the event loop or the event stream has to be running
+/// for the returned futures to complete.
+#[derive(Clone, Debug)]
+pub struct WebSocketControl {
+    commands: UnboundedSender<Command>,
+}
+
+impl WebSocketControl {
+    async fn request(&self, method: &'static str, params: Vec<Value>) -> Result<Option<Value>> {
+        let (reply, answer) = oneshot::channel();
+        self.commands
+            .send(Command { method, params, reply })
+            .map_err(|_| Error::Msg("The websocket was dropped".to_string()))?;
+        answer
+            .await
+            .map_err(|_| Error::Msg(format!("The connection was closed before the {method} reply")))?
+    }
+
+    /// Subscribe to additional streams, kept across reconnections
+    pub async fn subscribe(&self, streams: Vec<String>) -> Result<()> {
+        self.request(SUBSCRIBE, streams.into_iter().map(Value::String).collect())
+            .await
+            .map(|_| ())
+    }
+
+    /// Unsubscribe from streams, kept across reconnections
+    pub async fn unsubscribe(&self, streams: Vec<String>) -> Result<()> {
+        self.request(UNSUBSCRIBE, streams.into_iter().map(Value::String).collect())
+            .await
+            .map(|_| ())
+    }
+
+    /// Streams the connection is currently subscribed to
+    pub async fn list_subscriptions(&self) -> Result<Vec<String>> {
+        let result = self.request(LIST_SUBSCRIPTIONS, vec![]).await?;
+        Ok(serde_json::from_value(result.unwrap_or_default())?)
+    }
+
+    /// Set a connection property, e.g. `combined` to receive combined stream payloads
+    pub async fn set_property(&self, property: &str, value: bool) -> Result<()> {
+        self.request(SET_PROPERTY, vec![Value::from(property), Value::from(value)])
+            .await
+            .map(|_| ())
+    }
+
+    /// Get a connection property, e.g. `combined`
+    pub async fn get_property(&self, property: &str) -> Result<Value> {
+        let result = self.request(GET_PROPERTY, vec![Value::from(property)]).await?;
+        Ok(result.unwrap_or_default())
+    }
+}
+
+static SUBSCRIBE: &str = "SUBSCRIBE";
+static UNSUBSCRIBE: &str = "UNSUBSCRIBE";
+static LIST_SUBSCRIPTIONS: &str = "LIST_SUBSCRIPTIONS";
+static SET_PROPERTY: &str = "SET_PROPERTY";
+static GET_PROPERTY: &str = "GET_PROPERTY";
+
+/// Connection state shared by the spot and futures websockets
+pub(crate) struct ConnectionState<'a> {
+    url: Option<Url>,
+    reconnect: Option<ReconnectOptions>,
+    lifecycle_handler: Option<Box<dyn FnMut(ConnectionEvent) + 'a + Send>>,
+    commands: UnboundedSender<Command>,
+    command_receiver: UnboundedReceiver<Command>,
+    pending: HashMap<u64, PendingCommand>,
+    next_id: u64,
+    /// streams subscribed by the connection url
+    url_streams: BTreeSet<String>,
+    /// streams currently subscribed, including live subscriptions
+    streams: BTreeSet<String>,
+    /// properties set on the connection, e.g. `combined`
+    properties: BTreeMap<String, Value>,
+}
+
+impl<'a> ConnectionState<'a> {
+    pub(crate) fn new() -> Self {
+        let (commands, command_receiver) = unbounded_channel();
+        Self {
+            url: None,
+            reconnect: None,
+            lifecycle_handler: None,
+            commands,
+            command_receiver,
+            pending: HashMap::new(),
+            next_id: 1,
+            url_streams: BTreeSet::new(),
+            streams: BTreeSet::new(),
+            properties: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn set_reconnect(&mut self, options: Option<ReconnectOptions>) { self.reconnect = options; }
+
+    pub(crate) fn set_lifecycle_handler<Callback>(&mut self, handler: Callback)
+    where
+        Callback: FnMut(ConnectionEvent) + 'a + Send,
+    {
+        self.lifecycle_handler = Some(Box::new(handler));
+    }
+
+    pub(crate) fn control(&self) -> WebSocketControl {
+        WebSocketControl {
+            commands: self.commands.clone(),
+        }
+    }
+
+    fn notify(&mut self, event: ConnectionEvent) {
+        if let Some(handler) = self.lifecycle_handler.as_mut() {
+            handler(event);
+        }
+    }
+
+    pub(crate) async fn connect(
+        &mut self,
+        socket: &mut Option<WebSocketConnection>,
+        url: Url,
+        streams: Vec<String>,
+    ) -> Result<()> {
+        *socket = Some(handshake(&url).await?);
+        self.url = Some(url);
+        self.url_streams = streams.into_iter().collect();
+        self.streams = self.url_streams.clone();
+        self.properties.clear();
+        self.notify(ConnectionEvent::Connected);
+        Ok(())
+    }
+
+    pub(crate) async fn disconnect(&mut self, socket: &mut Option<WebSocketConnection>) -> Result<()> {
+        // forget the endpoint so that the close frame is not taken for a dropped connection
+        self.url = None;
+        if let Some(ref mut socket) = socket {
+            socket.0.close(None).await?;
+            Ok(())
+        } else {
+            Err(Error::Msg("Not able to close the connection".to_string()))
+        }
+    }
+
+    /// Waits for the next text frame, reconnecting to the same url with backoff if the connection drops
+    /// and reconnection is enabled.
+    /// Control commands are sent while waiting, and their replies are not returned.
+    /// Returns `None` if `running` was cleared while reconnecting.
+    pub(crate) async fn next_text(
+        &mut self,
+        socket: &mut Option<WebSocketConnection>,
+        running: Option<&AtomicBool>,
+    ) -> Result<Option<String>> {
+        loop {
+            let Some((ws, _)) = socket.as_mut() else {
+                return Err(Error::Msg("Not connected".to_string()));
+            };
+            let message = tokio::select! {
+                Some(command) = self.command_receiver.recv() => {
+                    let id = self.next_id;
+                    self.next_id += 1;
+                    let sent = send_request(ws, id, command.method, &command.params).await;
+                    match sent {
+                        Ok(()) => {
+                            self.pending.insert(id, PendingCommand {
+                                method: command.method,
+                                params: command.params,
+                                reply: Some(command.reply),
+                            });
+                        }
+                        Err(e) => {
+                            let _ = command.reply.send(Err(e));
+                        }
+                    }
+                    continue;
+                }
+                message = ws.next() => message,
+            };
+            let reason = match message {
+                Some(Ok(Message::Text(msg))) => {
+                    if !self.handle_reply(&msg) {
+                        return Ok(Some(msg));
+                    }
+                    continue;
+                }
+                Some(Ok(Message::Close(e))) => format!("Disconnected {e:?}"),
+                Some(Ok(_)) => continue,
+                Some(Err(e)) if self.reconnect.is_some() => e.to_string(),
+                Some(Err(e)) => return Err(e.into()),
+                None => "Disconnected, the stream ended".to_string(),
+            };
+            *socket = None;
+            for (_, pending) in self.pending.drain() {
+                if let Some(reply) = pending.reply {
+                    let _ = reply.send(Err(Error::Msg(reason.clone())));
+                }
+            }
+            if self.reconnect.is_none() || self.url.is_none() {
+                return Err(Error::Msg(reason));
+            }
+            self.notify(ConnectionEvent::Disconnected { reason });
+            if !self.reconnect(socket, running).await? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Completes the pending command a reply belongs to, returns false if the message is not a reply.
+    /// Only frames answering an outstanding request are replies, events carrying an `id` are not.
+    fn handle_reply(&mut self, msg: &str) -> bool {
+        if self.pending.is_empty() || !msg.contains("\"id\"") {
+            return false;
+        }
+        let Ok(reply) = serde_json::from_str::<QueryResult>(msg) else {
+            return false;
+        };
+        let Some(pending) = u64::try_from(reply.id).ok().and_then(|id| self.pending.remove(&id)) else {
+            return false;
+        };
+        let result = match reply.error {
+            Some(error) => Err(Error::BinanceError {
+                response: BinanceContentError::new(error.code, error.msg),
+            }),
+            None => {
+                self.track_command(pending.method, &pending.params);
+                Ok(reply.result)
+            }
+        };
+        if let Some(sender) = pending.reply {
+            let _ = sender.send(result);
+        }
+        true
+    }
+
+    /// Keeps the subscriptions and the properties of acknowledged commands, to restore them on reconnect
+    fn track_command(&mut self, method: &str, params: &[Value]) {
+        let streams = params.iter().filter_map(Value::as_str).map(str::to_string);
+        if method == SUBSCRIBE {
+            self.streams.extend(streams);
+        } else if method == UNSUBSCRIBE {
+            for stream in streams {
+                self.streams.remove(&stream);
+            }
+        } else if method == SET_PROPERTY {
+            if let [Value::String(property), value] = params {
+                self.properties.insert(property.clone(), value.clone());
+            }
+        }
+    }
+
+    /// Restores the live subscriptions which differ from the ones of the connection url,
+    /// and the properties set on the previous connection
+    async fn restore(&mut self, socket: &mut Option<WebSocketConnection>) -> Result<()> {
+        let Some((ws, _)) = socket.as_mut() else {
+            return Ok(());
+        };
+        let added: Vec<Value> = self.streams.difference(&self.url_streams).cloned().map(Value::String).collect();
+        let removed: Vec<Value> = self.url_streams.difference(&self.streams).cloned().map(Value::String).collect();
+        let properties = self
+            .properties
+            .iter()
+            .map(|(property, value)| (SET_PROPERTY, vec![Value::from(property.as_str()), value.clone()]));
+        let requests: Vec<(&'static str, Vec<Value>)> =
+            [(SUBSCRIBE, added), (UNSUBSCRIBE, removed)].into_iter().chain(properties).collect();
+        for (method, params) in requests {
+            if params.is_empty() {
+                continue;
+            }
+            let id = self.next_id;
+            self.next_id += 1;
+            send_request(ws, id, method, &params).await?;
+            self.pending.insert(id, PendingCommand {
+                method,
+                params,
+                reply: None,
+            });
+        }
+        Ok(())
+    }
+
+    async fn reconnect(&mut self, socket: &mut Option<WebSocketConnection>, running: Option<&AtomicBool>) -> Result<bool> {
+        let (Some(options), Some(url)) = (self.reconnect.clone(), self.url.clone()) else {
+            return Ok(false);
+        };
+        let mut attempt = 0;
+        loop {
+            if running.is_some_and(|r| !r.load(Ordering::Relaxed)) {
+                return Ok(false);
+            }
+            if options.max_retries.is_some_and(|max| attempt >= max) {
+                return Err(Error::Msg(format!("Unable to reconnect after {attempt} attempts")));
+            }
+            attempt += 1;
+            let delay = options.backoff(attempt);
+            self.notify(ConnectionEvent::Reconnecting { attempt, delay });
+            tokio::time::sleep(delay).await;
+            if let Ok(answer) = handshake(&url).await {
+                *socket = Some(answer);
+                if self.restore(socket).await.is_err() {
+                    *socket = None;
+                    continue;
+                }
+                self.notify(ConnectionEvent::Reconnected { attempts: attempt });
+                return Ok(true);
+            }
+        }
+    }
+}
+
+async fn send_request(
+    ws: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    id: u64,
+    method: &str,
+    params: &[Value],
+) -> Result<()> {
+    let request = json!({ "method": method, "params": params, "id": id });
+    ws.send(Message::Text(request.to_string())).await?;
+    Ok(())
+}
+
+pub(crate) async fn handshake(url: &Url) -> Result<WebSocketConnection> {
+    connect_async(url.clone())
+        .await
+        .map_err(|e| Error::Msg(format!("Error during handshake {e}")))
+}
+
 pub struct WebSockets<'a, WE> {
-    pub socket: Option<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)>,
+    pub socket: Option<WebSocketConnection>,
     handler: Box<dyn FnMut(WE) -> Result<()> + 'a + Send>,
     conf: Config,
+    state: ConnectionState<'a>,
 }
 
 impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
@@ -86,9 +520,28 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
             socket: None,
             handler: Box::new(handler),
             conf,
+            state: ConnectionState::new(),
         }
     }
 
+    /// New websocket holder without a handler, to be consumed with `into_stream`
+    pub fn new_stream(conf: Config) -> WebSockets<'a, WE> { Self::new_with_options(|_| Ok(()), conf) }
+
+    /// Reconnect to the same endpoints when the connection drops instead of ending the event loop
+    pub fn with_reconnect(mut self, options: ReconnectOptions) -> Self {
+        self.state.set_reconnect(Some(options));
+        self
+    }
+
+    /// Handler receiving connection lifecycle events (connects, drops and reconnects)
+    pub fn on_connection_event<Callback>(mut self, handler: Callback) -> Self
+    where
+        Callback: FnMut(ConnectionEvent) + 'a + Send,
+    {
+        self.state.set_lifecycle_handler(handler);
+        self
+    }
+
     /// Connect to multiple websocket endpoints
     /// N.B: WE has to be CombinedStreamEvent
     pub async fn connect_multiple(&mut self, endpoints: Vec<String>) -> Result<()> {
@@ -96,9 +549,9 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
         url.path_segments_mut()
             .map_err(|_| Error::UrlParserError(url::ParseError::RelativeUrlWithoutBase))?
             .push(STREAM_ENDPOINT);
-        url.set_query(Some(&format!("streams={}", combined_stream(endpoints))));
+        url.set_query(Some(&format!("streams={}", combined_stream(endpoints.clone()))));
 
-        self.handle_connect(url).await
+        self.state.connect(&mut self.socket, url, endpoints).await
     }
 
     /// Connect to a websocket endpoint
@@ -106,52 +559,348 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
         let wss: String = format!("{}/{}/{}", self.conf.ws_endpoint, WS_ENDPOINT, endpoint);
         let url = Url::parse(&wss)?;
 
-        self.handle_connect(url).await
+        self.state.connect(&mut self.socket, url, vec![endpoint.to_string()]).await
     }
 
-    async fn handle_connect(&mut self, url: Url) -> Result<()> {
-        match connect_async(url).await {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
+    /// Disconnect from the endpoint
+    pub async fn disconnect(&mut self) -> Result<()> { self.state.disconnect(&mut self.socket).await }
+
+    pub fn socket(&self) -> &Option<WebSocketConnection> { &self.socket }
+
+    /// Handle to manage the subscriptions of the connection while the event loop or the stream runs
+    pub fn control(&self) -> WebSocketControl { self.state.control() }
+
+    /// Dispatches events to the handler until `running` is cleared.
+    /// If reconnection is disabled, returns an error when the connection drops.
+    pub async fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
+        while running.load(Ordering::Relaxed) {
+            let Some(msg) = self.state.next_text(&mut self.socket, Some(running)).await? else {
+                break;
+            };
+            if msg.is_empty() {
+                return Ok(());
             }
-            Err(e) => Err(Error::Msg(format!("Error during handshake {e}"))),
+            let event: WE = from_str(msg.as_str())?;
+            (self.handler)(event)?;
         }
+        Ok(())
     }
 
-    /// Disconnect from the endpoint
-    pub async fn disconnect(&mut self) -> Result<()> {
-        if let Some(ref mut socket) = self.socket {
-            socket.0.close(None).await?;
-            Ok(())
-        } else {
-            Err(Error::Msg("Not able to close the connection".to_string()))
+    /// Consumes the connected websocket into a stream of events, the handler is never called.
+    /// Dropping the stream drops the connection, see `into_stream_until` to close it gracefully.
+    pub fn into_stream(self) -> BoxStream<'a, Result<WE>>
+    where
+        WE: Send + 'a,
+    {
+        self.into_stream_until(futures::future::pending())
+    }
+
+    /// Consumes the connected websocket into a stream of events which ends once `shutdown` completes,
+    /// after sending a close frame to the server.
+    /// Deserialization errors are yielded without ending the stream, connection errors end it.
+    pub fn into_stream_until<F>(self, shutdown: F) -> BoxStream<'a, Result<WE>>
+    where
+        F: Future<Output = ()> + Send + 'a,
+        WE: Send + 'a,
+    {
+        event_stream(self.socket, self.state, shutdown.boxed(), |msg: String| {
+            (!msg.is_empty()).then(|| from_str(msg.as_str()).map_err(Error::from))
+        })
+    }
+}
+
+/// Drives a connection into a stream, `parse` returns `None` to end the stream.
+pub(crate) fn event_stream<'a, E, P>(
+    socket: Option<WebSocketConnection>,
+    state: ConnectionState<'a>,
+    shutdown: BoxFuture<'a, ()>,
+    parse: P,
+) -> BoxStream<'a, Result<E>>
+where
+    E: Send + 'a,
+    P: Fn(String) -> Option<Result<E>> + Send + 'a,
+{
+    futures::stream::unfold(
+        Some((socket, state, shutdown, parse)),
+        |connection| async move {
+            let (mut socket, mut state, mut shutdown, parse) = connection?;
+            let next = tokio::select! {
+                _ = &mut shutdown => None,
+                next = state.next_text(&mut socket, None) => Some(next),
+            };
+            match next {
+                None => {
+                    let _ = state.disconnect(&mut socket).await;
+                    None
+                }
+                Some(Ok(Some(msg))) => {
+                    let event = parse(msg)?;
+                    Some((event, Some((socket, state, shutdown, parse))))
+                }
+                Some(Ok(None)) => None,
+                Some(Err(e)) => Some((Err(e), None)),
+            }
+        },
+    )
+    .boxed()
+}
+
+/// Connects to the user data stream of `listen_key`, obtained with `UserStream::start`,
+/// and returns its events as a stream
+pub async fn user_data_stream(conf: Config, listen_key: &str) -> Result<BoxStream<'static, Result<WebsocketEvent>>> {
+    let mut ws = WebSockets::new_stream(conf);
+    ws.connect(listen_key).await?;
+    Ok(ws.into_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use futures::SinkExt;
+    use serde_json::{from_str, json, Value};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::tungstenite::Message;
+    use tokio_tungstenite::WebSocketStream;
+
+    use super::*;
+
+    #[test]
+    fn backoff_grows_up_to_the_maximum() {
+        let options = ReconnectOptions::default()
+            .set_initial_backoff(Duration::from_millis(100))
+            .set_max_backoff(Duration::from_secs(1))
+            .set_jitter(0.0);
+        assert_eq!(options.backoff(1), Duration::from_millis(100));
+        assert_eq!(options.backoff(2), Duration::from_millis(200));
+        assert_eq!(options.backoff(4), Duration::from_millis(800));
+        assert_eq!(options.backoff(5), Duration::from_secs(1));
+        assert_eq!(options.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn backoff_jitter_stays_in_range() {
+        let options = ReconnectOptions::default()
+            .set_initial_backoff(Duration::from_secs(10))
+            .set_jitter(0.5);
+        for _ in 0..100 {
+            let delay = options.backoff(1);
+            assert!(delay >= Duration::from_secs(5) && delay <= Duration::from_secs(15));
         }
     }
 
-    pub fn socket(&self) -> &Option<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> { &self.socket }
+    #[tokio::test]
+    async fn reconnects_after_the_server_closes_the_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for i in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                ws.send(Message::Text(i.to_string())).await.unwrap();
+                if i == 0 {
+                    ws.close(None).await.unwrap();
+                } else {
+                    futures::future::pending::<()>().await;
+                }
+            }
+        });
 
-    pub async fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
-        while running.load(Ordering::Relaxed) {
-            if let Some((ref mut socket, _)) = self.socket {
-                // TODO: return error instead of panic?
-                let message = socket.next().await.unwrap()?;
-
-                match message {
-                    Message::Text(msg) => {
-                        if msg.is_empty() {
-                            return Ok(());
-                        }
-                        let event: WE = from_str(msg.as_str())?;
-                        (self.handler)(event)?;
-                    }
-                    Message::Ping(_) | Message::Pong(_) | Message::Binary(_) | Message::Frame(_) => {}
-                    Message::Close(e) => {
-                        return Err(Error::Msg(format!("Disconnected {e:?}")));
+        let running = Arc::new(AtomicBool::new(true));
+        let received = Arc::new(Mutex::new(vec![]));
+        let lifecycle = Arc::new(Mutex::new(vec![]));
+        let conf = Config::default().set_ws_endpoint(format!("ws://{addr}"));
+        let mut ws: WebSockets<'_, u32> = {
+            let running = running.clone();
+            let received = received.clone();
+            let lifecycle = lifecycle.clone();
+            WebSockets::new_with_options(
+                move |event| {
+                    let mut received = received.lock().unwrap();
+                    received.push(event);
+                    if received.len() == 2 {
+                        running.store(false, Ordering::Relaxed);
                     }
-                }
+                    Ok(())
+                },
+                conf,
+            )
+            .with_reconnect(ReconnectOptions::default().set_initial_backoff(Duration::from_millis(10)))
+            .on_connection_event(move |event| lifecycle.lock().unwrap().push(event))
+        };
+
+        ws.connect("btcusdt@trade").await.unwrap();
+        tokio::time::timeout(Duration::from_secs(10), ws.event_loop(&running))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(*received.lock().unwrap(), vec![0, 1]);
+        let lifecycle = lifecycle.lock().unwrap();
+        assert_eq!(lifecycle[0], ConnectionEvent::Connected);
+        assert!(matches!(lifecycle[1], ConnectionEvent::Disconnected { .. }));
+        assert!(matches!(lifecycle[2], ConnectionEvent::Reconnecting { attempt: 1, .. }));
+        assert_eq!(lifecycle[3], ConnectionEvent::Reconnected { attempts: 1 });
+    }
+
+    #[tokio::test]
+    async fn returns_an_error_when_the_connection_drops_without_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.close(None).await.unwrap();
+        });
+
+        let conf = Config::default().set_ws_endpoint(format!("ws://{addr}"));
+        let mut ws: WebSockets<'_, u32> = WebSockets::new_with_options(|_| Ok(()), conf);
+        ws.connect("btcusdt@trade").await.unwrap();
+        let running = AtomicBool::new(true);
+        assert!(ws.event_loop(&running).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn stream_ends_gracefully_on_shutdown() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.send(Message::Text("1".to_string())).await.unwrap();
+            ws.send(Message::Text("x".to_string())).await.unwrap();
+            ws.send(Message::Text("2".to_string())).await.unwrap();
+            // wait for the client to close the connection
+            matches!(ws.next().await, Some(Ok(Message::Close(_))))
+        });
+
+        let conf = Config::default().set_ws_endpoint(format!("ws://{addr}"));
+        let mut ws: WebSockets<'_, u32> = WebSockets::new_stream(conf);
+        ws.connect("btcusdt@trade").await.unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let mut stream = ws.into_stream_until(shutdown_rx.map(|_| ()));
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        assert!(stream.next().await.unwrap().is_err());
+        assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+        shutdown_tx.send(()).unwrap();
+        assert!(stream.next().await.is_none());
+        assert!(server.await.unwrap());
+    }
+
+    async fn next_request(ws: &mut WebSocketStream<TcpStream>) -> Value {
+        loop {
+            if let Message::Text(msg) = ws.next().await.unwrap().unwrap() {
+                return from_str(&msg).unwrap();
             }
         }
-        Ok(())
+    }
+
+    #[tokio::test]
+    async fn live_subscriptions_are_correlated_and_restored_on_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let subscribe = next_request(&mut ws).await;
+            assert_eq!(subscribe["method"], "SUBSCRIBE");
+            assert_eq!(subscribe["params"], json!(["ethusdt@trade"]));
+            let list = next_request(&mut ws).await;
+            // replies may come out of order
+            let reply = json!({ "result": ["btcusdt@trade", "ethusdt@trade"], "id": list["id"] });
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+            let reply = json!({ "result": null, "id": subscribe["id"] });
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+            let property = next_request(&mut ws).await;
+            let reply = json!({ "error": { "code": 2, "msg": "Invalid property" }, "id": property["id"] });
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+            ws.close(None).await.unwrap();
+
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let subscribe = next_request(&mut ws).await;
+            assert_eq!(subscribe["method"], "SUBSCRIBE");
+            assert_eq!(subscribe["params"], json!(["ethusdt@trade"]));
+            let reply = json!({ "result": null, "id": subscribe["id"] });
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+            ws.send(Message::Text("7".to_string())).await.unwrap();
+            futures::future::pending::<()>().await;
+        });
+
+        let conf = Config::default().set_ws_endpoint(format!("ws://{addr}"));
+        let mut ws: WebSockets<'static, u32> = WebSockets::new_stream(conf)
+            .with_reconnect(ReconnectOptions::default().set_initial_backoff(Duration::from_millis(10)));
+        ws.connect_multiple(vec!["btcusdt@trade".to_string()]).await.unwrap();
+        let control = ws.control();
+        let mut stream = ws.into_stream();
+        let (events_tx, mut events) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(event) = stream.next().await {
+                events_tx.send(event.unwrap()).unwrap();
+            }
+        });
+
+        let (subscribed, subscriptions) = tokio::join!(
+            control.subscribe(vec!["ethusdt@trade".to_string()]),
+            control.list_subscriptions()
+        );
+        subscribed.unwrap();
+        assert_eq!(subscriptions.unwrap(), vec!["btcusdt@trade", "ethusdt@trade"]);
+        assert!(matches!(
+            control.get_property("unknown").await,
+            Err(Error::BinanceError { response }) if response.code == 2
+        ));
+        let event = tokio::time::timeout(Duration::from_secs(10), events.recv()).await.unwrap();
+        assert_eq!(event, Some(7));
+    }
+
+    #[tokio::test]
+    async fn events_with_an_id_are_not_replies_and_properties_are_restored_on_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let property = next_request(&mut ws).await;
+            assert_eq!(property["params"], json!(["combined", true]));
+            // an event carrying the id of the outstanding request
+            let event = json!({ "e": "trade", "id": property["id"] });
+            ws.send(Message::Text(event.to_string())).await.unwrap();
+            let reply = json!({ "result": null, "id": property["id"] });
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+            ws.close(None).await.unwrap();
+
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let property = next_request(&mut ws).await;
+            assert_eq!(property["method"], "SET_PROPERTY");
+            assert_eq!(property["params"], json!(["combined", true]));
+            let reply = json!({ "result": null, "id": property["id"] });
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+            ws.send(Message::Text(json!({ "e": "restored" }).to_string())).await.unwrap();
+            futures::future::pending::<()>().await;
+        });
+
+        let conf = Config::default().set_ws_endpoint(format!("ws://{addr}"));
+        let mut ws: WebSockets<'static, Value> = WebSockets::new_stream(conf)
+            .with_reconnect(ReconnectOptions::default().set_initial_backoff(Duration::from_millis(10)));
+        ws.connect("btcusdt@trade").await.unwrap();
+        let control = ws.control();
+        let mut stream = ws.into_stream();
+        let (events_tx, mut events) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(event) = stream.next().await {
+                events_tx.send(event.unwrap()).unwrap();
+            }
+        });
+
+        control.set_property("combined", true).await.unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(10), events.recv()).await.unwrap();
+        assert_eq!(event.unwrap()["e"], "trade");
+        let event = tokio::time::timeout(Duration::from_secs(10), events.recv()).await.unwrap();
+        assert_eq!(event.unwrap()["e"], "restored");
     }
 }
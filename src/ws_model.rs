@@ -1,4 +1,6 @@
-use crate::rest_model::{string_or_float, Asks, Bids, OrderBook, OrderSide, OrderStatus, OrderType, TimeInForce};
+use crate::rest_model::{
+    string_or_float, Amount, Asks, Bids, OrderBook, OrderSide, OrderStatus, OrderType, TimeInForce,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "e")]
@@ -23,14 +25,27 @@ pub enum WebsocketEvent {
     OrderUpdate(Box<OrderUpdate>),
     #[serde(alias = "listStatus")]
     ListOrderUpdate(Box<OrderListUpdate>),
+    #[serde(alias = "listenKeyExpired")]
+    ListenKeyExpired(Box<ListenKeyExpired>),
 }
 
+/// Reply to a SUBSCRIBE, UNSUBSCRIBE, LIST_SUBSCRIPTIONS, SET_PROPERTY or GET_PROPERTY request
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
 pub struct QueryResult {
-    pub result: Option<String>,
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
+    #[serde(default)]
+    pub error: Option<QueryError>,
     pub id: i64,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QueryError {
+    pub code: i32,
+    pub msg: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TradesEvent {
@@ -238,6 +253,9 @@ pub struct DepthOrderBookEvent {
     pub first_update_id: u64,
     #[serde(rename = "u")]
     pub final_update_id: u64,
+    /// Final update id of the previous event, only sent on futures streams
+    #[serde(rename = "pu", default)]
+    pub previous_final_update_id: Option<u64>,
     #[serde(rename = "b")]
     pub bids: Vec<Bids>,
     #[serde(rename = "a")]
@@ -346,10 +364,10 @@ pub struct EventBalance {
     pub asset: String,
     #[serde(rename = "f")]
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Amount,
     #[serde(rename = "l")]
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Amount,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -363,7 +381,7 @@ pub struct BalanceUpdate {
 
     #[serde(rename = "d")]
     #[serde(with = "string_or_float")]
-    pub delta: f64,
+    pub delta: Amount,
 
     #[serde(alias = "T")]
     pub clear_time: u64,
@@ -386,16 +404,16 @@ pub struct OrderUpdate {
     pub time_in_force: TimeInForce,
     #[serde(rename = "q")]
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Amount,
     #[serde(rename = "p")]
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Amount,
     #[serde(rename = "P")]
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Amount,
     #[serde(rename = "F")]
     #[serde(with = "string_or_float")]
-    pub iceberg_qty: f64,
+    pub iceberg_qty: Amount,
     #[serde(rename = "g")]
     pub order_list_id: i64,
     #[serde(rename = "C")]
@@ -410,16 +428,16 @@ pub struct OrderUpdate {
     pub order_id: u64,
     #[serde(rename = "l")]
     #[serde(with = "string_or_float")]
-    pub qty_last_executed: f64,
+    pub qty_last_executed: Amount,
     #[serde(rename = "z")]
     #[serde(with = "string_or_float")]
-    pub cumulative_filled_qty: f64,
+    pub cumulative_filled_qty: Amount,
     #[serde(rename = "L")]
     #[serde(with = "string_or_float")]
-    pub last_executed_price: f64,
+    pub last_executed_price: Amount,
     #[serde(rename = "n")]
     #[serde(with = "string_or_float")]
-    pub commission: f64,
+    pub commission: Amount,
     #[serde(rename = "N")]
     pub commission_asset: Option<String>,
     #[serde(rename = "T")]
@@ -438,14 +456,14 @@ pub struct OrderUpdate {
     pub order_creation_time: u64,
     #[serde(rename = "Z")]
     #[serde(with = "string_or_float")]
-    pub cumulative_quote_asset_transacted_qty: f64,
+    pub cumulative_quote_asset_transacted_qty: Amount,
     /// (i.e. lastPrice * lastQty)
     #[serde(rename = "Y")]
     #[serde(with = "string_or_float")]
-    pub last_quote_asset_transacted_qty: f64,
+    pub last_quote_asset_transacted_qty: Amount,
     #[serde(rename = "Q")]
     #[serde(with = "string_or_float")]
-    pub quote_order_qty: f64,
+    pub quote_order_qty: Amount,
 }
 
 /// For OCO Events
@@ -484,3 +502,12 @@ pub struct OrderListTransaction {
     #[serde(rename = "c")]
     pub client_order_id: String,
 }
+
+/// Sent on a user data stream once its listen key expired, no more events are sent afterwards
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKeyExpired {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    pub listen_key: String,
+}